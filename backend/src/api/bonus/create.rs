@@ -0,0 +1,202 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{bonus_rule, BonusRule, User};
+use crate::db::values::{Role, Timestamp, UID};
+
+/// Creates a bonus rule for a child.
+#[post("bonus/{user_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    user_uid: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let user_uid = user_uid.into_inner();
+    {
+        let res = execute(&mut tx, state.clone(), &req.into_inner(), &user_uid)
+            .await?;
+        Notify::MemberAndParents {
+            event: Event::BonusRuleCreated {
+                rule: res.rule.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = User::read(tx.as_mut(), user_uid)
+        .await?
+        .filter(|user| user.role == Role::Child)
+        .ok_or_else(|| api::Error::not_found("unknown user"))?;
+    state
+        .assert_role(Role::Parent)?
+        .assert_family(&user.family_uid)?;
+
+    let rule = api::argument(
+        bonus_rule::BonusRuleDescription {
+            streak: Some(1),
+            ..Default::default()
+        }
+        .merge(req.clone())
+        .merge(bonus_rule::BonusRuleDescription {
+            user_uid: Some(user.uid.clone()),
+            current: Some(0),
+            evaluated: Some(None),
+            created: Some(Timestamp::now()),
+            ..Default::default()
+        })
+        .entity(UID::new()),
+    )?;
+    if rule.amount <= 0 || rule.streak == 0 {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid rule",
+        ));
+    }
+    rule.create(tx.as_mut()).await?;
+
+    Ok(Res { rule })
+}
+
+pub type Req = bonus_rule::BonusRuleDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new rule.
+    pub rule: BonusRule,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+    use crate::db::values::{BonusKind, Period};
+
+    use super::*;
+
+    fn req() -> Req {
+        Req {
+            name: Some("All chores done".into()),
+            kind: Some(BonusKind::Chores),
+            period: Some(Period::Weekly),
+            streak: Some(4),
+            amount: Some(20),
+            ..Default::default()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &req(),
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.rule.user_uid, children.0.uid);
+        assert_eq!(res.rule.current, 0);
+        assert_eq!(
+            BonusRule::read(conn.as_mut(), &res.rule.uid).await.unwrap(),
+            Some(res.rule),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_rule() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    streak: Some(0),
+                    ..req()
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid rule"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &req(),
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
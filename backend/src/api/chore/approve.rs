@@ -0,0 +1,211 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{Chore, Transaction};
+use crate::db::values::{ChoreStatus, Role, Timestamp, TransactionType, UID};
+
+/// Approves a chore that has been marked as done, and pays the reward.
+///
+/// The reward is split between the jars of the child like other income.
+#[post("chore/{family_uid}/{chore_uid}/approve")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (family_uid, chore_uid) = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &family_uid, &chore_uid).await?;
+        Notify::MemberAndParents {
+            event: Event::ChoreApproved {
+                chore: res.chore.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: api::expect(res.chore.claimed_by.clone())?,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    family_uid: &UID,
+    chore_uid: &UID,
+) -> Result<Res, api::Error> {
+    let (state, chore) = super::load(tx, state, family_uid, chore_uid).await?;
+    let state = state.assert_role(Role::Parent)?;
+    let user_uid = match (chore.status, &chore.claimed_by) {
+        (ChoreStatus::Done, Some(user_uid)) => user_uid.clone(),
+        _ => {
+            return Err(api::Error::Static(
+                StatusCode::CONFLICT,
+                "chore not done",
+            ))
+        }
+    };
+
+    let now = Timestamp::now();
+    let transactions = Transaction::create_split_with_auto_uid(
+        tx,
+        TransactionType::Chore,
+        user_uid,
+        chore.title.clone(),
+        chore.reward,
+        now,
+    )
+    .await?;
+    let chore = Chore {
+        status: ChoreStatus::Approved,
+        approved: Some(now),
+        approved_by: Some(state.user_uid.clone()),
+        ..chore
+    };
+    chore.update(tx.as_mut()).await?;
+
+    Ok(Res {
+        chore,
+        transactions,
+    })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The approved chore.
+    pub chore: Chore,
+
+    /// The transactions paying the reward.
+    pub transactions: Vec<Transaction>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let chore = Chore {
+            status: ChoreStatus::Done,
+            claimed_by: Some(children.0.uid.clone()),
+            ..create::chore(&mut conn, &family.uid, "Dishes", 100, None)
+        };
+        chore.update(conn.as_mut()).await.unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &chore.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.chore.status, ChoreStatus::Approved);
+        assert_eq!(res.chore.approved_by, Some(parent.uid.clone()));
+        assert_eq!(res.transactions.len(), 1);
+        assert_eq!(res.transactions[0].user_uid, children.0.uid);
+        assert_eq!(
+            res.transactions[0].transaction_type,
+            TransactionType::Chore,
+        );
+        assert_eq!(res.transactions[0].amount, 100);
+        assert_eq!(
+            Chore::read(conn.as_mut(), &chore.uid).await.unwrap(),
+            Some(res.chore),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn not_done() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let chore = create::chore(&mut conn, &family.uid, "Dishes", 100, None);
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &chore.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "chore not done"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let chore = Chore {
+            status: ChoreStatus::Done,
+            claimed_by: Some(children.0.uid.clone()),
+            ..create::chore(&mut conn, &family.uid, "Dishes", 100, None)
+        };
+        chore.update(conn.as_mut()).await.unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &chore.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::Chore;
+use crate::db::values::{ChoreStatus, Role, UID};
+
+/// Claims a chore.
+///
+/// Only open chores that are either assigned to the current child or open to
+/// all children can be claimed.
+#[post("chore/{family_uid}/{chore_uid}/claim")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (family_uid, chore_uid) = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &family_uid, &chore_uid).await?;
+        Notify::Parents {
+            event: Event::ChoreClaimed {
+                chore: res.chore.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    family_uid: &UID,
+    chore_uid: &UID,
+) -> Result<Res, api::Error> {
+    let (state, chore) = super::load(tx, state, family_uid, chore_uid).await?;
+    let state = state.assert_role(Role::Child)?;
+    if chore.status != ChoreStatus::Open
+        || !chore.is_available_to(&state.user_uid)
+    {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "chore not available",
+        ));
+    }
+
+    let chore = Chore {
+        status: ChoreStatus::Claimed,
+        claimed_by: Some(state.user_uid.clone()),
+        ..chore
+    };
+    chore.update(tx.as_mut()).await?;
+
+    Ok(Res { chore })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The claimed chore.
+    pub chore: Chore,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let chore = create::chore(&mut conn, &family.uid, "Dishes", 100, None);
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &chore.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.chore.status, ChoreStatus::Claimed);
+        assert_eq!(res.chore.claimed_by, Some(children.0.uid.clone()));
+        assert_eq!(
+            Chore::read(conn.as_mut(), &chore.uid).await.unwrap(),
+            Some(res.chore),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn assigned_to_other() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let chore = create::chore(
+            &mut conn,
+            &family.uid,
+            "Dishes",
+            100,
+            Some(&children.1.uid),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &chore.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "chore not available"),
+        );
+        assert_eq!(
+            Chore::read(conn.as_mut(), &chore.uid).await.unwrap(),
+            Some(chore),
+        );
+    }
+}
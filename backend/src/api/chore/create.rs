@@ -0,0 +1,205 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{chore, Chore};
+use crate::db::values::{ChoreStatus, Role, Timestamp, UID};
+
+/// Creates a chore.
+///
+/// A chore is either assigned to a specific child, or open to all children
+/// of the family.
+#[post("chore/{family_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let family_uid = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &req.into_inner(), &family_uid)
+                .await?;
+        Notify::Family {
+            event: Event::ChoreCreated {
+                chore: res.chore.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    family_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+    super::check_assignee(
+        tx,
+        family_uid,
+        req.assignee_uid.clone().flatten().as_ref(),
+    )
+    .await?;
+
+    let chore = api::argument(
+        chore::ChoreDescription {
+            due: Some(None),
+            assignee_uid: Some(None),
+            ..Default::default()
+        }
+        .merge(req.clone())
+        .merge(chore::ChoreDescription {
+            family_uid: Some(family_uid.clone()),
+            claimed_by: Some(None),
+            status: Some(ChoreStatus::Open),
+            created: Some(Timestamp::now()),
+            approved: Some(None),
+            approved_by: Some(None),
+            template_uid: Some(None),
+            ..Default::default()
+        })
+        .entity(UID::new()),
+    )?;
+    if chore.reward < 0 {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid reward",
+        ));
+    }
+    chore.create(tx.as_mut()).await?;
+
+    Ok(Res { chore })
+}
+
+pub type Req = chore::ChoreDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new chore.
+    pub chore: Chore,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    title: Some("Wash the car".into()),
+                    reward: Some(500),
+                    assignee_uid: Some(Some(children.0.uid.clone())),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.chore.title, "Wash the car");
+        assert_eq!(res.chore.status, ChoreStatus::Open);
+        assert_eq!(res.chore.assignee_uid, Some(children.0.uid.clone()));
+        assert_eq!(
+            Chore::read(conn.as_mut(), &res.chore.uid).await.unwrap(),
+            Some(res.chore),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_assignee() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    title: Some("Wash the car".into()),
+                    reward: Some(500),
+                    assignee_uid: Some(Some(parent.uid.clone())),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown user"));
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    title: Some("Eat candy".into()),
+                    reward: Some(500),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
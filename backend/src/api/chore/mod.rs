@@ -0,0 +1,54 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::session::State;
+use crate::db::entities::{Chore, User};
+use crate::db::values::{Role, UID};
+
+pub mod approve;
+pub mod claim;
+pub mod create;
+pub mod done;
+pub mod template;
+
+/// Loads a chore and ensures that the current user belongs to its family.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `state` - The current session state.
+/// *  `family_uid` - The family of the chore.
+/// *  `chore_uid` - The unique ID of the chore.
+pub async fn load<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    family_uid: &UID,
+    chore_uid: &UID,
+) -> Result<(State, Chore), api::Error> {
+    let state = state.assert_family(family_uid)?;
+    let chore = Chore::read(tx.as_mut(), chore_uid)
+        .await?
+        .filter(|chore| &chore.family_uid == family_uid)
+        .ok_or_else(|| api::Error::not_found("unknown chore"))?;
+    Ok((state, chore))
+}
+
+/// Ensures that an assignee is a child of a family.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `family_uid` - The family.
+/// *  `assignee_uid` - The assignee, or `None` for chores open to all
+///    children.
+pub async fn check_assignee<'a>(
+    tx: &mut Tx<'a>,
+    family_uid: &UID,
+    assignee_uid: Option<&UID>,
+) -> Result<(), api::Error> {
+    if let Some(assignee_uid) = assignee_uid {
+        User::read(tx.as_mut(), assignee_uid)
+            .await?
+            .filter(|u| &u.family_uid == family_uid && u.role == Role::Child)
+            .ok_or_else(|| api::Error::not_found("unknown user"))?;
+    }
+    Ok(())
+}
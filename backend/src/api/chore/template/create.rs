@@ -0,0 +1,208 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{chore_template, ChoreTemplate};
+use crate::db::values::{Role, Timestamp, UID};
+
+/// Creates a template from which chores are generated on a schedule.
+#[post("chore/{family_uid}/template")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let family_uid = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &req.into_inner(), &family_uid)
+                .await?;
+        Notify::Parents {
+            event: Event::ChoreTemplateCreated {
+                template: res.template.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    family_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+    api::chore::check_assignee(
+        tx,
+        family_uid,
+        req.assignee_uid.clone().flatten().as_ref(),
+    )
+    .await?;
+
+    let template = api::argument(
+        chore_template::ChoreTemplateDescription {
+            assignee_uid: Some(None),
+            penalty: Some(0),
+            required: Some(false),
+            ..Default::default()
+        }
+        .merge(req.clone())
+        .merge(chore_template::ChoreTemplateDescription {
+            family_uid: Some(family_uid.clone()),
+            created: Some(Timestamp::now()),
+            generated: Some(None),
+            ..Default::default()
+        })
+        .entity(UID::new()),
+    )?;
+    if template.reward < 0 || template.penalty < 0 {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid template",
+        ));
+    }
+    template.create(tx.as_mut()).await?;
+
+    Ok(Res { template })
+}
+
+pub type Req = chore_template::ChoreTemplateDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new template.
+    pub template: ChoreTemplate,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+    use crate::db::values::Schedule;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    title: Some("Empty dishwasher".into()),
+                    reward: Some(50),
+                    assignee_uid: Some(Some(children.0.uid.clone())),
+                    schedule: Some(Schedule::Daily),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.template.title, "Empty dishwasher");
+        assert_eq!(res.template.penalty, 0);
+        assert_eq!(res.template.generated, None);
+        assert_eq!(
+            ChoreTemplate::read(conn.as_mut(), &res.template.uid)
+                .await
+                .unwrap(),
+            Some(res.template),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_penalty() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    title: Some("Mow the lawn".into()),
+                    reward: Some(500),
+                    schedule: Some("Sat".parse().unwrap()),
+                    penalty: Some(-1),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid template"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    title: Some("Eat candy".into()),
+                    reward: Some(500),
+                    schedule: Some(Schedule::Daily),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::ChoreTemplate;
+use crate::db::values::{Role, UID};
+
+/// Removes a chore template.
+///
+/// Chores already generated from the template are kept.
+#[delete("chore/{family_uid}/template/{template_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (family_uid, template_uid) = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &family_uid, &template_uid).await?;
+        Notify::Parents {
+            event: Event::ChoreTemplateRemoved {
+                template: res.template.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    family_uid: &UID,
+    template_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+    let template = ChoreTemplate::read(tx.as_mut(), template_uid)
+        .await?
+        .filter(|template| &template.family_uid == family_uid)
+        .ok_or_else(|| api::Error::not_found("unknown template"))?;
+
+    template.delete(tx.as_mut()).await?;
+
+    Ok(Res { template })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The template that was removed.
+    pub template: ChoreTemplate,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+    use crate::db::values::{Schedule, Timestamp};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let template = ChoreTemplate::new(
+            UID::new(),
+            family.uid.clone(),
+            "Empty dishwasher".into(),
+            10,
+            None,
+            Schedule::Daily,
+            0,
+            Timestamp::now(),
+            None,
+            false,
+        );
+        template.create(conn.as_mut()).await.unwrap();
+
+        {
+            let mut tx = conn.begin().await.unwrap();
+            execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &template.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        assert!(ChoreTemplate::read(conn.as_mut(), &template.uid)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[actix_rt::test]
+    async fn unknown_template() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &UID::new(),
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown template"));
+    }
+}
@@ -0,0 +1,59 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::session::State;
+use crate::db::entities::Currency;
+
+/// Lists all currencies available to families.
+#[get("currency")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    session: Session,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    State::load(&session)?;
+    {
+        let res = execute(&mut tx).await?;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(tx: &mut Tx<'a>) -> Result<Res, api::Error> {
+    let mut currencies = Currency::list(tx.as_mut()).await?;
+    currencies.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Res { currencies })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// All available currencies, ordered by name.
+    currencies: Vec<Currency>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(&mut tx).await.unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert!(res.currencies.iter().any(|c| c.name == "EUR"));
+        assert!(res.currencies.iter().any(|c| c.name == "SEK"));
+        assert!(res.currencies.iter().any(|c| c.name == "USD"));
+        assert!(res.currencies.windows(2).all(|w| w[0].name < w[1].name));
+    }
+}
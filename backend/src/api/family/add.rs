@@ -49,13 +49,16 @@ pub async fn execute<'a>(
         .assert_role(Role::Parent)?
         .assert_family(&family_uid)?;
     let user = api::argument(
-        req.user
-            .clone()
-            .merge(user::UserDescription {
-                family_uid: Some(family_uid.clone()),
-                ..Default::default()
-            })
-            .entity(UID::new()),
+        user::UserDescription {
+            birth_date: Some(None),
+            ..Default::default()
+        }
+        .merge(req.user.clone())
+        .merge(user::UserDescription {
+            family_uid: Some(family_uid.clone()),
+            ..Default::default()
+        })
+        .entity(UID::new()),
     )?;
     if user.role == Role::Parent && req.allowance.is_some() {
         return Err(api::Error::Static(
@@ -81,16 +84,24 @@ pub async fn execute<'a>(
     user.create(tx.as_mut()).await?;
     password.create(tx.as_mut()).await?;
     if let Some(allowance) = req.allowance.clone() {
-        api::argument(
-            allowance
-                .merge(allowance::AllowanceDescription {
-                    user_uid: Some(user.uid.clone()),
-                    ..Default::default()
-                })
-                .entity(UID::new()),
-        )?
-        .create(tx.as_mut())
-        .await?;
+        let allowance = api::argument(
+            allowance::AllowanceDescription {
+                ledger_uid: Some(None),
+                required_share: Some(None),
+                prorate: Some(false),
+                age_scale: Some(None),
+                ..Default::default()
+            }
+            .merge(allowance)
+            .merge(allowance::AllowanceDescription {
+                user_uid: Some(user.uid.clone()),
+                ..Default::default()
+            })
+            .entity(UID::new()),
+        )?;
+        api::ledger::check(tx, family_uid, allowance.ledger_uid.as_ref())
+            .await?;
+        allowance.create(tx.as_mut()).await?;
     }
 
     Ok(Res { user })
@@ -0,0 +1,217 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{Configuration, Currency};
+use crate::db::values::{Role, UID};
+
+/// Changes the currency used by a family.
+#[put("family/{family_uid}/currency")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let family_uid = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &req.into_inner(), &family_uid)
+                .await?;
+        Notify::Family {
+            event: Event::CurrencyChanged {
+                currency: res.currency.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    family_uid: &UID,
+) -> Result<Res, api::Error> {
+    state
+        .assert_role(Role::Parent)?
+        .assert_family(&family_uid)?;
+    let currency = Currency::read(tx.as_mut(), &req.currency)
+        .await?
+        .ok_or_else(|| api::Error::not_found("unknown currency"))?;
+
+    let configuration =
+        Configuration::new(family_uid.clone(), currency.name.clone());
+    if Configuration::read(tx.as_mut(), family_uid)
+        .await?
+        .is_some()
+    {
+        configuration.update(tx.as_mut()).await?;
+    } else {
+        configuration.create(tx.as_mut()).await?;
+    }
+
+    Ok(Res { currency })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Req {
+    /// The name of the new currency.
+    pub currency: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new currency of the family.
+    pub currency: Currency,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::configuration::FamilyConfiguration;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        for name in ["EUR", "SEK"] {
+            let res = {
+                let mut tx = conn.begin().await.unwrap();
+                let r = execute(
+                    &mut tx,
+                    State {
+                        user_uid: parent.uid.clone(),
+                        family_uid: family.uid.clone(),
+                        role: parent.role.clone(),
+                    },
+                    &Req {
+                        currency: name.into(),
+                    },
+                    &family.uid,
+                )
+                .await
+                .unwrap();
+                tx.commit().await.unwrap();
+                r
+            };
+
+            assert_eq!(res.currency.name, name);
+            let mut tx = conn.begin().await.unwrap();
+            assert_eq!(
+                FamilyConfiguration::read(&mut tx, &family.uid)
+                    .await
+                    .unwrap()
+                    .map(|c| c.currency().clone()),
+                Some(res.currency),
+            );
+        }
+    }
+
+    #[actix_rt::test]
+    async fn unknown_currency() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    currency: "unknown".into(),
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown currency"));
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_parent() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let other_family = create::family(&mut conn, "Other Family");
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    currency: "EUR".into(),
+                },
+                &other_family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid family"));
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &Req {
+                    currency: "EUR".into(),
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
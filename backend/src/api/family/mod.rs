@@ -1,3 +1,5 @@
 pub mod add;
+pub mod currency;
 pub mod register;
 pub mod remove;
+pub mod settings;
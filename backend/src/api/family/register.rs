@@ -28,14 +28,17 @@ pub async fn execute<'a>(
 ) -> Result<Res, api::Error> {
     let family = api::argument(req.family.clone().entity(UID::new()))?;
     let user = api::argument(
-        req.user
-            .clone()
-            .merge(user::UserDescription {
-                family_uid: Some(family.uid.clone()),
-                role: Some(Role::Parent),
-                ..Default::default()
-            })
-            .entity(UID::new()),
+        user::UserDescription {
+            birth_date: Some(None),
+            ..Default::default()
+        }
+        .merge(req.user.clone())
+        .merge(user::UserDescription {
+            family_uid: Some(family.uid.clone()),
+            role: Some(Role::Parent),
+            ..Default::default()
+        })
+        .entity(UID::new()),
     )?;
     let password = Password::new(
         user.uid.clone(),
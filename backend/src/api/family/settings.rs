@@ -0,0 +1,204 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{family_settings, FamilySettings};
+use crate::db::values::{Role, UID};
+
+/// The largest offset from UTC of any time zone, in minutes.
+const MAX_UTC_OFFSET: i32 = 14 * 60;
+
+/// Changes the settings of a family.
+#[put("family/{family_uid}/settings")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let family_uid = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &req.into_inner(), &family_uid)
+                .await?;
+        Notify::Family {
+            event: Event::SettingsChanged {
+                settings: res.settings.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    family_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+
+    let settings = FamilySettings::read_or_default(tx, family_uid)
+        .await?
+        .merge(
+            req.clone()
+                .merge(family_settings::FamilySettingsDescription {
+                    family_uid: Some(family_uid.clone()),
+                    ..Default::default()
+                }),
+        );
+    if settings.approvals_required < 1
+        || settings.approval_threshold.unwrap_or(0) < 0
+        || settings.request_reminder.unwrap_or(1) < 1
+        || settings.request_expiry.unwrap_or(1) < 1
+        || settings.overdraft_limit < 0
+        || settings.utc_offset.abs() > MAX_UTC_OFFSET
+    {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid settings",
+        ));
+    }
+    settings.store(tx).await?;
+
+    Ok(Res { settings })
+}
+
+pub type Req = family_settings::FamilySettingsDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new settings of the family.
+    pub settings: FamilySettings,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    approval_threshold: Some(Some(5000)),
+                    approvals_required: Some(2),
+                    request_reminder: Some(Some(3)),
+                    request_expiry: Some(Some(14)),
+                    overdraft_limit: Some(500),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            res.settings,
+            FamilySettings::new(
+                family.uid.clone(),
+                Some(5000),
+                2,
+                Some(3),
+                Some(14),
+                500,
+                0,
+            ),
+        );
+        assert_eq!(
+            FamilySettings::read(conn.as_mut(), &family.uid)
+                .await
+                .unwrap(),
+            Some(res.settings),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    approvals_required: Some(0),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid settings"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req::default(),
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
@@ -0,0 +1,287 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{FamilySettings, FineReason, Transaction, User};
+use crate::db::values::{Role, Timestamp, TransactionType, UID};
+
+/// Fines a child for one of the reasons of the family.
+///
+/// A fine never takes the balance further below zero than the overdraft
+/// limit of the family allows; larger fines are reduced accordingly.
+#[post("fine/{family_uid}/{reason_uid}/apply")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (family_uid, reason_uid) = path.into_inner();
+    {
+        let req = req.into_inner();
+        let res =
+            execute(&mut tx, state.clone(), &req, &family_uid, &reason_uid)
+                .await?;
+        Notify::MemberAndParents {
+            event: Event::FineApplied {
+                transaction: res.transaction.clone(),
+                reason: res.reason.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: req.user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    family_uid: &UID,
+    reason_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+    let reason = super::load(tx, family_uid, reason_uid).await?;
+    let user = User::read(tx.as_mut(), &req.user_uid)
+        .await?
+        .filter(|user| {
+            user.role == Role::Child && &user.family_uid == family_uid
+        })
+        .ok_or_else(|| api::Error::not_found("unknown user"))?;
+
+    let amount = req.amount.unwrap_or(reason.amount);
+    if amount <= 0 {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid amount",
+        ));
+    }
+    let settings = FamilySettings::read_or_default(tx, family_uid).await?;
+    let available = Transaction::balance(tx, &user.uid, None)
+        .await?
+        .unwrap_or(0)
+        + settings.overdraft_limit;
+    if available <= 0 {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "insufficient funds",
+        ));
+    }
+
+    let transaction = Transaction::create_with_auto_uid(
+        tx,
+        TransactionType::Fine,
+        user.uid,
+        None,
+        None,
+        reason.name.clone(),
+        -amount.min(available),
+        Timestamp::now(),
+    )
+    .await?;
+
+    Ok(Res {
+        transaction,
+        reason,
+    })
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Req {
+    /// The child to fine.
+    pub user_uid: UID,
+
+    /// The amount of the fine, if not the default amount of the reason.
+    #[serde(default)]
+    pub amount: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The transaction booking the fine.
+    pub transaction: Transaction,
+
+    /// The reason for the fine.
+    pub reason: FineReason,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let reason =
+            create::fine_reason(&mut conn, &family.uid, "Rudeness", 100);
+        let state = State {
+            user_uid: parent.uid.clone(),
+            role: parent.role.clone(),
+            family_uid: family.uid.clone(),
+        };
+        let mut tx = conn.begin().await.unwrap();
+        let balance = Transaction::balance(&mut tx, &children.0.uid, None)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &Req {
+                user_uid: children.0.uid.clone(),
+                amount: None,
+            },
+            &family.uid,
+            &reason.uid,
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.transaction.transaction_type, TransactionType::Fine);
+        assert_eq!(res.transaction.description, "Rudeness");
+        assert_eq!(res.transaction.amount, -100);
+
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &Req {
+                user_uid: children.0.uid.clone(),
+                amount: Some(balance * 2),
+            },
+            &family.uid,
+            &reason.uid,
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.transaction.amount, 100 - balance);
+        assert_eq!(
+            Transaction::balance(&mut tx, &children.0.uid, None)
+                .await
+                .unwrap(),
+            Some(0),
+        );
+
+        let err = execute(
+            &mut tx,
+            state,
+            &Req {
+                user_uid: children.0.uid.clone(),
+                amount: None,
+            },
+            &family.uid,
+            &reason.uid,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "insufficient funds"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn overdraft() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let parent = create::user(
+            &mut conn,
+            Role::Parent,
+            "Parent",
+            "parent@example.com",
+            &family.uid,
+        );
+        let child = create::user(
+            &mut conn,
+            Role::Child,
+            "Child",
+            "child@example.com",
+            &family.uid,
+        );
+        let reason =
+            create::fine_reason(&mut conn, &family.uid, "Rudeness", 300);
+        FamilySettings {
+            overdraft_limit: 200,
+            ..FamilySettings::default_for(&family.uid)
+        }
+        .create(conn.as_mut())
+        .await
+        .unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    user_uid: child.uid.clone(),
+                    amount: None,
+                },
+                &family.uid,
+                &reason.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.transaction.amount, -200);
+    }
+
+    #[actix_rt::test]
+    async fn unknown_user() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let reason =
+            create::fine_reason(&mut conn, &family.uid, "Rudeness", 100);
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    user_uid: parent.uid.clone(),
+                    amount: None,
+                },
+                &family.uid,
+                &reason.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown user"));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{fine_reason, FineReason};
+use crate::db::values::{Role, Timestamp, UID};
+
+/// Adds a reason to the catalogue of fines of a family.
+#[post("fine/{family_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let family_uid = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &req.into_inner(), &family_uid)
+                .await?;
+        Notify::Parents {
+            event: Event::FineReasonCreated {
+                reason: res.reason.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    family_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+
+    let reason = api::argument(
+        req.clone()
+            .merge(fine_reason::FineReasonDescription {
+                family_uid: Some(family_uid.clone()),
+                created: Some(Timestamp::now()),
+                ..Default::default()
+            })
+            .entity(UID::new()),
+    )?;
+    if reason.amount <= 0 {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid amount",
+        ));
+    }
+    reason.create(tx.as_mut()).await?;
+
+    Ok(Res { reason })
+}
+
+pub type Req = fine_reason::FineReasonDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new reason.
+    pub reason: FineReason,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    name: Some("Rudeness".into()),
+                    amount: Some(100),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.reason.name, "Rudeness");
+        assert_eq!(
+            FineReason::read(conn.as_mut(), &res.reason.uid)
+                .await
+                .unwrap(),
+            Some(res.reason),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_amount() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    name: Some("Rudeness".into()),
+                    amount: Some(-100),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid amount"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    name: Some("Being a parent".into()),
+                    amount: Some(100),
+                    ..Default::default()
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
@@ -0,0 +1,26 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::db::entities::FineReason;
+use crate::db::values::UID;
+
+pub mod apply;
+pub mod create;
+pub mod remove;
+
+/// Loads a fine reason of a family.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `family_uid` - The family of the reason.
+/// *  `reason_uid` - The unique ID of the reason.
+pub async fn load<'a>(
+    tx: &mut Tx<'a>,
+    family_uid: &UID,
+    reason_uid: &UID,
+) -> Result<FineReason, api::Error> {
+    FineReason::read(tx.as_mut(), reason_uid)
+        .await?
+        .filter(|reason| &reason.family_uid == family_uid)
+        .ok_or_else(|| api::Error::not_found("unknown reason"))
+}
@@ -0,0 +1,129 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::FineReason;
+use crate::db::values::{Role, UID};
+
+/// Removes a reason from the catalogue of fines of a family.
+///
+/// Fines already applied are kept.
+#[delete("fine/{family_uid}/{reason_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (family_uid, reason_uid) = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &family_uid, &reason_uid).await?;
+        Notify::Parents {
+            event: Event::FineReasonRemoved {
+                reason: res.reason.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    family_uid: &UID,
+    reason_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+    let reason = super::load(tx, family_uid, reason_uid).await?;
+
+    reason.delete(tx.as_mut()).await?;
+
+    Ok(Res { reason })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The reason that was removed.
+    pub reason: FineReason,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let reason =
+            create::fine_reason(&mut conn, &family.uid, "Rudeness", 100);
+
+        {
+            let mut tx = conn.begin().await.unwrap();
+            execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &reason.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        assert!(FineReason::read(conn.as_mut(), &reason.uid)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[actix_rt::test]
+    async fn unknown_reason() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let other = create::family(&mut conn, "Other Family");
+        let reason =
+            create::fine_reason(&mut conn, &other.uid, "Rudeness", 100);
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &reason.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown reason"));
+    }
+}
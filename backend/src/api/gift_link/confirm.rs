@@ -0,0 +1,258 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{GiftLink, Transaction};
+use crate::db::values::{Role, Timestamp, TransactionType, UID};
+
+/// Confirms receipt of a gift recorded through a gift link.
+///
+/// The gift is booked with the name of the giver, and the link is removed.
+/// Recorded gifts can be confirmed after the link has expired.
+#[post("gift-link/{family_uid}/{link_uid}/confirm")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (family_uid, link_uid) = path.into_inner();
+    {
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &req.into_inner(),
+            &family_uid,
+            &link_uid,
+        )
+        .await?;
+        Notify::MemberAndParents {
+            event: Event::GiftReceived {
+                link: res.link.clone(),
+                transactions: res.transactions.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: res.link.user_uid.clone(),
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    family_uid: &UID,
+    link_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+    let (link, user) = super::load(tx, family_uid, link_uid).await?;
+    let (giver, recorded_amount) = match (&link.giver, link.amount) {
+        (Some(giver), Some(amount)) if link.is_recorded() => (giver, amount),
+        _ => {
+            return Err(api::Error::Static(
+                StatusCode::CONFLICT,
+                "gift not recorded",
+            ))
+        }
+    };
+    let amount = req.amount.unwrap_or(recorded_amount);
+    if amount <= 0 {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid amount",
+        ));
+    }
+
+    let description = match link.message.as_deref() {
+        Some(message) if !message.is_empty() => {
+            format!("Gift from {}: {}", giver, message)
+        }
+        _ => format!("Gift from {}", giver),
+    };
+    let transactions = api::transaction::book(
+        tx,
+        TransactionType::Gift,
+        user.uid,
+        None,
+        None,
+        description,
+        amount,
+        Timestamp::now(),
+    )
+    .await?;
+    link.delete(tx.as_mut()).await?;
+
+    Ok(Res { link, transactions })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Req {
+    /// The amount actually received, or `None` to book the recorded amount.
+    #[serde(default)]
+    pub amount: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The link, which no longer exists.
+    pub link: GiftLink,
+
+    /// The booked transactions.
+    ///
+    /// The gift is split between the jars of the child, which generates one
+    /// transaction per jar.
+    pub transactions: Vec<Transaction>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let link = GiftLink {
+            giver: Some("Grandma".into()),
+            message: Some("Happy birthday!".into()),
+            amount: Some(5000),
+            recorded: Some(Timestamp::now()),
+            ..create::gift_link(
+                &mut conn,
+                &children.0.uid,
+                "2000-01-01T00:00:00Z".parse().unwrap(),
+            )
+        };
+        link.update(conn.as_mut()).await.unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req { amount: Some(4000) },
+                &family.uid,
+                &link.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.link, link);
+        assert_eq!(res.transactions.len(), 1);
+        let transaction = res.transactions[0].clone();
+        assert_eq!(transaction.transaction_type, TransactionType::Gift);
+        assert_eq!(transaction.user_uid, children.0.uid);
+        assert_eq!(transaction.amount, 4000);
+        assert_eq!(
+            transaction.description,
+            "Gift from Grandma: Happy birthday!",
+        );
+        assert_eq!(
+            GiftLink::read(conn.as_mut(), &link.uid).await.unwrap(),
+            None,
+        );
+    }
+
+    #[actix_rt::test]
+    async fn not_recorded() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let link = create::gift_link(
+            &mut conn,
+            &children.0.uid,
+            "2000-01-01T00:00:00Z".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req { amount: None },
+                &family.uid,
+                &link.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "gift not recorded"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn unknown_link() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let other_family = create::family(&mut conn, "Other Family");
+        let other_child = create::user(
+            &mut conn,
+            Role::Child,
+            "Other User",
+            "other@example.com",
+            &other_family.uid,
+        );
+        let link = create::gift_link(
+            &mut conn,
+            &other_child.uid,
+            "2000-01-01T00:00:00Z".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req { amount: None },
+                &family.uid,
+                &link.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown link"));
+    }
+}
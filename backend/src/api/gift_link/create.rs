@@ -0,0 +1,212 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{GiftLink, User};
+use crate::db::values::{Role, Timestamp, UID};
+
+/// Creates a link through which a relative can announce a gift for a child.
+///
+/// The unique ID of the link is the token to share with the relative.
+#[post("gift-link/{family_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    family_uid: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    {
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &req.into_inner(),
+            &family_uid.into_inner(),
+        )
+        .await?;
+        Notify::Parents {
+            event: Event::GiftLinkCreated {
+                link: res.link.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    family_uid: &UID,
+) -> Result<Res, api::Error> {
+    let state = state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+    let user = User::read(tx.as_mut(), &req.user_uid)
+        .await?
+        .filter(|user| {
+            user.role == Role::Child && &user.family_uid == family_uid
+        })
+        .ok_or_else(|| api::Error::not_found("unknown user"))?;
+    let now = Timestamp::now();
+    if req.expires.0 <= now.0 {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid expiry",
+        ));
+    }
+
+    let link = GiftLink::new(
+        UID::new(),
+        user.uid,
+        req.expires,
+        None,
+        None,
+        None,
+        None,
+        now,
+        Some(state.user_uid),
+    );
+    link.create(tx.as_mut()).await?;
+
+    Ok(Res { link })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Req {
+    /// The child receiving the gift.
+    pub user_uid: UID,
+
+    /// The timestamp after which the link can no longer be used.
+    pub expires: Timestamp,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The generated link.
+    pub link: GiftLink,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::api::tests;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let expires = Timestamp::from(Timestamp::now().0 + Duration::days(7));
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    user_uid: children.0.uid.clone(),
+                    expires,
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.link.user_uid, children.0.uid);
+        assert_eq!(res.link.expires, expires);
+        assert_eq!(res.link.created_by, Some(parent.uid));
+        assert!(!res.link.is_recorded());
+        assert_eq!(
+            GiftLink::read(conn.as_mut(), &res.link.uid).await.unwrap(),
+            Some(res.link),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_expiry() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    user_uid: children.0.uid.clone(),
+                    expires: "2000-01-01T00:00:00Z".parse().unwrap(),
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid expiry"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    user_uid: children.0.uid.clone(),
+                    expires: Timestamp::from(
+                        Timestamp::now().0 + Duration::days(7),
+                    ),
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
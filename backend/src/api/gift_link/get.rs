@@ -0,0 +1,110 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::db::entities::Family;
+use crate::db::values::{Timestamp, UID};
+
+/// Retrieves information about a gift link.
+///
+/// This action is public, so only the names of the child and the family are
+/// revealed.
+#[get("gift/{link_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let link_uid = path.into_inner();
+    {
+        let res = execute(&mut tx, &link_uid).await?;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    link_uid: &UID,
+) -> Result<Res, api::Error> {
+    let (link, user) = super::resolve(tx, link_uid).await?;
+    let family =
+        api::expect(Family::read(tx.as_mut(), &user.family_uid).await?)?;
+
+    Ok(Res {
+        child: user.name,
+        family: family.name,
+        expires: link.expires,
+        recorded: link.is_recorded(),
+    })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The name of the child receiving the gift.
+    child: String,
+
+    /// The name of the family.
+    family: String,
+
+    /// The timestamp after which the link can no longer be used.
+    expires: Timestamp,
+
+    /// Whether a gift has already been recorded through the link.
+    recorded: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let link = create::gift_link(
+            &mut conn,
+            &children.0.uid,
+            Timestamp::from(Timestamp::now().0 + Duration::days(7)),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(&mut tx, &link.uid).await.unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.child, children.0.name);
+        assert_eq!(res.family, family.name);
+        assert_eq!(res.expires, link.expires);
+        assert!(!res.recorded);
+    }
+
+    #[actix_rt::test]
+    async fn expired() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (_, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let link = create::gift_link(
+            &mut conn,
+            &children.0.uid,
+            "2000-01-01T00:00:00Z".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(&mut tx, &link.uid).await.err().unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown link"));
+    }
+}
@@ -0,0 +1,55 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::db::entities::{GiftLink, User};
+use crate::db::values::{Timestamp, UID};
+
+pub mod confirm;
+pub mod create;
+pub mod get;
+pub mod record;
+pub mod remove;
+
+/// Loads a gift link for a child of a family.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `family_uid` - The family of the child.
+/// *  `link_uid` - The unique ID of the link.
+pub async fn load<'a>(
+    tx: &mut Tx<'a>,
+    family_uid: &UID,
+    link_uid: &UID,
+) -> Result<(GiftLink, User), api::Error> {
+    if let Some(link) = GiftLink::read(tx.as_mut(), link_uid).await? {
+        if let Some(user) = User::read(tx.as_mut(), &link.user_uid)
+            .await?
+            .filter(|user| &user.family_uid == family_uid)
+        {
+            return Ok((link, user));
+        }
+    }
+    Err(api::Error::not_found("unknown link"))
+}
+
+/// Resolves the token of a gift link for the public endpoints.
+///
+/// Expired links are treated as unknown.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `link_uid` - The unique ID of the link.
+pub async fn resolve<'a>(
+    tx: &mut Tx<'a>,
+    link_uid: &UID,
+) -> Result<(GiftLink, User), api::Error> {
+    if let Some(link) = GiftLink::read(tx.as_mut(), link_uid)
+        .await?
+        .filter(|link| !link.is_expired(Timestamp::now()))
+    {
+        if let Some(user) = User::read(tx.as_mut(), &link.user_uid).await? {
+            return Ok((link, user));
+        }
+    }
+    Err(api::Error::not_found("unknown link"))
+}
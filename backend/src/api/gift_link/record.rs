@@ -0,0 +1,223 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::db::entities::{GiftLink, User};
+use crate::db::values::{Timestamp, UID};
+
+/// The maximum length of the name of a giver.
+const MAX_GIVER_LENGTH: usize = 100;
+
+/// The maximum length of the message from a giver.
+const MAX_MESSAGE_LENGTH: usize = 1000;
+
+/// Records a gift announced through a gift link.
+///
+/// This action is public. The gift is booked once a parent confirms receipt
+/// of the money.
+#[post("gift/{link_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    req: web::Json<Req>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let link_uid = path.into_inner();
+    {
+        let res = execute(&mut tx, &req.into_inner(), &link_uid).await?;
+        let user =
+            api::expect(User::read(tx.as_mut(), &res.link.user_uid).await?)?;
+        Notify::Parents {
+            event: Event::GiftLinkRecorded {
+                link: res.link.clone(),
+            },
+            family: user.family_uid,
+        }
+        .send_system(&mut tx, &channel)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    req: &Req,
+    link_uid: &UID,
+) -> Result<Res, api::Error> {
+    let (link, _) = super::resolve(tx, link_uid).await?;
+    if link.is_recorded() {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "gift already recorded",
+        ));
+    }
+    let giver = req.giver.trim();
+    if giver.is_empty() || giver.chars().count() > MAX_GIVER_LENGTH {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid name",
+        ));
+    }
+    let message = req.message.trim();
+    if message.chars().count() > MAX_MESSAGE_LENGTH {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid message",
+        ));
+    }
+    if req.amount <= 0 {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid amount",
+        ));
+    }
+
+    let link = GiftLink {
+        giver: Some(giver.into()),
+        message: Some(message.into()),
+        amount: Some(req.amount),
+        recorded: Some(Timestamp::now()),
+        ..link
+    };
+    link.update(tx.as_mut()).await?;
+
+    Ok(Res { link })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Req {
+    /// The name of the giver.
+    pub giver: String,
+
+    /// A message for the child.
+    #[serde(default)]
+    pub message: String,
+
+    /// The amount of the gift.
+    pub amount: i64,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The link with the recorded gift.
+    pub link: GiftLink,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (_, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let link = create::gift_link(
+            &mut conn,
+            &children.0.uid,
+            Timestamp::from(Timestamp::now().0 + Duration::days(7)),
+        );
+        let req = Req {
+            giver: " Grandma ".into(),
+            message: "Happy birthday!".into(),
+            amount: 5000,
+        };
+
+        let (res, err) = {
+            let mut tx = conn.begin().await.unwrap();
+            let res = execute(&mut tx, &req, &link.uid).await.unwrap();
+            let err = execute(&mut tx, &req, &link.uid).await.err().unwrap();
+            tx.commit().await.unwrap();
+            (res, err)
+        };
+
+        assert_eq!(res.link.giver.as_deref(), Some("Grandma"));
+        assert_eq!(res.link.message.as_deref(), Some("Happy birthday!"));
+        assert_eq!(res.link.amount, Some(5000));
+        assert!(res.link.is_recorded());
+        assert_eq!(
+            GiftLink::read(conn.as_mut(), &link.uid).await.unwrap(),
+            Some(res.link),
+        );
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "gift already recorded"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_amount() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (_, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let link = create::gift_link(
+            &mut conn,
+            &children.0.uid,
+            Timestamp::from(Timestamp::now().0 + Duration::days(7)),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                &Req {
+                    giver: "Grandma".into(),
+                    message: String::new(),
+                    amount: 0,
+                },
+                &link.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid amount"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn expired() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (_, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let link = create::gift_link(
+            &mut conn,
+            &children.0.uid,
+            "2000-01-01T00:00:00Z".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                &Req {
+                    giver: "Grandma".into(),
+                    message: String::new(),
+                    amount: 5000,
+                },
+                &link.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown link"));
+    }
+}
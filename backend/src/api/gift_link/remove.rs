@@ -0,0 +1,135 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::GiftLink;
+use crate::db::values::{Role, UID};
+
+/// Removes a gift link, whether or not a gift was recorded through it.
+#[delete("gift-link/{family_uid}/{link_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (family_uid, link_uid) = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &family_uid, &link_uid).await?;
+        Notify::Parents {
+            event: Event::GiftLinkRemoved {
+                link: res.link.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    family_uid: &UID,
+    link_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+    let (link, _) = super::load(tx, family_uid, link_uid).await?;
+
+    link.delete(tx.as_mut()).await?;
+
+    Ok(Res { link })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The link that was removed.
+    pub link: GiftLink,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let link = create::gift_link(
+            &mut conn,
+            &children.0.uid,
+            "2000-01-01T00:00:00Z".parse().unwrap(),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &link.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.link, link);
+        assert_eq!(
+            GiftLink::read(conn.as_mut(), &link.uid).await.unwrap(),
+            None,
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let link = create::gift_link(
+            &mut conn,
+            &children.0.uid,
+            "2000-01-01T00:00:00Z".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+                &link.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::Goal;
+use crate::db::values::{Timestamp, UID};
+
+/// Marks a savings goal as completed.
+///
+/// Any earmarked amount is released.
+#[post("goal/{user_uid}/{goal_uid}/complete")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, goal_uid) = path.into_inner();
+    {
+        let res = execute(&mut tx, state.clone(), &user_uid, &goal_uid).await?;
+        Notify::MemberAndParents {
+            event: Event::GoalCompleted {
+                goal: res.goal.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    user_uid: &UID,
+    goal_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = super::user(tx, state, user_uid).await?;
+    let goal = Goal::read(tx.as_mut(), goal_uid)
+        .await?
+        .filter(|goal| goal.user_uid == user.uid)
+        .ok_or_else(|| api::Error::not_found("unknown goal"))?;
+    if goal.completed.is_some() {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "goal already completed",
+        ));
+    }
+
+    let goal = Goal {
+        completed: Some(Timestamp::now()),
+        ..goal
+    };
+    goal.update(tx.as_mut()).await?;
+
+    Ok(Res { goal })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The completed goal.
+    pub goal: Goal,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Role;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let goal = create::goal(&mut conn, &children.0.uid, "Bicycle", 1000);
+
+        {
+            let mut tx = conn.begin().await.unwrap();
+            execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &children.0.uid,
+                &goal.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let mut tx = conn.begin().await.unwrap();
+        assert!(Goal::read(tx.as_mut(), &goal.uid)
+            .await
+            .unwrap()
+            .unwrap()
+            .completed
+            .is_some());
+        assert_eq!(
+            Goal::read_for_user(&mut tx, &children.0.uid).await.unwrap(),
+            Vec::new(),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let other_family = create::family(&mut conn, "Other Family");
+        let other_child = create::user(
+            &mut conn,
+            Role::Child,
+            "Other User",
+            "other@email.com",
+            &other_family.uid,
+        );
+        let goal = create::goal(&mut conn, &other_child.uid, "Bicycle", 1000);
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &other_child.uid,
+                &goal.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid family"));
+    }
+}
@@ -0,0 +1,231 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{goal, Goal};
+use crate::db::values::UID;
+
+/// Creates a savings goal for a child.
+#[post("goal/{user_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    user_uid: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let user_uid = user_uid.into_inner();
+    {
+        let res = execute(&mut tx, state.clone(), &req.into_inner(), &user_uid)
+            .await?;
+        Notify::MemberAndParents {
+            event: Event::GoalCreated {
+                goal: res.goal.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = super::user(tx, state, user_uid).await?;
+
+    let goal = api::argument(
+        goal::GoalDescription {
+            url: Some(None),
+            deadline: Some(None),
+            earmarked: Some(0),
+            ..Default::default()
+        }
+        .merge(req.clone())
+        .merge(goal::GoalDescription {
+            user_uid: Some(user.uid.clone()),
+            completed: Some(None),
+            ..Default::default()
+        })
+        .entity(UID::new()),
+    )?;
+    super::check_earmark(tx, &goal).await?;
+    goal.create(tx.as_mut()).await?;
+
+    Ok(Res { goal })
+}
+
+pub type Req = goal::GoalDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new goal.
+    pub goal: Goal,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Role;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &Req {
+                    name: Some("Bicycle".into()),
+                    amount: Some(1000),
+                    earmarked: Some(20),
+                    ..Default::default()
+                },
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.goal.user_uid, children.0.uid);
+        assert_eq!(res.goal.earmarked, 20);
+        assert_eq!(res.goal.completed, None);
+        assert_eq!(
+            Goal::read(conn.as_mut(), &res.goal.uid).await.unwrap(),
+            Some(res.goal),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn success_parent() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    name: Some("Bicycle".into()),
+                    amount: Some(1000),
+                    ..Default::default()
+                },
+                &children.1.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.goal.user_uid, children.1.uid);
+        assert_eq!(res.goal.earmarked, 0);
+    }
+
+    #[actix_rt::test]
+    async fn insufficient_funds() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, _, _, _) = tests::populate(&mut conn).unwrap();
+        let child = create::user(
+            &mut conn,
+            Role::Child,
+            "Child",
+            "child@example.com",
+            &family.uid,
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: child.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: child.role.clone(),
+                },
+                &Req {
+                    name: Some("Bicycle".into()),
+                    amount: Some(1000),
+                    earmarked: Some(20),
+                    ..Default::default()
+                },
+                &child.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "insufficient funds"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &Req {
+                    name: Some("Bicycle".into()),
+                    amount: Some(1000),
+                    ..Default::default()
+                },
+                &children.1.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid user"));
+    }
+}
@@ -0,0 +1,360 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::session::State;
+use crate::db::entities::{Allowance, Goal, Transaction, User};
+use crate::db::values::{Role, Schedule, Timestamp, UID};
+
+pub mod complete;
+pub mod create;
+pub mod update;
+
+/// The number of days of history used to estimate spending.
+const SPENDING_DAYS: i64 = 28;
+
+/// The number of weeks over which income is estimated.
+///
+/// Income is estimated per year rather than per week, so that small amounts
+/// are not lost to rounding.
+const WEEKS_PER_YEAR: i64 = 52;
+
+/// The progress towards a goal.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Progress {
+    /// The goal.
+    pub goal: Goal,
+
+    /// The amount saved towards this goal.
+    ///
+    /// This is the earmarked amount, plus the part of the free balance not
+    /// used by goals with earlier deadlines. It never exceeds the goal amount.
+    pub saved: i64,
+
+    /// The projected completion time.
+    ///
+    /// This is estimated from the allowance and the recent spending of the
+    /// user. If the user is not expected to ever reach this goal, this is
+    /// `None`.
+    pub projected: Option<Timestamp>,
+}
+
+/// Loads a user and ensures that the current user may manage their goals.
+///
+/// Parents may manage the goals of all children in their family, and children
+/// may manage their own goals.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `state` - The current session state.
+/// *  `user_uid` - The user owning the goals.
+pub async fn user<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    user_uid: &UID,
+) -> Result<User, api::Error> {
+    let user = User::read(tx.as_mut(), user_uid)
+        .await?
+        .ok_or_else(|| api::Error::not_found("unknown user"))?;
+    let state = state.assert_family(&user.family_uid)?;
+    if state.role == Role::Child {
+        state.assert_user(&user.uid)?;
+    }
+    if user.role != Role::Child {
+        Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "only children have goals",
+        ))
+    } else {
+        Ok(user)
+    }
+}
+
+/// Ensures that the earmarked amount of a goal is valid.
+///
+/// The earmarked amount may not be negative, may not exceed the goal amount,
+/// and the total earmarked amount of all active goals may not exceed the
+/// balance of the user.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `goal` - The goal to check.
+pub async fn check_earmark<'a>(
+    tx: &mut Tx<'a>,
+    goal: &Goal,
+) -> Result<(), api::Error> {
+    if goal.amount <= 0 || goal.earmarked < 0 || goal.earmarked > goal.amount {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid amount",
+        ));
+    }
+    let balance = Transaction::balance(tx, &goal.user_uid, None)
+        .await?
+        .unwrap_or(0);
+    let earmarked = Goal::read_for_user(tx, &goal.user_uid)
+        .await?
+        .iter()
+        .filter(|g| g.uid != goal.uid)
+        .map(|g| g.earmarked)
+        .sum::<i64>()
+        + goal.earmarked;
+    if goal.earmarked > 0 && earmarked > balance {
+        Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "insufficient funds",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Calculates the progress towards all active goals of a user.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `user_uid` - The user UID.
+/// *  `now` - The current time.
+pub async fn progress<'a>(
+    tx: &mut Tx<'a>,
+    user_uid: &UID,
+    now: Timestamp,
+) -> Result<Vec<Progress>, DatabaseError> {
+    let goals = Goal::read_for_user(tx, user_uid).await?;
+    let balance = Transaction::balance(tx, user_uid, None).await?.unwrap_or(0);
+    let earmarked = goals.iter().map(|g| g.earmarked).sum::<i64>();
+
+    // The expected net income per year
+    let allowance = Allowance::read_for_user(tx, user_uid)
+        .await?
+        .iter()
+        .filter(|a| a.ledger_uid.is_none())
+        .map(|a| yearly(a.amount as i64, &a.schedule))
+        .sum::<i64>();
+    let spending = Transaction::spending(
+        tx,
+        user_uid,
+        now.0
+            .checked_sub_signed(chrono::Duration::days(SPENDING_DAYS))
+            .unwrap_or(now.0)
+            .into(),
+    )
+    .await?;
+    let rate = allowance - spending * 7 * WEEKS_PER_YEAR / SPENDING_DAYS;
+
+    let mut free = (balance - earmarked).max(0);
+    let mut remaining = 0;
+    Ok(goals
+        .into_iter()
+        .map(|goal| {
+            let used = free.min(goal.amount - goal.earmarked).max(0);
+            free -= used;
+            let saved = goal.earmarked + used;
+            remaining += goal.amount - saved;
+            let projected = if remaining <= 0 {
+                Some(now)
+            } else if rate > 0 {
+                let weeks = (remaining * WEEKS_PER_YEAR + rate - 1) / rate;
+                now.0
+                    .checked_add_signed(chrono::Duration::weeks(weeks))
+                    .map(Timestamp::from)
+            } else {
+                None
+            };
+            Progress {
+                goal,
+                saved,
+                projected,
+            }
+        })
+        .collect())
+}
+
+/// The amount paid per year by an allowance.
+///
+/// # Arguments
+/// *  `amount` - The amount of each payment.
+/// *  `schedule` - The schedule of the payments.
+fn yearly(amount: i64, schedule: &Schedule) -> i64 {
+    match schedule {
+        Schedule::Daily => amount * 7 * WEEKS_PER_YEAR,
+        Schedule::Weekly(_) => amount * WEEKS_PER_YEAR,
+        Schedule::Monthly(_) => amount * 12,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::TransactionType;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn progress_projected() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, _, _, _) = tests::populate(&mut conn).unwrap();
+        let child = create::user(
+            &mut conn,
+            Role::Child,
+            "Child",
+            "child@example.com",
+            &family.uid,
+        );
+        let now = Timestamp::now();
+        create::transaction(
+            &mut conn,
+            TransactionType::Gift,
+            &child.uid,
+            "gift",
+            300,
+            now,
+        );
+        create::transaction(
+            &mut conn,
+            TransactionType::Request,
+            &child.uid,
+            "sweets",
+            -40,
+            now,
+        );
+        create::allowance(&mut conn, &child.uid, 100, "mon".parse().unwrap());
+        let goal1 = Goal {
+            deadline: now
+                .0
+                .checked_add_signed(chrono::Duration::days(30))
+                .map(Timestamp::from),
+            earmarked: 50,
+            ..create::goal(&mut conn, &child.uid, "Game", 200)
+        };
+        goal1.update(conn.as_mut()).await.unwrap();
+        let goal2 = create::goal(&mut conn, &child.uid, "Bicycle", 1000);
+        let mut tx = conn.begin().await.unwrap();
+
+        let progress = progress(&mut tx, &child.uid, now).await.unwrap();
+        assert_eq!(progress.len(), 2);
+
+        // The balance is 260, of which 50 is earmarked for the first goal
+        assert_eq!(progress[0].goal, goal1);
+        assert_eq!(progress[0].saved, 200);
+        assert_eq!(progress[0].projected, Some(now));
+
+        // The net income is 100 - 40 * 7 / 28 = 90 per week, and 940 remains
+        assert_eq!(progress[1].goal, goal2);
+        assert_eq!(progress[1].saved, 60);
+        assert_eq!(
+            progress[1].projected,
+            Some(
+                now.0
+                    .checked_add_signed(chrono::Duration::weeks(11))
+                    .unwrap()
+                    .into()
+            ),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn progress_monthly() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, _, _, _) = tests::populate(&mut conn).unwrap();
+        let child = create::user(
+            &mut conn,
+            Role::Child,
+            "Child",
+            "child@example.com",
+            &family.uid,
+        );
+        let other = create::user(
+            &mut conn,
+            Role::Child,
+            "Other",
+            "other@example.com",
+            &family.uid,
+        );
+        let now = Timestamp::now();
+        create::allowance(&mut conn, &child.uid, 520, Schedule::Monthly(1));
+        create::goal(&mut conn, &child.uid, "Bicycle", 1000);
+        create::allowance(&mut conn, &other.uid, 4, Schedule::Monthly(1));
+        create::goal(&mut conn, &other.uid, "Sweets", 100);
+        let mut tx = conn.begin().await.unwrap();
+        let projected = |weeks| -> Option<Timestamp> {
+            Some(
+                now.0
+                    .checked_add_signed(chrono::Duration::weeks(weeks))
+                    .unwrap()
+                    .into(),
+            )
+        };
+
+        // The net income is 520 * 12 = 6240 per year, or 120 per week
+        let child_progress = progress(&mut tx, &child.uid, now).await.unwrap();
+        assert_eq!(child_progress[0].projected, projected(9));
+
+        // The net income is 4 * 12 = 48 per year, which is less than 1 per
+        // week
+        let other_progress = progress(&mut tx, &other.uid, now).await.unwrap();
+        assert_eq!(other_progress[0].projected, projected(109));
+    }
+
+    #[actix_rt::test]
+    async fn progress_daily() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, _, _, _) = tests::populate(&mut conn).unwrap();
+        let child = create::user(
+            &mut conn,
+            Role::Child,
+            "Child",
+            "child@example.com",
+            &family.uid,
+        );
+        let now = Timestamp::now();
+        create::allowance(&mut conn, &child.uid, 20, Schedule::Daily);
+        create::goal(&mut conn, &child.uid, "Bicycle", 1000);
+        let mut tx = conn.begin().await.unwrap();
+
+        let progress = progress(&mut tx, &child.uid, now).await.unwrap();
+
+        // The net income is 20 * 7 = 140 per week
+        assert_eq!(
+            progress[0].projected,
+            Some(
+                now.0
+                    .checked_add_signed(chrono::Duration::weeks(8))
+                    .unwrap()
+                    .into()
+            ),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn progress_never() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, _, _, _) = tests::populate(&mut conn).unwrap();
+        let child = create::user(
+            &mut conn,
+            Role::Child,
+            "Child",
+            "child@example.com",
+            &family.uid,
+        );
+        let goal = create::goal(&mut conn, &child.uid, "Bicycle", 1000);
+        let mut tx = conn.begin().await.unwrap();
+
+        let progress = progress(&mut tx, &child.uid, Timestamp::now())
+            .await
+            .unwrap();
+        assert_eq!(
+            progress,
+            vec![Progress {
+                goal,
+                saved: 0,
+                projected: None,
+            }],
+        );
+    }
+}
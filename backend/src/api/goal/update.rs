@@ -0,0 +1,204 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{goal, Goal};
+use crate::db::values::UID;
+
+/// Changes a savings goal.
+#[put("goal/{user_uid}/{goal_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, goal_uid) = path.into_inner();
+    {
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &req.into_inner(),
+            &user_uid,
+            &goal_uid,
+        )
+        .await?;
+        Notify::MemberAndParents {
+            event: Event::GoalUpdated {
+                goal: res.goal.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+    goal_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = super::user(tx, state, user_uid).await?;
+    let goal = Goal::read(tx.as_mut(), goal_uid)
+        .await?
+        .filter(|goal| goal.user_uid == user.uid)
+        .ok_or_else(|| api::Error::not_found("unknown goal"))?;
+    if goal.completed.is_some() {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "goal already completed",
+        ));
+    }
+
+    let goal = goal.clone().merge(req.clone().merge(goal::GoalDescription {
+        user_uid: Some(goal.user_uid),
+        completed: Some(goal.completed),
+        ..Default::default()
+    }));
+    super::check_earmark(tx, &goal).await?;
+    goal.update(tx.as_mut()).await?;
+
+    Ok(Res { goal })
+}
+
+pub type Req = goal::GoalDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The updated goal.
+    pub goal: Goal,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Timestamp;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let goal = create::goal(&mut conn, &children.0.uid, "Bicycle", 1000);
+
+        {
+            let mut tx = conn.begin().await.unwrap();
+            execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &Req {
+                    user_uid: Some(children.1.uid.clone()),
+                    amount: Some(1200),
+                    earmarked: Some(30),
+                    ..Default::default()
+                },
+                &children.0.uid,
+                &goal.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let goal = Goal::read(conn.as_mut(), &goal.uid).await.unwrap().unwrap();
+        assert_eq!(goal.user_uid, children.0.uid);
+        assert_eq!(goal.name, "Bicycle");
+        assert_eq!(goal.amount, 1200);
+        assert_eq!(goal.earmarked, 30);
+    }
+
+    #[actix_rt::test]
+    async fn completed() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let goal = Goal {
+            completed: Some(Timestamp::now()),
+            ..create::goal(&mut conn, &children.0.uid, "Bicycle", 1000)
+        };
+        goal.update(conn.as_mut()).await.unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    amount: Some(1200),
+                    ..Default::default()
+                },
+                &children.0.uid,
+                &goal.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "goal already completed"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn unknown_goal() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let goal = create::goal(&mut conn, &children.1.uid, "Bicycle", 1000);
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    amount: Some(1200),
+                    ..Default::default()
+                },
+                &children.0.uid,
+                &goal.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown goal"));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::Ledger;
+use crate::db::values::{Role, UID};
+
+/// Creates a non-monetary ledger for a family.
+#[post("ledger/{family_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    family_uid: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    {
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &req.into_inner(),
+            &family_uid.into_inner(),
+        )
+        .await?;
+        Notify::Family {
+            event: Event::LedgerCreated {
+                ledger: res.ledger.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    family_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+
+    let ledger = Ledger::new(
+        UID::new(),
+        family_uid.clone(),
+        req.name.clone(),
+        req.unit.clone(),
+    );
+    ledger.create(tx.as_mut()).await?;
+
+    Ok(Res { ledger })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Req {
+    /// The ledger name.
+    pub name: String,
+
+    /// The unit of values in the ledger.
+    pub unit: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new ledger.
+    pub ledger: Ledger,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    name: "Screen time".into(),
+                    unit: "minutes".into(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.ledger.family_uid, family.uid);
+        assert_eq!(
+            Ledger::read(conn.as_mut(), &res.ledger.uid).await.unwrap(),
+            Some(res.ledger),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_parent() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let other_family = create::family(&mut conn, "Other Family");
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    name: "Screen time".into(),
+                    unit: "minutes".into(),
+                },
+                &other_family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid family"));
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &Req {
+                    name: "Screen time".into(),
+                    unit: "minutes".into(),
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
@@ -0,0 +1,28 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::db::entities::Ledger;
+use crate::db::values::UID;
+
+pub mod create;
+pub mod remove;
+
+/// Ensures that a ledger, if specified, belongs to a family.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `family_uid` - The family UID.
+/// *  `ledger_uid` - The ledger UID, or `None` for the monetary ledger.
+pub async fn check<'a>(
+    tx: &mut Tx<'a>,
+    family_uid: &UID,
+    ledger_uid: Option<&UID>,
+) -> Result<(), api::Error> {
+    if let Some(ledger_uid) = ledger_uid {
+        Ledger::read(tx.as_mut(), ledger_uid)
+            .await?
+            .filter(|ledger| &ledger.family_uid == family_uid)
+            .ok_or_else(|| api::Error::not_found("unknown ledger"))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::Ledger;
+use crate::db::values::{Role, UID};
+
+/// Removes a non-monetary ledger, and all its transactions, from a family.
+#[delete("ledger/{family_uid}/{ledger_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (family_uid, ledger_uid) = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &family_uid, &ledger_uid).await?;
+        Notify::Family {
+            event: Event::LedgerRemoved {
+                ledger: res.ledger.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    family_uid: &UID,
+    ledger_uid: &UID,
+) -> Result<Res, api::Error> {
+    let ledger = Ledger::read(tx.as_mut(), ledger_uid)
+        .await?
+        .ok_or_else(|| api::Error::not_found("unknown ledger"))?;
+    state
+        .assert_role(Role::Parent)?
+        .assert_family(family_uid)?
+        .assert_family(&ledger.family_uid)?;
+
+    ledger.delete(tx.as_mut()).await?;
+
+    Ok(Res { ledger })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The ledger that was removed.
+    pub ledger: Ledger,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::{create, Transaction};
+    use crate::db::test_engine;
+    use crate::db::values::{Timestamp, TransactionType};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let ledger = create::ledger(&mut conn, &family.uid, "Stars", "stars");
+        let transaction = create::ledger_transaction(
+            &mut conn,
+            TransactionType::Gift,
+            &children.0.uid,
+            &ledger.uid,
+            "Stars",
+            3,
+            Timestamp::now(),
+        );
+
+        {
+            let mut tx = conn.begin().await.unwrap();
+            execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &family.uid,
+                &ledger.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        assert!(Ledger::read(conn.as_mut(), &ledger.uid)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(Transaction::read(conn.as_mut(), &transaction.uid)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_parent() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let other_family = create::family(&mut conn, "Other Family");
+        let ledger =
+            create::ledger(&mut conn, &other_family.uid, "Stars", "stars");
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &family.uid,
+                &ledger.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid family"));
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let ledger = create::ledger(&mut conn, &family.uid, "Stars", "stars");
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &family.uid,
+                &ledger.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{match_rule, Goal, MatchRule, User};
+use crate::db::values::{Role, Timestamp, UID};
+
+/// Creates a savings matching rule for a child.
+#[post("match/{user_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    user_uid: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let user_uid = user_uid.into_inner();
+    {
+        let res = execute(&mut tx, state.clone(), &req.into_inner(), &user_uid)
+            .await?;
+        Notify::MemberAndParents {
+            event: Event::MatchRuleCreated {
+                rule: res.rule.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = User::read(tx.as_mut(), user_uid)
+        .await?
+        .ok_or_else(|| api::Error::not_found("unknown user"))?;
+    state
+        .assert_role(Role::Parent)?
+        .assert_family(&user.family_uid)?;
+
+    let rule = api::argument(
+        match_rule::MatchRuleDescription {
+            cap: Some(None),
+            jar_uid: Some(None),
+            goal_uid: Some(None),
+            ..Default::default()
+        }
+        .merge(req.clone())
+        .merge(match_rule::MatchRuleDescription {
+            user_uid: Some(user.uid.clone()),
+            created: Some(Timestamp::now()),
+            ..Default::default()
+        })
+        .entity(UID::new()),
+    )?;
+    if rule.per <= 0 || rule.matched < 0 || rule.cap.unwrap_or(0) < 0 {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid amount",
+        ));
+    }
+    api::user::jars::check(tx, &user.uid, rule.jar_uid.as_ref()).await?;
+    if let Some(goal_uid) = &rule.goal_uid {
+        Goal::read(tx.as_mut(), goal_uid)
+            .await?
+            .filter(|goal| goal.user_uid == user.uid)
+            .ok_or_else(|| api::Error::not_found("unknown goal"))?;
+    }
+    rule.create(tx.as_mut()).await?;
+
+    Ok(Res { rule })
+}
+
+pub type Req = match_rule::MatchRuleDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new rule.
+    pub rule: MatchRule,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::{MatchKind, Period};
+
+    use super::*;
+
+    fn req() -> Req {
+        Req {
+            name: Some("Savings bonus".into()),
+            kind: Some(MatchKind::Savings),
+            per: Some(10),
+            matched: Some(1),
+            period: Some(Period::Monthly),
+            ..Default::default()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &req(),
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.rule.user_uid, children.0.uid);
+        assert_eq!(res.rule.cap, None);
+        assert_eq!(
+            MatchRule::read(conn.as_mut(), &res.rule.uid).await.unwrap(),
+            Some(res.rule),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn unknown_goal() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let goal = create::goal(&mut conn, &children.1.uid, "Bicycle", 1000);
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    goal_uid: Some(Some(goal.uid.clone())),
+                    ..req()
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown goal"));
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &req(),
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod create;
+pub mod preview;
+pub mod remove;
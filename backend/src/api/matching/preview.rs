@@ -0,0 +1,166 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::session::State;
+use crate::db::entities::{MatchRule, User};
+use crate::db::values::{Role, Timestamp, UID};
+
+/// Lists the matching rules of a child, and what they would contribute if
+/// the current period ended now.
+#[get("match/{user_uid}/preview")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    session: Session,
+    user_uid: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    {
+        let res =
+            execute(&mut tx, state, &user_uid.into_inner(), Timestamp::now())
+                .await?;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    user_uid: &UID,
+    now: Timestamp,
+) -> Result<Res, api::Error> {
+    let user = User::read(tx.as_mut(), user_uid)
+        .await?
+        .ok_or_else(|| api::Error::not_found("unknown user"))?;
+    let state = state.assert_family(&user.family_uid)?;
+    if state.role == Role::Child {
+        state.assert_user(&user.uid)?;
+    }
+
+    let mut previews = Vec::new();
+    for rule in MatchRule::read_for_user(tx, &user.uid).await? {
+        let start = rule.period.start(now);
+        let amount = rule.evaluate(tx, start, now).await?;
+        previews.push(Preview {
+            rule,
+            start,
+            amount,
+        });
+    }
+
+    Ok(Res { previews })
+}
+
+/// A preview of the contribution of a rule.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Preview {
+    /// The rule.
+    pub rule: MatchRule,
+
+    /// The start of the current period.
+    pub start: Timestamp,
+
+    /// The amount that would be contributed if the period ended now.
+    pub amount: i64,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The previews of all rules for the user.
+    pub previews: Vec<Preview>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+    use crate::db::values::{MatchKind, Period};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, transactions, _) =
+            tests::populate(&mut conn).unwrap();
+        let rule = MatchRule::new(
+            UID::new(),
+            children.0.uid.clone(),
+            "Savings bonus".into(),
+            MatchKind::Savings,
+            10,
+            1,
+            Some(20),
+            Period::Monthly,
+            None,
+            None,
+            Timestamp::now(),
+        );
+        rule.create(conn.as_mut()).await.unwrap();
+        let balance = transactions
+            .iter()
+            .filter(|t| t.user_uid == children.0.uid)
+            .map(|t| t.amount)
+            .sum::<i64>();
+        let now: Timestamp =
+            (Timestamp::now().0 + chrono::Duration::hours(1)).into();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &children.0.uid,
+                now,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            res.previews,
+            vec![Preview {
+                rule: rule.clone(),
+                start: Period::Monthly.start(now),
+                amount: (balance / 10).min(20),
+            }],
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &children.1.uid,
+                Timestamp::now(),
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid user"));
+    }
+}
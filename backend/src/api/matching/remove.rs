@@ -0,0 +1,162 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{MatchRule, User};
+use crate::db::values::{Role, UID};
+
+/// Removes a savings matching rule.
+///
+/// Contributions already made are kept.
+#[delete("match/{user_uid}/{rule_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, rule_uid) = path.into_inner();
+    {
+        let res = execute(&mut tx, state.clone(), &user_uid, &rule_uid).await?;
+        Notify::MemberAndParents {
+            event: Event::MatchRuleRemoved {
+                rule: res.rule.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    user_uid: &UID,
+    rule_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = User::read(tx.as_mut(), user_uid)
+        .await?
+        .ok_or_else(|| api::Error::not_found("unknown user"))?;
+    state
+        .assert_role(Role::Parent)?
+        .assert_family(&user.family_uid)?;
+    let rule = MatchRule::read(tx.as_mut(), rule_uid)
+        .await?
+        .filter(|rule| rule.user_uid == user.uid)
+        .ok_or_else(|| api::Error::not_found("unknown rule"))?;
+
+    rule.delete(tx.as_mut()).await?;
+
+    Ok(Res { rule })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The rule that was removed.
+    pub rule: MatchRule,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+    use crate::db::values::{MatchKind, Period, Timestamp};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let rule = MatchRule::new(
+            UID::new(),
+            children.0.uid.clone(),
+            "Savings bonus".into(),
+            MatchKind::Savings,
+            10,
+            1,
+            None,
+            Period::Monthly,
+            None,
+            None,
+            Timestamp::now(),
+        );
+        rule.create(conn.as_mut()).await.unwrap();
+
+        {
+            let mut tx = conn.begin().await.unwrap();
+            execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &children.0.uid,
+                &rule.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        assert!(MatchRule::read(conn.as_mut(), &rule.uid)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[actix_rt::test]
+    async fn unknown_rule() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let rule = MatchRule::new(
+            UID::new(),
+            children.1.uid.clone(),
+            "Savings bonus".into(),
+            MatchKind::Savings,
+            10,
+            1,
+            None,
+            Period::Monthly,
+            None,
+            None,
+            Timestamp::now(),
+        );
+        rule.create(conn.as_mut()).await.unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &children.0.uid,
+                &rule.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown rule"));
+    }
+}
@@ -12,13 +12,22 @@ use weru::log;
 
 use crate::db::values::EmailAddress;
 
+pub mod bonus;
+pub mod chore;
+pub mod currency;
 pub mod family;
+pub mod fine;
+pub mod gift_link;
+pub mod goal;
 pub mod invitation;
+pub mod ledger;
+pub mod matching;
 pub mod notify;
 pub mod overview;
 pub mod request;
 pub mod server;
 pub mod session;
+pub mod subscription;
 pub mod transaction;
 pub mod user;
 
@@ -3,7 +3,12 @@ use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::db::entities::user;
-use crate::db::entities::{Allowance, Request, User};
+use crate::db::entities::{
+    Allowance, AllowanceChange, AutoApproval, BirthdayGift, BonusRule, Chore,
+    ChoreTemplate, Currency, FamilySettings, FineReason, GiftLink, Goal, Jar,
+    Ledger, MatchRule, PendingTransaction, Request, RequestApproval,
+    RequestComment, Subscription, Transaction, User,
+};
 use crate::db::values::UID;
 
 /// An event sent over the notification channel.
@@ -22,7 +27,26 @@ pub enum Event {
         /// The new allowance.
         allowance: Allowance,
 
-        /// The unique ID of the parent that updated the allowance.
+        /// The unique ID of the parent that updated the allowance, or `None`
+        /// if the allowance was updated automatically.
+        by: Option<UID>,
+    },
+
+    /// A change to the allowance for a user was scheduled.
+    AllowanceChangeScheduled {
+        /// The scheduled change.
+        change: AllowanceChange,
+
+        /// The unique ID of the parent that scheduled the change.
+        by: UID,
+    },
+
+    /// The currency used by the family was changed.
+    CurrencyChanged {
+        /// The new currency.
+        currency: Currency,
+
+        /// The unique ID of the parent that changed the currency.
         by: UID,
     },
 
@@ -44,6 +68,30 @@ pub enum Event {
         by: UID,
     },
 
+    /// The birth date or birthday gift of a family member was changed.
+    BirthdayUpdated {
+        /// The updated user.
+        user: User,
+
+        /// The birthday gift, if any.
+        gift: Option<BirthdayGift>,
+
+        /// The unique ID of the parent that made the change.
+        by: UID,
+    },
+
+    /// A child has a birthday, and a gift was booked.
+    Birthday {
+        /// The unique ID of the child.
+        user_uid: UID,
+
+        /// The age of the child.
+        age: u32,
+
+        /// The transactions booking the gift.
+        transactions: Vec<Transaction>,
+    },
+
     /// A family member was removed.
     FamilyMemberRemoved {
         /// The user that was removed.
@@ -53,6 +101,317 @@ pub enum Event {
         by: UID,
     },
 
+    /// The settings of the family were changed.
+    SettingsChanged {
+        /// The new settings.
+        settings: FamilySettings,
+
+        /// The unique ID of the parent that changed the settings.
+        by: UID,
+    },
+
+    /// A chore was created.
+    ChoreCreated {
+        /// The chore that was created.
+        chore: Chore,
+
+        /// The unique ID of the parent that created the chore.
+        by: UID,
+    },
+
+    /// A chore was claimed by a child.
+    ChoreClaimed {
+        /// The claimed chore.
+        chore: Chore,
+
+        /// The unique ID of the child that claimed the chore.
+        by: UID,
+    },
+
+    /// A chore was marked as done by a child.
+    ChoreDone {
+        /// The chore marked as done.
+        chore: Chore,
+
+        /// The unique ID of the child that did the chore.
+        by: UID,
+    },
+
+    /// A chore was approved, and the reward paid.
+    ChoreApproved {
+        /// The approved chore.
+        chore: Chore,
+
+        /// The unique ID of the parent that approved the chore.
+        by: UID,
+    },
+
+    /// A chore was not claimed before it was due.
+    ChoreMissed {
+        /// The missed chore.
+        chore: Chore,
+
+        /// The penalty booked for the missed chore, if any.
+        transaction: Option<Transaction>,
+    },
+
+    /// Chores were generated from templates.
+    ChoresGenerated {
+        /// The generated chores.
+        chores: Vec<Chore>,
+    },
+
+    /// A chore template was created.
+    ChoreTemplateCreated {
+        /// The template that was created.
+        template: ChoreTemplate,
+
+        /// The unique ID of the parent that created the template.
+        by: UID,
+    },
+
+    /// A chore template was removed.
+    ChoreTemplateRemoved {
+        /// The template that was removed.
+        template: ChoreTemplate,
+
+        /// The unique ID of the parent that removed the template.
+        by: UID,
+    },
+
+    /// A reason for fines was created.
+    FineReasonCreated {
+        /// The reason that was created.
+        reason: FineReason,
+
+        /// The unique ID of the parent that created the reason.
+        by: UID,
+    },
+
+    /// A reason for fines was removed.
+    FineReasonRemoved {
+        /// The reason that was removed.
+        reason: FineReason,
+
+        /// The unique ID of the parent that removed the reason.
+        by: UID,
+    },
+
+    /// A child was fined.
+    FineApplied {
+        /// The transaction booking the fine.
+        transaction: Transaction,
+
+        /// The reason for the fine.
+        reason: FineReason,
+
+        /// The unique ID of the parent that imposed the fine.
+        by: UID,
+    },
+
+    /// A link for a gift from a relative was created.
+    GiftLinkCreated {
+        /// The link that was created.
+        link: GiftLink,
+
+        /// The unique ID of the parent that created the link.
+        by: UID,
+    },
+
+    /// A relative recorded a gift through a gift link.
+    GiftLinkRecorded {
+        /// The link with the recorded gift.
+        link: GiftLink,
+    },
+
+    /// A gift link was removed.
+    GiftLinkRemoved {
+        /// The link that was removed.
+        link: GiftLink,
+
+        /// The unique ID of the parent that removed the link.
+        by: UID,
+    },
+
+    /// A gift recorded through a gift link was received and booked.
+    GiftReceived {
+        /// The link, which no longer exists.
+        link: GiftLink,
+
+        /// The booked transactions.
+        transactions: Vec<Transaction>,
+
+        /// The unique ID of the parent that confirmed receipt of the gift.
+        by: UID,
+    },
+
+    /// A savings goal was created.
+    GoalCreated {
+        /// The goal that was created.
+        goal: Goal,
+
+        /// The unique ID of the user that created the goal.
+        by: UID,
+    },
+
+    /// A savings goal was changed.
+    GoalUpdated {
+        /// The updated goal.
+        goal: Goal,
+
+        /// The unique ID of the user that changed the goal.
+        by: UID,
+    },
+
+    /// A savings goal was completed.
+    GoalCompleted {
+        /// The completed goal.
+        goal: Goal,
+
+        /// The unique ID of the user that completed the goal.
+        by: UID,
+    },
+
+    /// The jars of a user were replaced.
+    JarsUpdated {
+        /// The unique ID of the user owning the jars.
+        user_uid: UID,
+
+        /// The new jars.
+        jars: Vec<Jar>,
+
+        /// The unique ID of the parent that changed the jars.
+        by: UID,
+    },
+
+    /// A ledger was created.
+    LedgerCreated {
+        /// The ledger that was created.
+        ledger: Ledger,
+
+        /// The unique ID of the parent that created the ledger.
+        by: UID,
+    },
+
+    /// A ledger was removed.
+    LedgerRemoved {
+        /// The ledger that was removed.
+        ledger: Ledger,
+
+        /// The unique ID of the parent that removed the ledger.
+        by: UID,
+    },
+
+    /// A savings matching rule was created.
+    MatchRuleCreated {
+        /// The rule that was created.
+        rule: MatchRule,
+
+        /// The unique ID of the parent that created the rule.
+        by: UID,
+    },
+
+    /// A savings matching rule was removed.
+    MatchRuleRemoved {
+        /// The rule that was removed.
+        rule: MatchRule,
+
+        /// The unique ID of the parent that removed the rule.
+        by: UID,
+    },
+
+    /// A bonus rule was created.
+    BonusRuleCreated {
+        /// The rule that was created.
+        rule: BonusRule,
+
+        /// The unique ID of the parent that created the rule.
+        by: UID,
+    },
+
+    /// A bonus rule was removed.
+    BonusRuleRemoved {
+        /// The rule that was removed.
+        rule: BonusRule,
+
+        /// The unique ID of the parent that removed the rule.
+        by: UID,
+    },
+
+    /// A streak was completed, and a bonus booked.
+    BonusAwarded {
+        /// The rule, with its streak starting over.
+        rule: BonusRule,
+
+        /// The bonus transaction.
+        transaction: Transaction,
+    },
+
+    /// A transaction was scheduled.
+    PendingTransactionCreated {
+        /// The pending transaction.
+        pending: PendingTransaction,
+
+        /// The unique ID of the parent that scheduled the transaction.
+        by: UID,
+    },
+
+    /// A scheduled transaction was changed.
+    PendingTransactionUpdated {
+        /// The updated pending transaction.
+        pending: PendingTransaction,
+
+        /// The unique ID of the parent that changed the transaction.
+        by: UID,
+    },
+
+    /// A scheduled transaction was cancelled.
+    PendingTransactionCancelled {
+        /// The cancelled pending transaction.
+        pending: PendingTransaction,
+
+        /// The unique ID of the parent that cancelled the transaction.
+        by: UID,
+    },
+
+    /// A scheduled transaction was booked.
+    PendingTransactionBooked {
+        /// The pending transaction, which no longer exists.
+        pending: PendingTransaction,
+
+        /// The booked transactions.
+        transactions: Vec<Transaction>,
+    },
+
+    /// Transactions were booked for several children at once.
+    ///
+    /// Each child is notified only of their own transactions.
+    TransactionsBooked {
+        /// The transactions booked for the notified child.
+        transactions: Vec<Transaction>,
+
+        /// The unique ID of the parent that booked the transactions.
+        by: UID,
+    },
+
+    /// A subscription was cancelled.
+    SubscriptionCancelled {
+        /// The cancelled subscription.
+        subscription: Subscription,
+
+        /// The unique ID of the user that cancelled the subscription.
+        by: UID,
+    },
+
+    /// A subscription was paused or resumed.
+    SubscriptionPaused {
+        /// The updated subscription.
+        subscription: Subscription,
+
+        /// The unique ID of the parent that paused the subscription.
+        by: UID,
+    },
+
     /// A request was made.
     RequestCreated {
         /// The request that was made.
@@ -62,6 +421,36 @@ pub enum Event {
         by: UID,
     },
 
+    /// A pending request was changed by the child that made it.
+    RequestUpdated {
+        /// The updated request.
+        request: Request,
+
+        /// The unique ID of the child that changed the request.
+        by: UID,
+    },
+
+    /// A request was withdrawn by the child that made it.
+    RequestWithdrawn {
+        /// The request that was withdrawn.
+        request: Request,
+
+        /// The unique ID of the child that withdrew the request.
+        by: UID,
+    },
+
+    /// A request was approved by a parent, but requires more approvals.
+    RequestApproved {
+        /// The request that was approved.
+        request: Request,
+
+        /// All approvals of the request so far.
+        approvals: Vec<RequestApproval>,
+
+        /// The unique ID of the parent that approved the request.
+        by: UID,
+    },
+
     /// A request was granted.
     RequestGranted {
         /// The request that was granted.
@@ -71,6 +460,42 @@ pub enum Event {
         by: UID,
     },
 
+    /// A request was granted automatically when it was made.
+    RequestAutoApproved {
+        /// The request that was granted.
+        request: Request,
+
+        /// The transaction charging the cost of the request.
+        transaction: Transaction,
+
+        /// The unique ID of the child that made the request.
+        by: UID,
+    },
+
+    /// A request expired before it was decided.
+    RequestExpired {
+        /// The request that expired.
+        request: Request,
+    },
+
+    /// Parents are reminded of requests that have been pending for a while.
+    RequestReminder {
+        /// The pending requests.
+        requests: Vec<Request>,
+    },
+
+    /// The automatic approval rule for a child was changed.
+    AutoApprovalUpdated {
+        /// The unique ID of the child.
+        user_uid: UID,
+
+        /// The new rule, or `None` if it was removed.
+        rule: Option<AutoApproval>,
+
+        /// The unique ID of the parent that changed the rule.
+        by: UID,
+    },
+
     /// A request was declined.
     RequestDeclined {
         /// The request that was declined.
@@ -79,4 +504,13 @@ pub enum Event {
         /// The unique ID of the parent that declined the request.
         by: UID,
     },
+
+    /// A request was commented.
+    RequestCommented {
+        /// The new comment.
+        comment: RequestComment,
+
+        /// The unique ID of the user that wrote the comment.
+        by: UID,
+    },
 }
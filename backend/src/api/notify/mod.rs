@@ -47,6 +47,38 @@ impl Notify {
         tx: &mut Tx<'a>,
         channel: &ChannelEngine,
         from: &UID,
+    ) {
+        self.deliver(tx, channel, Some(from)).await
+    }
+
+    /// Sends a notification not initiated by any user to all affected users.
+    ///
+    /// This is used for events generated by the server itself, such as those
+    /// of scheduled tasks. If any error occurs during the operation, they are
+    /// logged, but the error is then ignored.
+    ///
+    /// # Arguments
+    /// *  `tx` - A database transaction.
+    /// *  `channel` - The channel engine.
+    pub async fn send_system<'a>(
+        self,
+        tx: &mut Tx<'a>,
+        channel: &ChannelEngine,
+    ) {
+        self.deliver(tx, channel, None).await
+    }
+
+    /// Sends a notification to all affected users except its initiator.
+    ///
+    /// # Arguments
+    /// *  `tx` - A database transaction.
+    /// *  `channel` - The channel engine.
+    /// *  `from` - The user that initiated the event, if any.
+    async fn deliver<'a>(
+        self,
+        tx: &mut Tx<'a>,
+        channel: &ChannelEngine,
+        from: Option<&UID>,
     ) {
         let event = self.event();
         for uid in self.users(tx, from).await {
@@ -87,24 +119,25 @@ impl Notify {
     ///
     /// # Arguments
     /// *  `tx` - A database transaction.
-    /// *  `from` - The user that initiated the event. This user will not be
-    ///    included.
-    async fn users<'a>(&self, tx: &mut Tx<'a>, from: &UID) -> Vec<UID> {
+    /// *  `from` - The user that initiated the event, if any. This user will
+    ///    not be included.
+    async fn users<'a>(&self, tx: &mut Tx<'a>, from: Option<&UID>) -> Vec<UID> {
         use Notify::*;
+        let initiator = |u: &User| Some(&u.uid) == from;
         match self {
             Member { user, .. } => vec![user.clone()],
             Family { family, .. } => {
-                self.members(tx, family, |u| &u.uid != from).await
+                self.members(tx, family, |u| !initiator(u)).await
             }
             MemberAndParents { uid, family, .. } => {
                 self.members(tx, family, |u| {
-                    (&u.uid == uid || u.role == Role::Parent) && &u.uid != from
+                    (&u.uid == uid || u.role == Role::Parent) && !initiator(u)
                 })
                 .await
             }
             Parents { family, .. } => {
                 self.members(tx, family, |u| {
-                    u.role == Role::Parent && &u.uid != from
+                    u.role == Role::Parent && !initiator(u)
                 })
                 .await
             }
@@ -6,8 +6,12 @@ use crate::api;
 use crate::api::session::State;
 use crate::configuration::FamilyConfiguration;
 use crate::db;
-use crate::db::entities::{Family, Invitation, Request, Transaction, User};
-use crate::db::values::{Role, UID};
+use crate::db::entities::{
+    BirthdayGift, BonusRule, Chore, ChoreTemplate, Family, FamilySettings,
+    FineReason, GiftLink, Invitation, Jar, Ledger, PendingTransaction, Request,
+    Transaction, User,
+};
+use crate::db::values::{Role, Timestamp, UID};
 
 /// The maximum number of transactions to return per user.
 const TRANSACTION_LIMIT: usize = 5;
@@ -47,12 +51,27 @@ pub async fn execute<'a>(
         .await?
         .unwrap_or(defaults);
     let family = api::expect(Family::read(tx.as_mut(), family_uid).await?)?;
+    let settings = FamilySettings::read_or_default(tx, family_uid).await?;
     let members = User::read_by_family(tx, &family_uid).await?;
     let invitations = Invitation::read_for_family(tx, family_uid).await?;
     let requests = match role {
         Role::Parent => Request::read_for_family(tx, family_uid).await?,
         Role::Child => Request::read_for_user(tx, &user_uid).await?,
     };
+    let chores = Chore::read_for_family(tx, family_uid)
+        .await?
+        .into_iter()
+        .filter(|chore| role == Role::Parent || chore.is_visible_to(&user_uid))
+        .collect::<Vec<_>>();
+    let chore_templates = match role {
+        Role::Parent => ChoreTemplate::read_for_family(tx, family_uid).await?,
+        Role::Child => Vec::new(),
+    };
+    let fine_reasons = FineReason::read_for_family(tx, family_uid).await?;
+    let gift_links = match role {
+        Role::Parent => GiftLink::read_for_family(tx, family_uid).await?,
+        Role::Child => Vec::new(),
+    };
     let children = || {
         members.iter().filter(|user| match (role, user.role) {
             (Role::Parent, Role::Child) => true,
@@ -74,36 +93,145 @@ pub async fn execute<'a>(
         }
         transactions
     };
+    let upcoming = {
+        let mut upcoming = HashMap::new();
+        for child in children() {
+            upcoming.insert(
+                child.uid.clone(),
+                PendingTransaction::read_for_user(tx, &child.uid).await?,
+            );
+        }
+        upcoming
+    };
     let balances = {
         let mut balances = HashMap::new();
         for child in children() {
             balances.insert(
                 child.uid.clone(),
-                Transaction::balance(tx, &child.uid).await?.unwrap_or(0),
+                Transaction::balance(tx, &child.uid, None)
+                    .await?
+                    .unwrap_or(0),
             );
         }
         balances
     };
+    let jars = {
+        let mut jars = HashMap::new();
+        for child in children() {
+            jars.insert(
+                child.uid.clone(),
+                JarBalances {
+                    jars: Jar::read_for_user(tx, &child.uid).await?,
+                    balances: Transaction::jar_balances(tx, &child.uid).await?,
+                },
+            );
+        }
+        jars
+    };
+    let goals = {
+        let now = Timestamp::now();
+        let mut goals = HashMap::new();
+        for child in children() {
+            goals.insert(
+                child.uid.clone(),
+                api::goal::progress(tx, &child.uid, now).await?,
+            );
+        }
+        goals
+    };
+    let bonuses = {
+        let mut bonuses = HashMap::new();
+        for child in children() {
+            bonuses.insert(
+                child.uid.clone(),
+                BonusRule::read_for_user(tx, &child.uid).await?,
+            );
+        }
+        bonuses
+    };
+    let birthday_gifts = {
+        let mut birthday_gifts = HashMap::new();
+        if role == Role::Parent {
+            for child in children() {
+                if let Some(gift) =
+                    BirthdayGift::read(tx.as_mut(), &child.uid).await?
+                {
+                    birthday_gifts.insert(child.uid.clone(), gift.amount);
+                }
+            }
+        }
+        birthday_gifts
+    };
+    let ledgers = Ledger::read_for_family(tx, family_uid).await?;
+    let ledger_balances = {
+        let mut ledger_balances = HashMap::new();
+        for child in children() {
+            let balances = Transaction::ledger_balances(tx, &child.uid).await?;
+            ledger_balances.insert(
+                child.uid.clone(),
+                ledgers
+                    .iter()
+                    .map(|ledger| {
+                        (
+                            ledger.uid.clone(),
+                            balances.get(&ledger.uid).cloned().unwrap_or(0),
+                        )
+                    })
+                    .collect(),
+            );
+        }
+        ledger_balances
+    };
 
     Ok(Res {
         currency: configuration.currency().clone(),
         family,
+        settings,
         members,
         invitations,
         requests,
+        chores,
+        chore_templates,
+        fine_reasons,
+        gift_links,
         transactions,
+        upcoming,
         balances,
+        jars,
+        goals,
+        bonuses,
+        birthday_gifts,
+        ledgers,
+        ledger_balances,
     })
 }
 
+/// The jars of a user, and their balances.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct JarBalances {
+    /// The jars.
+    jars: Vec<Jar>,
+
+    /// The balances of the jars.
+    ///
+    /// Jars without any transactions are not included.
+    balances: HashMap<UID, i64>,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct Res {
     /// The currency of all monetary values.
+    ///
+    /// This includes the metadata required to format amounts, which are all
+    /// in the minor unit of the currency.
     currency: db::entities::Currency,
 
     /// The family.
     family: Family,
 
+    /// The settings of the family.
+    settings: FamilySettings,
+
     /// All members of this family.
     members: Vec<User>,
 
@@ -113,11 +241,55 @@ pub struct Res {
     /// All outstanding requests.
     requests: Vec<db::entities::Request>,
 
+    /// All chores not yet approved.
+    ///
+    /// Children only see the chores available to them, and those they have
+    /// claimed.
+    chores: Vec<Chore>,
+
+    /// The templates from which chores are generated.
+    ///
+    /// This is only available to parents.
+    chore_templates: Vec<ChoreTemplate>,
+
+    /// The reasons for which children may be fined.
+    fine_reasons: Vec<FineReason>,
+
+    /// The links through which relatives can announce gifts.
+    ///
+    /// This is only available to parents.
+    gift_links: Vec<GiftLink>,
+
     /// The most recent transactions for the children.
     transactions: Vec<db::entities::Transaction>,
 
+    /// The transactions scheduled for the children, the earliest first.
+    upcoming: HashMap<UID, Vec<PendingTransaction>>,
+
     /// The balances of the child accounts.
     balances: HashMap<UID, i64>,
+
+    /// The jars of the child accounts, and their balances.
+    jars: HashMap<UID, JarBalances>,
+
+    /// The progress towards the active savings goals of the children.
+    goals: HashMap<UID, Vec<api::goal::Progress>>,
+
+    /// The bonus rules of the children, including their current streaks.
+    bonuses: HashMap<UID, Vec<BonusRule>>,
+
+    /// The amounts of the birthday gifts of the children.
+    ///
+    /// This is only available to parents.
+    birthday_gifts: HashMap<UID, i64>,
+
+    /// The non-monetary ledgers of this family.
+    ledgers: Vec<Ledger>,
+
+    /// The balances of the non-monetary ledgers of the child accounts.
+    ///
+    /// This maps child UID to a map from ledger UID to balance.
+    ledger_balances: HashMap<UID, HashMap<UID, i64>>,
 }
 
 #[cfg(test)]
@@ -125,7 +297,7 @@ mod tests {
     use crate::api::tests;
     use crate::db::entities::create;
     use crate::db::test_engine;
-    use crate::db::values::CurrencyFormat;
+    use crate::db::values::{BonusKind, CurrencyFormat, NegativeStyle, Period};
 
     use super::*;
 
@@ -159,6 +331,10 @@ mod tests {
                 FamilyConfiguration::new(db::entities::Currency::new(
                     "TST".into(),
                     CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
                 )),
                 State {
                     user_uid: parent.uid.clone(),
@@ -173,7 +349,10 @@ mod tests {
             r
         };
 
+        assert_eq!(res.currency.name, "TST");
+        assert_eq!(res.currency.exponent, 2);
         assert_eq!(res.family, family);
+        assert_eq!(res.settings, FamilySettings::default_for(&family.uid));
         assert_eq!(res.members.len(), 3);
         assert!(res.members.contains(&parent));
         assert!(res.members.contains(&children.0));
@@ -207,6 +386,10 @@ mod tests {
                 FamilyConfiguration::new(db::entities::Currency::new(
                     "TST".into(),
                     CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
                 )),
                 State {
                     user_uid: children.0.uid.clone(),
@@ -248,6 +431,511 @@ mod tests {
         }
     }
 
+    #[actix_rt::test]
+    async fn success_ledgers() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let ledger =
+            create::ledger(&mut conn, &family.uid, "Screen time", "minutes");
+        create::ledger_transaction(
+            &mut conn,
+            db::values::TransactionType::Gift,
+            &children.0.uid,
+            &ledger.uid,
+            "Screen time",
+            30,
+            db::values::Timestamp::now(),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.ledgers, vec![ledger.clone()]);
+        assert_eq!(res.ledger_balances[&children.0.uid][&ledger.uid], 30);
+        assert_eq!(res.ledger_balances[&children.1.uid][&ledger.uid], 0);
+    }
+
+    #[actix_rt::test]
+    async fn success_goals() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let child = create::user(
+            &mut conn,
+            Role::Child,
+            "Child",
+            "child@example.com",
+            &family.uid,
+        );
+        create::transaction(
+            &mut conn,
+            db::values::TransactionType::Gift,
+            &child.uid,
+            "gift",
+            300,
+            Timestamp::now(),
+        );
+        let bicycle = create::goal(&mut conn, &child.uid, "Bicycle", 200);
+        let game = create::goal(&mut conn, &child.uid, "Game", 1000);
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        // The balance of 300 completes the first goal, and the remainder is
+        // saved towards the second, which is never reached without income
+        let progress = &res.goals[&child.uid];
+        assert_eq!(progress.len(), 2);
+        assert_eq!(progress[0].goal, bicycle);
+        assert_eq!(progress[0].saved, 200);
+        assert!(progress[0].projected.is_some());
+        assert_eq!(progress[1].goal, game);
+        assert_eq!(progress[1].saved, 100);
+        assert_eq!(progress[1].projected, None);
+    }
+
+    #[actix_rt::test]
+    async fn success_jars() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let savings = create::jar(&mut conn, &children.0.uid, "Savings", 30);
+        let spending = create::jar(&mut conn, &children.0.uid, "Spending", 70);
+        {
+            let mut tx = conn.begin().await.unwrap();
+            Transaction::create_split_with_auto_uid(
+                &mut tx,
+                db::values::TransactionType::Gift,
+                children.0.uid.clone(),
+                "Gift".into(),
+                100,
+                Timestamp::now(),
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            res.jars[&children.0.uid],
+            JarBalances {
+                jars: vec![spending.clone(), savings.clone()],
+                balances: [(spending.uid, 70), (savings.uid, 30)]
+                    .into_iter()
+                    .collect(),
+            },
+        );
+        assert_eq!(
+            res.jars[&children.1.uid],
+            JarBalances {
+                jars: Vec::new(),
+                balances: HashMap::new(),
+            },
+        );
+    }
+
+    #[actix_rt::test]
+    async fn success_chores() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let open = create::chore(&mut conn, &family.uid, "Dishes", 100, None);
+        let assigned = create::chore(
+            &mut conn,
+            &family.uid,
+            "Laundry",
+            200,
+            Some(&children.0.uid),
+        );
+        let other = create::chore(
+            &mut conn,
+            &family.uid,
+            "Vacuum",
+            300,
+            Some(&children.1.uid),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+        assert_eq!(res.chores.len(), 3);
+        assert!(res.chores.contains(&open));
+        assert!(res.chores.contains(&assigned));
+        assert!(res.chores.contains(&other));
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+        assert_eq!(res.chores.len(), 2);
+        assert!(res.chores.contains(&open));
+        assert!(res.chores.contains(&assigned));
+    }
+
+    #[actix_rt::test]
+    async fn success_bonuses() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let rule = BonusRule::new(
+            UID::new(),
+            children.0.uid.clone(),
+            "No spending".into(),
+            BonusKind::NoSpending,
+            Period::Weekly,
+            4,
+            50,
+            2,
+            None,
+            Timestamp::now(),
+        );
+        rule.create(conn.as_mut()).await.unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.bonuses[&children.0.uid], vec![rule]);
+        assert_eq!(res.bonuses[&children.1.uid], Vec::new());
+    }
+
+    #[actix_rt::test]
+    async fn success_upcoming() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let now = Timestamp::now();
+        let later = create::pending_transaction(
+            &mut conn,
+            &children.0.uid,
+            "Birthday",
+            500,
+            now.0
+                .checked_add_signed(chrono::Duration::days(14))
+                .unwrap()
+                .into(),
+        );
+        let sooner = create::pending_transaction(
+            &mut conn,
+            &children.0.uid,
+            "Holiday",
+            200,
+            now.0
+                .checked_add_signed(chrono::Duration::days(7))
+                .unwrap()
+                .into(),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.upcoming[&children.0.uid], vec![sooner, later]);
+        assert_eq!(res.upcoming[&children.1.uid], Vec::new());
+    }
+
+    #[actix_rt::test]
+    async fn success_birthday_gifts() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        BirthdayGift::new(children.0.uid.clone(), 5000, None)
+            .create(conn.as_mut())
+            .await
+            .unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+        assert_eq!(
+            res.birthday_gifts,
+            [(children.0.uid.clone(), 5000)].into_iter().collect(),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+        assert_eq!(res.birthday_gifts, HashMap::new());
+    }
+
+    #[actix_rt::test]
+    async fn success_gift_links() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let expires =
+            Timestamp::from(Timestamp::now().0 + chrono::Duration::days(7));
+        let links = vec![
+            create::gift_link(&mut conn, &children.0.uid, expires),
+            create::gift_link(&mut conn, &children.1.uid, expires),
+        ];
+        let other_family = create::family(&mut conn, "Other Family");
+        let other_child = create::user(
+            &mut conn,
+            Role::Child,
+            "Other Child",
+            "other@example.com",
+            &other_family.uid,
+        );
+        create::gift_link(&mut conn, &other_child.uid, expires);
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+        assert_eq!(res.gift_links.len(), links.len());
+        for link in &links {
+            assert!(res.gift_links.contains(link));
+        }
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                FamilyConfiguration::new(db::entities::Currency::new(
+                    "TST".into(),
+                    CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
+                )),
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+        assert_eq!(res.gift_links, Vec::new());
+    }
+
     #[actix_rt::test]
     async fn forbidden() {
         let database = test_engine().await;
@@ -262,6 +950,10 @@ mod tests {
                 FamilyConfiguration::new(db::entities::Currency::new(
                     "TST".into(),
                     CurrencyFormat::new("#{}"),
+                    2,
+                    ".".into(),
+                    ",".into(),
+                    NegativeStyle::Leading,
                 )),
                 State {
                     user_uid: parent.uid.clone(),
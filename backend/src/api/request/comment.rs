@@ -0,0 +1,234 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{Request, RequestComment, User};
+use crate::db::values::{RequestStatus, Role, Timestamp, UID};
+
+/// Comments on a pending request.
+#[post("request/{user_uid}/{request_uid}/comment")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<(UID, i64)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, request_uid) = path.into_inner();
+    {
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &req.into_inner(),
+            &user_uid,
+            &request_uid,
+        )
+        .await?;
+        Notify::MemberAndParents {
+            event: Event::RequestCommented {
+                comment: res.comment.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+    request_uid: &i64,
+) -> Result<Res, api::Error> {
+    let request = Request::read(tx.as_mut(), request_uid)
+        .await?
+        .filter(|request| &request.user_uid == user_uid)
+        .ok_or_else(|| api::Error::not_found("unknown request"))?;
+    let user = User::read(tx.as_mut(), user_uid)
+        .await?
+        .ok_or_else(|| api::Error::not_found("unknown request"))?;
+    let state = match state.role {
+        Role::Parent => state.assert_family(&user.family_uid)?,
+        Role::Child => state.assert_user(&user.uid)?,
+    };
+    if request.status != RequestStatus::Pending {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "request already decided",
+        ));
+    }
+    let text = req.text.trim();
+    if text.is_empty() {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "empty comment",
+        ));
+    }
+
+    let comment = RequestComment::new(
+        UID::new(),
+        request.uid,
+        state.user_uid,
+        text.into(),
+        Timestamp::now(),
+    );
+    comment.create(tx.as_mut()).await?;
+
+    Ok(Res { comment })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Req {
+    /// The comment text.
+    pub text: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new comment.
+    pub comment: RequestComment,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    text: " Why do you need this? ".into(),
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.comment.text, "Why do you need this?");
+        assert_eq!(res.comment.user_uid, parent.uid);
+        let mut tx = conn.begin().await.unwrap();
+        assert_eq!(
+            RequestComment::read_for_request(&mut tx, &candidate.uid)
+                .await
+                .unwrap(),
+            vec![res.comment],
+        );
+    }
+
+    #[actix_rt::test]
+    async fn already_decided() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone()
+            .decide(RequestStatus::Declined, Some(&parent.uid), None);
+        candidate.update(conn.as_mut()).await.unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    text: "Please?".into(),
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "request already decided"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.1.uid.clone(),
+                    role: children.1.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    text: "Me too!".into(),
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid user"));
+    }
+}
@@ -4,14 +4,15 @@ use crate::api;
 use crate::api::notify::{Event, Notify};
 use crate::api::session::State;
 use crate::db::entities::{Request, User};
-use crate::db::values::{Role, UID};
+use crate::db::values::{RequestStatus, Role, UID};
 
-/// Deletes a user request
+/// Declines a request.
 #[delete("request/{user_uid}/{request_uid}")]
 pub async fn handle(
     database: web::Data<DatabaseEngine>,
     channel: web::Data<ChannelEngine>,
     session: Session,
+    req: Option<web::Json<Req>>,
     path: web::Path<(UID, i64)>,
 ) -> impl Responder {
     let mut conn = database.connection().await?;
@@ -19,8 +20,14 @@ pub async fn handle(
     let state = State::load(&session)?;
     let (user_uid, request_uid) = path.into_inner();
     {
-        let res =
-            execute(&mut tx, state.clone(), &user_uid, &request_uid).await?;
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &req.map(web::Json::into_inner).unwrap_or_default(),
+            &user_uid,
+            &request_uid,
+        )
+        .await?;
         Notify::MemberAndParents {
             event: Event::RequestDeclined {
                 request: res.request.clone(),
@@ -39,6 +46,7 @@ pub async fn handle(
 pub async fn execute<'a>(
     tx: &mut Tx<'a>,
     state: State,
+    req: &Req,
     user_uid: &UID,
     request_uid: &i64,
 ) -> Result<Res, api::Error> {
@@ -49,15 +57,31 @@ pub async fn execute<'a>(
     let user = User::read(tx.as_mut(), user_uid)
         .await?
         .ok_or_else(|| api::Error::not_found("unknown request"))?;
-    match state.role {
-        Role::Parent => state.assert_family(&user.family_uid)?,
-        Role::Child => state.assert_user(&user.uid)?,
-    };
+    let state = state
+        .assert_role(Role::Parent)?
+        .assert_family(&user.family_uid)?;
+    if request.status != RequestStatus::Pending {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "request already decided",
+        ));
+    }
 
-    request.delete(tx.as_mut()).await?;
+    let request = Request {
+        reason: req.reason.clone().filter(|reason| !reason.is_empty()),
+        ..request.decide(RequestStatus::Declined, Some(&state.user_uid), None)
+    };
+    request.update(tx.as_mut()).await?;
     Ok(Res { request })
 }
 
+#[derive(Default, Deserialize, Serialize)]
+pub struct Req {
+    /// An optional explanation of why the request was declined.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct Res {
     /// The request that was declined.
@@ -95,43 +119,8 @@ mod tests {
                     role: parent.role.clone(),
                     family_uid: family.uid.clone(),
                 },
-                &children.0.uid,
-                &candidate.uid,
-            )
-            .await
-            .unwrap();
-            tx.commit().await.unwrap();
-            r
-        };
-
-        assert_eq!(res.request, candidate);
-        assert!(Request::read(conn.as_mut(), &candidate.uid)
-            .await
-            .unwrap()
-            .is_none());
-    }
-
-    #[actix_rt::test]
-    async fn success_child() {
-        let database = test_engine().await;
-        let mut conn = database.connection().await.unwrap();
-        let (family, _, children, _, requests) =
-            tests::populate(&mut conn).unwrap();
-        let candidate = requests
-            .iter()
-            .filter(|r| r.user_uid == children.0.uid)
-            .next()
-            .unwrap()
-            .clone();
-
-        let res = {
-            let mut tx = conn.begin().await.unwrap();
-            let r = execute(
-                &mut tx,
-                State {
-                    user_uid: children.0.uid.clone(),
-                    role: children.0.role.clone(),
-                    family_uid: family.uid.clone(),
+                &Req {
+                    reason: Some("Too expensive".into()),
                 },
                 &children.0.uid,
                 &candidate.uid,
@@ -142,11 +131,13 @@ mod tests {
             r
         };
 
-        assert_eq!(res.request, candidate);
-        assert!(Request::read(conn.as_mut(), &candidate.uid)
-            .await
-            .unwrap()
-            .is_none());
+        assert_eq!(res.request.status, RequestStatus::Declined);
+        assert_eq!(res.request.reason, Some("Too expensive".into()));
+        assert_eq!(res.request.decided_by, Some(parent.uid.clone()));
+        assert_eq!(
+            Request::read(conn.as_mut(), &candidate.uid).await.unwrap(),
+            Some(res.request),
+        );
     }
 
     #[actix_rt::test]
@@ -178,6 +169,7 @@ mod tests {
                     role: other_parent.role.clone(),
                     family_uid: other_family.uid.clone(),
                 },
+                &Req::default(),
                 &children.0.uid,
                 &candidate.uid,
             )
@@ -217,6 +209,7 @@ mod tests {
                     role: children.1.role.clone(),
                     family_uid: family.uid.clone(),
                 },
+                &Req::default(),
                 &children.0.uid,
                 &candidate.uid,
             )
@@ -227,7 +220,7 @@ mod tests {
             r
         };
 
-        assert_eq!(err, api::Error::forbidden("invalid user"));
+        assert_eq!(err, api::Error::forbidden("invalid role"));
         assert_eq!(
             Request::read(conn.as_mut(), &candidate.uid).await.unwrap(),
             Some(candidate),
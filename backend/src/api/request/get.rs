@@ -3,7 +3,7 @@ use crate::prelude::*;
 use crate::api;
 use crate::api::session::State;
 use crate::db;
-use crate::db::entities::{Request, User};
+use crate::db::entities::{Request, RequestComment, RequestContribution, User};
 use crate::db::values::{Role, UID};
 
 /// Retrieves all requests for a user.
@@ -42,7 +42,15 @@ pub async fn execute<'a>(
     if request.user_uid != user.uid {
         Err(api::Error::not_found("request not found"))
     } else {
-        Ok(Res { request })
+        let comments =
+            RequestComment::read_for_request(tx, &request.uid).await?;
+        let contributions =
+            RequestContribution::read_for_request(tx, &request.uid).await?;
+        Ok(Res {
+            request,
+            comments,
+            contributions,
+        })
     }
 }
 
@@ -50,6 +58,12 @@ pub async fn execute<'a>(
 pub struct Res {
     /// The request.
     request: db::entities::Request,
+
+    /// The comments on the request, oldest first.
+    comments: Vec<RequestComment>,
+
+    /// The contributions towards the cost, if the request was granted.
+    contributions: Vec<RequestContribution>,
 }
 
 #[cfg(test)]
@@ -73,6 +87,8 @@ mod tests {
             .next()
             .unwrap()
             .clone();
+        let comment =
+            create::request_comment(&mut conn, &request.uid, &parent.uid, "?");
 
         let res = {
             let mut tx = conn.begin().await.unwrap();
@@ -93,6 +109,8 @@ mod tests {
         };
 
         assert_eq!(res.request, request);
+        assert_eq!(res.comments, vec![comment]);
+        assert_eq!(res.contributions, Vec::new());
     }
 
     #[actix_rt::test]
@@ -3,10 +3,20 @@ use crate::prelude::*;
 use crate::api;
 use crate::api::notify::{Event, Notify};
 use crate::api::session::State;
-use crate::db::entities::{Request, Transaction, User};
-use crate::db::values::{Role, Timestamp, TransactionType, UID};
+use crate::db::entities::{
+    ApprovedContribution, FamilySettings, Request, RequestApproval,
+    RequestContribution, Subscription, Transaction, User,
+};
+use crate::db::values::{RequestStatus, Role, Timestamp, TransactionType, UID};
 
 /// Grants a request.
+///
+/// If the family requires approval by several parents for the request, this
+/// records the approval of the current parent, and the request is only
+/// granted once enough parents have approved it.
+///
+/// Granting a recurring request charges the first deduction immediately, and
+/// creates a subscription for the following ones.
 #[post("request/{user_uid}/{request_uid}")]
 pub async fn handle(
     database: web::Data<DatabaseEngine>,
@@ -28,13 +38,24 @@ pub async fn handle(
             &request_uid,
         )
         .await?;
-        Notify::MemberAndParents {
-            event: Event::RequestGranted {
-                request: res.request.clone(),
-                by: state.user_uid.clone(),
-            },
-            uid: user_uid,
-            family: state.family_uid,
+        if res.transaction.is_some() {
+            Notify::MemberAndParents {
+                event: Event::RequestGranted {
+                    request: res.request.clone(),
+                    by: state.user_uid.clone(),
+                },
+                uid: user_uid,
+                family: state.family_uid,
+            }
+        } else {
+            Notify::Parents {
+                event: Event::RequestApproved {
+                    request: res.request.clone(),
+                    approvals: res.approvals.clone(),
+                    by: state.user_uid.clone(),
+                },
+                family: state.family_uid,
+            }
         }
         .send(&mut tx, &channel, &state.user_uid)
         .await;
@@ -56,27 +77,255 @@ pub async fn execute<'a>(
     let user = User::read(tx.as_mut(), user_uid)
         .await?
         .ok_or_else(|| api::Error::not_found("unknown request"))?;
-    state
+    let state = state
         .assert_role(Role::Parent)?
         .assert_family(&user.family_uid)?;
 
     if request.user_uid != user.uid {
         Err(api::Error::not_found("unknown request"))
+    } else if request.status != RequestStatus::Pending {
+        Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "request already decided",
+        ))
     } else {
+        let grant = Grant::prepare(tx, &request, &user, req).await?;
+
+        let mut approvals =
+            RequestApproval::read_for_request(tx, &request.uid).await?;
+        if approvals.iter().any(|a| a.user_uid == state.user_uid) {
+            return Err(api::Error::Static(
+                StatusCode::CONFLICT,
+                "request already approved",
+            ));
+        }
+
+        // The terms of the first approval are binding for later approvals
+        let mut terms = grant.contributors.clone();
+        terms.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.1.cmp(&b.1)));
+        if let Some(first) = approvals.first() {
+            let approved =
+                ApprovedContribution::read_for_approval(tx, &first.uid)
+                    .await?
+                    .into_iter()
+                    .map(|c| (c.user_uid, c.name, c.amount))
+                    .collect::<Vec<_>>();
+            if first.cost != grant.cost
+                || first.jar_uid != grant.jar_uid
+                || approved != terms
+            {
+                return Err(api::Error::Static(
+                    StatusCode::CONFLICT,
+                    "approval terms differ",
+                ));
+            }
+        }
+        let approval = RequestApproval::new(
+            UID::new(),
+            request.uid,
+            state.user_uid.clone(),
+            Timestamp::now(),
+            grant.cost,
+            grant.jar_uid.clone(),
+        );
+        approval.create(tx.as_mut()).await?;
+        for (user_uid, name, amount) in terms {
+            ApprovedContribution::new(
+                UID::new(),
+                approval.uid.clone(),
+                user_uid,
+                name,
+                amount,
+            )
+            .create(tx.as_mut())
+            .await?;
+        }
+        approvals.push(approval);
+        let required = {
+            let settings =
+                FamilySettings::read_or_default(tx, &user.family_uid).await?;
+            let parents = User::read_by_family(tx, &user.family_uid)
+                .await?
+                .into_iter()
+                .filter(|u| u.role == Role::Parent)
+                .count();
+            settings
+                .approvals_for(request.amount.max(approvals[0].cost))
+                .min(parents.max(1))
+        };
+        if approvals.len() < required {
+            return Ok(Res {
+                request,
+                transaction: None,
+                contributions: Vec::new(),
+                approvals,
+                subscription: None,
+            });
+        }
+
+        let cost = grant.cost;
+        let jar_uid = grant.jar_uid.clone();
+        let (request, transaction, contributions) = grant
+            .book(tx, request, Some(&state.user_uid), TransactionType::Request)
+            .await?;
+        let subscription = match &request.schedule {
+            Some(schedule) => {
+                let subscription = Subscription::new(
+                    UID::new(),
+                    request.user_uid.clone(),
+                    request.uid,
+                    request.ledger_uid.clone(),
+                    jar_uid,
+                    request.name.clone(),
+                    cost,
+                    schedule.clone(),
+                    request.ends,
+                    false,
+                    None,
+                    Some(transaction.time),
+                );
+                subscription.create(tx.as_mut()).await?;
+                Some(subscription)
+            }
+            None => None,
+        };
+        Ok(Res {
+            request,
+            transaction: Some(transaction),
+            contributions,
+            approvals,
+            subscription,
+        })
+    }
+}
+
+/// A validated grant of a request, ready to be booked.
+pub struct Grant {
+    /// The cost charged to the child.
+    pub cost: i64,
+
+    /// The jar from which to draw the cost.
+    pub jar_uid: Option<UID>,
+
+    /// The contributors and their contributions.
+    contributors: Vec<(Option<UID>, String, i64)>,
+}
+
+impl Grant {
+    /// Validates the parameters for granting a request.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `request` - The request to grant.
+    /// *  `user` - The user that made the request.
+    /// *  `req` - The grant parameters.
+    pub async fn prepare<'a>(
+        tx: &mut Tx<'a>,
+        request: &Request,
+        user: &User,
+        req: &Req,
+    ) -> Result<Self, api::Error> {
+        let cost = req.cost.unwrap_or(request.amount);
+        let jar_uid =
+            req.jar_uid.clone().filter(|_| request.ledger_uid.is_none());
+        api::user::jars::check(tx, &user.uid, jar_uid.as_ref()).await?;
+        let mut contributors = Vec::with_capacity(req.contributions.len());
+        for contribution in &req.contributions {
+            if contribution.amount <= 0 {
+                return Err(api::Error::Static(
+                    StatusCode::BAD_REQUEST,
+                    "invalid contribution",
+                ));
+            }
+            let (user_uid, name) = match &contribution.user_uid {
+                Some(uid) => User::read(tx.as_mut(), uid)
+                    .await?
+                    .filter(|u| u.family_uid == user.family_uid)
+                    .map(|u| (Some(u.uid), u.name))
+                    .ok_or_else(|| api::Error::not_found("unknown user"))?,
+                None => (
+                    None,
+                    api::argument(
+                        contribution
+                            .name
+                            .clone()
+                            .filter(|name| !name.is_empty()),
+                    )?,
+                ),
+            };
+            contributors.push((user_uid, name, contribution.amount));
+        }
+        if contributors.iter().map(|c| c.2).sum::<i64>() > cost {
+            return Err(api::Error::Static(
+                StatusCode::BAD_REQUEST,
+                "invalid contribution",
+            ));
+        }
+
+        Ok(Self {
+            cost,
+            jar_uid,
+            contributors,
+        })
+    }
+
+    /// Books the transactions for a granted request, and marks it as granted.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `request` - The request to grant.
+    /// *  `by` - The parent granting the request, or `None` if it is granted
+    ///    automatically.
+    /// *  `transaction_type` - The type of the transaction charging the cost.
+    pub async fn book<'a>(
+        self,
+        tx: &mut Tx<'a>,
+        request: Request,
+        by: Option<&UID>,
+        transaction_type: TransactionType,
+    ) -> Result<(Request, Transaction, Vec<RequestContribution>), api::Error>
+    {
+        let now = Timestamp::now();
         let transaction = Transaction::create_with_auto_uid(
             tx,
-            TransactionType::Request,
-            user.uid.clone(),
+            transaction_type,
+            request.user_uid.clone(),
+            request.ledger_uid.clone(),
+            self.jar_uid.clone(),
             request.name.clone(),
-            -(req.cost.unwrap_or(request.amount) as i64),
-            Timestamp::now(),
+            -self.cost,
+            now,
         )
         .await?;
-        request.delete(tx.as_mut()).await?;
-        Ok(Res {
-            request,
-            transaction,
-        })
+        let mut contributions = Vec::with_capacity(self.contributors.len());
+        for (user_uid, name, amount) in self.contributors {
+            let gift = Transaction::create_with_auto_uid(
+                tx,
+                TransactionType::Gift,
+                request.user_uid.clone(),
+                request.ledger_uid.clone(),
+                self.jar_uid.clone(),
+                request.name.clone(),
+                amount,
+                now,
+            )
+            .await?;
+            let contribution = RequestContribution::new(
+                UID::new(),
+                request.uid,
+                user_uid,
+                name,
+                amount,
+                gift.uid,
+            );
+            contribution.create(tx.as_mut()).await?;
+            contributions.push(contribution);
+        }
+
+        let request =
+            request.decide(RequestStatus::Granted, by, Some(transaction.uid));
+        request.update(tx.as_mut()).await?;
+        Ok((request, transaction, contributions))
     }
 }
 
@@ -86,6 +335,39 @@ pub struct Req {
     ///
     /// If this is not present, the value from the database is used.
     pub cost: Option<i64>,
+
+    /// The jar from which to draw the cost.
+    ///
+    /// If this is not present, the cost is not drawn from any specific jar.
+    /// This is ignored for requests in non-monetary ledgers.
+    #[serde(default)]
+    pub jar_uid: Option<UID>,
+
+    /// Contributions towards the cost by parents or relatives.
+    ///
+    /// Each contribution is booked as a gift to the child, so that the child
+    /// only pays the remainder of the cost.
+    #[serde(default)]
+    pub contributions: Vec<Contribution>,
+}
+
+/// A contribution towards the cost of a granted request.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Contribution {
+    /// The family member contributing.
+    ///
+    /// If this is not present, `name` must be.
+    #[serde(default)]
+    pub user_uid: Option<UID>,
+
+    /// The name of a contributor outside the family.
+    ///
+    /// This is ignored if `user_uid` is present.
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The amount contributed.
+    pub amount: i64,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -94,15 +376,28 @@ pub struct Res {
     pub request: Request,
 
     /// The generated transaction.
-    pub transaction: Transaction,
+    ///
+    /// This is not present if the request requires more approvals.
+    pub transaction: Option<Transaction>,
+
+    /// The contributions towards the cost.
+    pub contributions: Vec<RequestContribution>,
+
+    /// The approvals of the request, including that of the current parent.
+    pub approvals: Vec<RequestApproval>,
+
+    /// The subscription created when granting a recurring request.
+    pub subscription: Option<Subscription>,
 }
 
 #[cfg(test)]
 mod tests {
+    use weru::database::Connection;
+
     use crate::api::tests;
     use crate::db::entities::create;
     use crate::db::test_engine;
-    use crate::db::values::Role;
+    use crate::db::values::{Role, Schedule};
 
     use super::*;
 
@@ -128,7 +423,11 @@ mod tests {
                     role: parent.role.clone(),
                     family_uid: family.uid.clone(),
                 },
-                &Req { cost: None },
+                &Req {
+                    cost: None,
+                    jar_uid: None,
+                    contributions: Vec::new(),
+                },
                 &children.0.uid,
                 &candidate.uid,
             )
@@ -138,16 +437,20 @@ mod tests {
             r
         };
 
-        assert_eq!(res.transaction.amount, -candidate.amount);
-        assert!(Request::read(conn.as_mut(), &candidate.uid)
-            .await
-            .unwrap()
-            .is_none());
+        let transaction = res.transaction.unwrap();
+        assert_eq!(transaction.amount, -candidate.amount);
+        assert_eq!(res.request.status, RequestStatus::Granted);
+        assert_eq!(res.request.decided_by, Some(parent.uid.clone()));
+        assert_eq!(res.request.transaction_uid, Some(transaction.uid));
+        assert_eq!(
+            Request::read(conn.as_mut(), &candidate.uid).await.unwrap(),
+            Some(res.request),
+        );
         assert_eq!(
-            Transaction::read(conn.as_mut(), &res.transaction.uid)
+            Transaction::read(conn.as_mut(), &transaction.uid)
                 .await
                 .unwrap(),
-            Some(res.transaction),
+            Some(transaction),
         );
     }
 
@@ -174,7 +477,68 @@ mod tests {
                     role: parent.role.clone(),
                     family_uid: family.uid.clone(),
                 },
-                &Req { cost: Some(cost) },
+                &Req {
+                    cost: Some(cost),
+                    jar_uid: None,
+                    contributions: Vec::new(),
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        let transaction = res.transaction.unwrap();
+        assert_eq!(transaction.amount, -cost);
+        assert_eq!(res.request.status, RequestStatus::Granted);
+        assert_eq!(res.request.decided_by, Some(parent.uid.clone()));
+        assert_eq!(res.request.transaction_uid, Some(transaction.uid));
+        assert_eq!(
+            Request::read(conn.as_mut(), &candidate.uid).await.unwrap(),
+            Some(res.request),
+        );
+        assert_eq!(
+            Transaction::read(conn.as_mut(), &transaction.uid)
+                .await
+                .unwrap(),
+            Some(transaction),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn success_recurring() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = Request {
+            schedule: Some(Schedule::Monthly(1)),
+            ..requests
+                .iter()
+                .filter(|r| r.user_uid == children.0.uid)
+                .next()
+                .unwrap()
+                .clone()
+        };
+        candidate.update(conn.as_mut()).await.unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    cost: None,
+                    jar_uid: None,
+                    contributions: Vec::new(),
+                },
                 &children.0.uid,
                 &candidate.uid,
             )
@@ -184,16 +548,377 @@ mod tests {
             r
         };
 
-        assert_eq!(res.transaction.amount, -cost);
-        assert!(Request::read(conn.as_mut(), &candidate.uid)
+        let transaction = res.transaction.unwrap();
+        let subscription = res.subscription.unwrap();
+        assert_eq!(subscription.request_uid, candidate.uid);
+        assert_eq!(subscription.user_uid, children.0.uid);
+        assert_eq!(subscription.amount, candidate.amount);
+        assert_eq!(subscription.schedule, Schedule::Monthly(1));
+        assert_eq!(subscription.booked, Some(transaction.time));
+        assert_eq!(
+            Subscription::read(conn.as_mut(), &subscription.uid)
+                .await
+                .unwrap(),
+            Some(subscription),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn success_with_jar() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+        let jar = create::jar(&mut conn, &children.0.uid, "spend", 100);
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    cost: None,
+                    jar_uid: Some(jar.uid.clone()),
+                    contributions: Vec::new(),
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
             .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        let transaction = res.transaction.unwrap();
+        assert_eq!(transaction.jar_uid, Some(jar.uid));
+        assert_eq!(transaction.amount, -candidate.amount);
+    }
+
+    #[actix_rt::test]
+    async fn success_with_contributions() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
             .unwrap()
-            .is_none());
+            .clone();
+        let cost = 1000;
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    cost: Some(cost),
+                    jar_uid: None,
+                    contributions: vec![
+                        Contribution {
+                            user_uid: Some(parent.uid.clone()),
+                            name: None,
+                            amount: 500,
+                        },
+                        Contribution {
+                            user_uid: None,
+                            name: Some("Grandma".into()),
+                            amount: 200,
+                        },
+                    ],
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.transaction.unwrap().amount, -cost);
+        assert_eq!(res.contributions.len(), 2);
+        assert_eq!(res.contributions[0].user_uid, Some(parent.uid.clone()));
+        assert_eq!(res.contributions[0].name, parent.name);
+        assert_eq!(res.contributions[1].user_uid, None);
+        assert_eq!(res.contributions[1].name, "Grandma");
+        for contribution in &res.contributions {
+            let gift =
+                Transaction::read(conn.as_mut(), &contribution.transaction_uid)
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(gift.transaction_type, TransactionType::Gift);
+            assert_eq!(gift.amount, contribution.amount);
+        }
+        let mut tx = conn.begin().await.unwrap();
         assert_eq!(
-            Transaction::read(conn.as_mut(), &res.transaction.uid)
+            RequestContribution::read_for_request(&mut tx, &candidate.uid)
                 .await
                 .unwrap(),
-            Some(res.transaction),
+            res.contributions,
+        );
+    }
+
+    /// Grants a request as a specific parent without overrides.
+    async fn grant_as(
+        conn: &mut Connection,
+        parent: &User,
+        request: &Request,
+    ) -> Result<Res, api::Error> {
+        let mut tx = conn.begin().await.unwrap();
+        let r = execute(
+            &mut tx,
+            State {
+                user_uid: parent.uid.clone(),
+                role: parent.role.clone(),
+                family_uid: parent.family_uid.clone(),
+            },
+            &Req {
+                cost: None,
+                jar_uid: None,
+                contributions: Vec::new(),
+            },
+            &request.user_uid,
+            &request.uid,
+        )
+        .await;
+        tx.commit().await.unwrap();
+        r
+    }
+
+    #[actix_rt::test]
+    async fn success_with_quorum() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let other_parent = create::user(
+            &mut conn,
+            Role::Parent,
+            "Other Parent",
+            "other@example.com",
+            &family.uid,
+        );
+        FamilySettings::new(family.uid.clone(), Some(0), 2, None, None, 0, 0)
+            .create(conn.as_mut())
+            .await
+            .unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+        let res = grant_as(&mut conn, &parent, &candidate).await.unwrap();
+        assert!(res.transaction.is_none());
+        assert_eq!(res.request.status, RequestStatus::Pending);
+        assert_eq!(res.approvals.len(), 1);
+
+        assert_eq!(
+            grant_as(&mut conn, &parent, &candidate)
+                .await
+                .err()
+                .unwrap(),
+            api::Error::Static(
+                StatusCode::CONFLICT,
+                "request already approved"
+            ),
+        );
+
+        let res = grant_as(&mut conn, &other_parent, &candidate)
+            .await
+            .unwrap();
+        assert_eq!(res.transaction.unwrap().amount, -candidate.amount);
+        assert_eq!(res.request.status, RequestStatus::Granted);
+        assert_eq!(res.request.decided_by, Some(other_parent.uid.clone()));
+        assert_eq!(res.approvals.len(), 2);
+    }
+
+    #[actix_rt::test]
+    async fn quorum_terms_differ() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let other_parent = create::user(
+            &mut conn,
+            Role::Parent,
+            "Other Parent",
+            "other@example.com",
+            &family.uid,
+        );
+        FamilySettings::new(family.uid.clone(), Some(0), 2)
+            .create(conn.as_mut())
+            .await
+            .unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+        grant_as(&mut conn, &parent, &candidate).await.unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: other_parent.uid.clone(),
+                    role: other_parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    cost: Some(candidate.amount * 100),
+                    jar_uid: None,
+                    contributions: Vec::new(),
+                },
+                &candidate.user_uid,
+                &candidate.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "approval terms differ"),
+        );
+        let mut tx = conn.begin().await.unwrap();
+        let approvals =
+            RequestApproval::read_for_request(&mut tx, &candidate.uid)
+                .await
+                .unwrap();
+        assert_eq!(approvals.len(), 1);
+        assert_eq!(approvals[0].cost, candidate.amount);
+        assert_eq!(
+            Request::read(tx.as_mut(), &candidate.uid)
+                .await
+                .unwrap()
+                .unwrap()
+                .status,
+            RequestStatus::Pending,
+        );
+    }
+
+    #[actix_rt::test]
+    async fn excessive_contributions() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    cost: Some(100),
+                    jar_uid: None,
+                    contributions: vec![Contribution {
+                        user_uid: Some(parent.uid.clone()),
+                        name: None,
+                        amount: 101,
+                    }],
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid contribution"),
+        );
+        assert_eq!(
+            Request::read(conn.as_mut(), &candidate.uid).await.unwrap(),
+            Some(candidate),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn already_decided() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone()
+            .decide(RequestStatus::Declined, Some(&parent.uid), None);
+        candidate.update(conn.as_mut()).await.unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    cost: None,
+                    jar_uid: None,
+                    contributions: Vec::new(),
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "request already decided"),
+        );
+        assert_eq!(
+            Request::read(conn.as_mut(), &candidate.uid).await.unwrap(),
+            Some(candidate),
         );
     }
 
@@ -226,7 +951,11 @@ mod tests {
                     role: other_parent.role.clone(),
                     family_uid: other_family.uid.clone(),
                 },
-                &Req { cost: None },
+                &Req {
+                    cost: None,
+                    jar_uid: None,
+                    contributions: Vec::new(),
+                },
                 &children.0.uid,
                 &candidate.uid,
             )
@@ -266,7 +995,11 @@ mod tests {
                     role: children.1.role.clone(),
                     family_uid: family.uid.clone(),
                 },
-                &Req { cost: None },
+                &Req {
+                    cost: None,
+                    jar_uid: None,
+                    contributions: Vec::new(),
+                },
                 &children.0.uid,
                 &candidate.uid,
             )
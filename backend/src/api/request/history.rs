@@ -0,0 +1,259 @@
+use crate::prelude::*;
+
+use std::ops::Range;
+
+use crate::api;
+use crate::api::session::State;
+use crate::db::entities::request::HistoryFilter;
+use crate::db::entities::{Request, User};
+use crate::db::values::{RequestStatus, Role, Timestamp, UID};
+
+/// Retrieves the request history of a family, including decided requests.
+#[get("requests/{family_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    session: Session,
+    family_uid: web::Path<UID>,
+    query: web::Query<Query>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    {
+        let res = execute(
+            &mut tx,
+            state,
+            &query.into_inner(),
+            &family_uid.into_inner(),
+        )
+        .await?;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    query: &Query,
+    family_uid: &UID,
+) -> Result<Res, api::Error> {
+    let state = state.assert_family(family_uid)?;
+    let user_uid = match state.role {
+        Role::Parent => query.user_uid.clone(),
+        Role::Child => {
+            if let Some(user_uid) = &query.user_uid {
+                state.clone().assert_user(user_uid)?;
+            }
+            Some(state.user_uid.clone())
+        }
+    };
+    if let Some(user_uid) = &user_uid {
+        User::read(tx.as_mut(), user_uid)
+            .await?
+            .filter(|user| &user.family_uid == family_uid)
+            .ok_or_else(|| api::Error::forbidden("invalid user"))?;
+    }
+
+    let requests = Request::history(
+        tx,
+        family_uid,
+        &HistoryFilter {
+            user_uid,
+            status: query.status,
+            since: query.since,
+            until: query.until,
+        },
+        query.clone().into(),
+    )
+    .await?;
+
+    Ok(Res { requests })
+}
+
+#[derive(Clone, Deserialize)]
+pub struct Query {
+    /// Only include requests made by this user.
+    ///
+    /// For children, this is always their own UID.
+    user_uid: Option<UID>,
+
+    /// Only include requests with this status.
+    status: Option<RequestStatus>,
+
+    /// Only include requests made at or after this time.
+    since: Option<Timestamp>,
+
+    /// Only include requests made before this time.
+    until: Option<Timestamp>,
+
+    /// The maximum number of requests to return.
+    limit: usize,
+
+    /// The offset from which to start.
+    offset: usize,
+}
+
+impl From<Query> for Range<usize> {
+    fn from(source: Query) -> Self {
+        source.offset..(source.offset + source.limit)
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The matching requests, most recent first.
+    requests: Vec<Request>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    fn query() -> Query {
+        Query {
+            user_uid: None,
+            status: None,
+            since: None,
+            until: None,
+            limit: 100,
+            offset: 0,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn success_parent() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let declined = requests[0].clone().decide(
+            RequestStatus::Declined,
+            Some(&parent.uid),
+            None,
+        );
+        declined.update(conn.as_mut()).await.unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Query {
+                    status: Some(RequestStatus::Declined),
+                    ..query()
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.requests, vec![declined]);
+    }
+
+    #[actix_rt::test]
+    async fn success_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &query(),
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        let expected = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .collect::<Vec<_>>();
+        assert_eq!(res.requests.len(), expected.len());
+        for request in expected {
+            assert!(res.requests.contains(request));
+        }
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Query {
+                    user_uid: Some(children.1.uid.clone()),
+                    ..query()
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid user"));
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_family() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+        let other_family = create::family(&mut conn, "Other Family");
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &query(),
+                &other_family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid family"));
+    }
+}
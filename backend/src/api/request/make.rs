@@ -2,11 +2,15 @@ use crate::prelude::*;
 
 use crate::api;
 use crate::api::notify::{Event, Notify};
+use crate::api::request::grant;
 use crate::api::session::State;
-use crate::db::entities::{request, Request};
-use crate::db::values::{Role, Timestamp, UID};
+use crate::db::entities::{request, AutoApproval, Request, Transaction, User};
+use crate::db::values::{Role, Timestamp, TransactionType, UID};
 
 /// Generates a user request.
+///
+/// If the request matches the automatic approval rule of the child, it is
+/// granted immediately.
 #[post("request/{user_uid}")]
 pub async fn handle(
     database: web::Data<DatabaseEngine>,
@@ -27,9 +31,16 @@ pub async fn handle(
         )
         .await?;
         Notify::Parents {
-            event: Event::RequestCreated {
-                request: res.request.clone(),
-                by: state.user_uid.clone(),
+            event: match &res.transaction {
+                Some(transaction) => Event::RequestAutoApproved {
+                    request: res.request.clone(),
+                    transaction: transaction.clone(),
+                    by: state.user_uid.clone(),
+                },
+                None => Event::RequestCreated {
+                    request: res.request.clone(),
+                    by: state.user_uid.clone(),
+                },
             },
             family: state.family_uid,
         }
@@ -46,20 +57,62 @@ pub async fn execute<'a>(
     req: &Req,
     user_uid: &UID,
 ) -> Result<Res, api::Error> {
-    state.assert_user(&user_uid)?.assert_role(Role::Child)?;
+    let state = state.assert_user(&user_uid)?.assert_role(Role::Child)?;
+    let ledger_uid = req.ledger_uid.clone().flatten();
+    api::ledger::check(tx, &state.family_uid, ledger_uid.as_ref()).await?;
+    let now = Timestamp::now();
+    let schedule = req.schedule.clone().flatten();
+    let ends = req.ends.clone().flatten().filter(|_| schedule.is_some());
+    if ends.map(|ends| ends.0 <= now.0).unwrap_or(false) {
+        return Err(api::Error::Static(StatusCode::BAD_REQUEST, "invalid end"));
+    }
 
     let request = Request::create_with_auto_uid(
         tx,
         user_uid.clone(),
+        ledger_uid,
         api::argument(req.name.clone())?,
         api::argument(req.description.clone())?,
         api::argument(req.amount)?,
         req.url.clone().flatten(),
-        Timestamp::now(),
+        req.category
+            .clone()
+            .flatten()
+            .filter(|c| !c.trim().is_empty()),
+        schedule,
+        ends,
+        now,
     )
     .await?;
 
-    Ok(Res { request })
+    if let Some(rule) = AutoApproval::read(tx.as_mut(), user_uid).await? {
+        if rule.allows(tx, &request, request.time).await? {
+            let user = api::expect(User::read(tx.as_mut(), user_uid).await?)?;
+            let grant = grant::Grant::prepare(
+                tx,
+                &request,
+                &user,
+                &grant::Req {
+                    cost: None,
+                    jar_uid: None,
+                    contributions: Vec::new(),
+                },
+            )
+            .await?;
+            let (request, transaction, _) = grant
+                .book(tx, request, None, TransactionType::AutoApproved)
+                .await?;
+            return Ok(Res {
+                request,
+                transaction: Some(transaction),
+            });
+        }
+    }
+
+    Ok(Res {
+        request,
+        transaction: None,
+    })
 }
 
 pub type Req = request::RequestDescription;
@@ -68,12 +121,16 @@ pub type Req = request::RequestDescription;
 pub struct Res {
     /// The generated request.
     pub request: Request,
+
+    /// The transaction, if the request was granted automatically.
+    pub transaction: Option<Transaction>,
 }
 
 #[cfg(test)]
 mod tests {
     use crate::api::tests;
     use crate::db::test_engine;
+    use crate::db::values::{Period, RequestStatus, Schedule};
 
     use super::*;
 
@@ -109,6 +166,7 @@ mod tests {
         };
 
         assert_eq!(res.request.amount, amount);
+        assert!(res.transaction.is_none());
         assert_eq!(
             Request::read(conn.as_mut(), &res.request.uid)
                 .await
@@ -117,6 +175,111 @@ mod tests {
         );
     }
 
+    #[actix_rt::test]
+    async fn success_auto_approved() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        AutoApproval::new(
+            children.0.uid.clone(),
+            100,
+            "sweets".parse().unwrap(),
+            Some(150),
+            Period::Weekly,
+        )
+        .create(conn.as_mut())
+        .await
+        .unwrap();
+        let make = |category: &str| Req {
+            name: Some("A name".into()),
+            description: Some("A description!".into()),
+            amount: Some(100),
+            url: Some(None),
+            category: Some(Some(category.into())),
+            ..Default::default()
+        };
+
+        let (first, second, other) = {
+            let mut tx = conn.begin().await.unwrap();
+            let state = State {
+                user_uid: children.0.uid.clone(),
+                role: children.0.role.clone(),
+                family_uid: family.uid.clone(),
+            };
+            let first = execute(
+                &mut tx,
+                state.clone(),
+                &make("sweets"),
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            let second = execute(
+                &mut tx,
+                state.clone(),
+                &make("sweets"),
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            let other =
+                execute(&mut tx, state.clone(), &make("toys"), &children.0.uid)
+                    .await
+                    .unwrap();
+            tx.commit().await.unwrap();
+            (first, second, other)
+        };
+
+        let transaction = first.transaction.unwrap();
+        assert_eq!(transaction.transaction_type, TransactionType::AutoApproved);
+        assert_eq!(transaction.amount, -100);
+        assert_eq!(first.request.status, RequestStatus::Granted);
+        assert_eq!(first.request.decided_by, None);
+        assert_eq!(first.request.transaction_uid, Some(transaction.uid));
+        assert!(second.transaction.is_none());
+        assert_eq!(second.request.status, RequestStatus::Pending);
+        assert!(other.transaction.is_none());
+    }
+
+    #[actix_rt::test]
+    async fn invalid_end() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    name: Some("A name".into()),
+                    description: Some("A description!".into()),
+                    amount: Some(100),
+                    url: Some(None),
+                    schedule: Some(Some(Schedule::Monthly(1))),
+                    ends: Some(Some("2000-01-01T00:00:00Z".parse().unwrap())),
+                    ..Default::default()
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid end"),
+        );
+    }
+
     #[actix_rt::test]
     async fn forbidden() {
         let database = test_engine().await;
@@ -1,4 +1,8 @@
+pub mod comment;
 pub mod decline;
 pub mod get;
 pub mod grant;
+pub mod history;
 pub mod make;
+pub mod update;
+pub mod withdraw;
@@ -0,0 +1,353 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{request, Request, RequestApproval};
+use crate::db::values::{RequestStatus, Role, UID};
+
+/// Edits a pending request made by the current child.
+#[put("request/{user_uid}/{request_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<(UID, i64)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, request_uid) = path.into_inner();
+    {
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &req.into_inner(),
+            &user_uid,
+            &request_uid,
+        )
+        .await?;
+        Notify::Parents {
+            event: Event::RequestUpdated {
+                request: res.request.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+    request_uid: &i64,
+) -> Result<Res, api::Error> {
+    state.assert_user(user_uid)?.assert_role(Role::Child)?;
+    let request = Request::read(tx.as_mut(), request_uid)
+        .await?
+        .filter(|request| &request.user_uid == user_uid)
+        .ok_or_else(|| api::Error::not_found("unknown request"))?;
+    if request.status != RequestStatus::Pending {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "request already decided",
+        ));
+    }
+    if req.amount.map(|amount| amount <= 0).unwrap_or(false) {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid amount",
+        ));
+    }
+
+    let previous_amount = request.amount;
+    let request = request.merge(request::RequestDescription {
+        name: req.name.clone(),
+        description: req.description.clone(),
+        amount: req.amount,
+        url: req.url.clone(),
+        ..Default::default()
+    });
+    request.update(tx.as_mut()).await?;
+
+    // Approvals were given for the previous amount
+    if request.amount != previous_amount {
+        for approval in
+            RequestApproval::read_for_request(tx, &request.uid).await?
+        {
+            approval.delete(tx.as_mut()).await?;
+        }
+    }
+
+    Ok(Res { request })
+}
+
+/// The new values for the request.
+///
+/// Only `name`, `description`, `amount` and `url` are used.
+pub type Req = request::RequestDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The updated request.
+    pub request: Request,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+    use crate::db::values::Timestamp;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    amount: Some(1234),
+                    url: Some(None),
+                    ..Default::default()
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            res.request,
+            Request {
+                amount: 1234,
+                url: None,
+                ..candidate.clone()
+            },
+        );
+        assert_eq!(
+            Request::read(conn.as_mut(), &candidate.uid).await.unwrap(),
+            Some(res.request),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_amount() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+
+        for amount in [0, -1234] {
+            let err = {
+                let mut tx = conn.begin().await.unwrap();
+                let r = execute(
+                    &mut tx,
+                    State {
+                        user_uid: children.0.uid.clone(),
+                        role: children.0.role.clone(),
+                        family_uid: family.uid.clone(),
+                    },
+                    &Req {
+                        amount: Some(amount),
+                        ..Default::default()
+                    },
+                    &children.0.uid,
+                    &candidate.uid,
+                )
+                .await
+                .err()
+                .unwrap();
+                tx.commit().await.unwrap();
+                r
+            };
+
+            assert_eq!(
+                err,
+                api::Error::Static(StatusCode::BAD_REQUEST, "invalid amount"),
+            );
+        }
+        assert_eq!(
+            Request::read(conn.as_mut(), &candidate.uid).await.unwrap(),
+            Some(candidate),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn approvals_reset() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+        RequestApproval::new(
+            UID::new(),
+            candidate.uid,
+            parent.uid.clone(),
+            Timestamp::now(),
+            candidate.amount,
+            None,
+        )
+        .create(conn.as_mut())
+        .await
+        .unwrap();
+        let update = |amount| {
+            let state = State {
+                user_uid: children.0.uid.clone(),
+                role: children.0.role.clone(),
+                family_uid: family.uid.clone(),
+            };
+            let req = Req {
+                name: Some("Renamed".into()),
+                amount,
+                ..Default::default()
+            };
+            (state, req)
+        };
+
+        let mut tx = conn.begin().await.unwrap();
+        let (state, req) = update(Some(candidate.amount));
+        execute(&mut tx, state, &req, &children.0.uid, &candidate.uid)
+            .await
+            .unwrap();
+        assert_eq!(
+            RequestApproval::read_for_request(&mut tx, &candidate.uid)
+                .await
+                .unwrap()
+                .len(),
+            1,
+        );
+
+        let (state, req) = update(Some(candidate.amount + 1));
+        execute(&mut tx, state, &req, &children.0.uid, &candidate.uid)
+            .await
+            .unwrap();
+        assert_eq!(
+            RequestApproval::read_for_request(&mut tx, &candidate.uid)
+                .await
+                .unwrap(),
+            vec![],
+        );
+    }
+
+    #[actix_rt::test]
+    async fn already_decided() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone()
+            .decide(RequestStatus::Declined, Some(&parent.uid), None);
+        candidate.update(conn.as_mut()).await.unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    amount: Some(1234),
+                    ..Default::default()
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::CONFLICT, "request already decided"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_parent() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    amount: Some(1),
+                    ..Default::default()
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid user"));
+    }
+}
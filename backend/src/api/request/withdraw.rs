@@ -0,0 +1,187 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::Request;
+use crate::db::values::{RequestStatus, Role, UID};
+
+/// Withdraws a pending request made by the current child.
+#[post("request/{user_uid}/{request_uid}/withdraw")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, i64)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, request_uid) = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &user_uid, &request_uid).await?;
+        Notify::Parents {
+            event: Event::RequestWithdrawn {
+                request: res.request.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    user_uid: &UID,
+    request_uid: &i64,
+) -> Result<Res, api::Error> {
+    let state = state.assert_user(user_uid)?.assert_role(Role::Child)?;
+    let request = Request::read(tx.as_mut(), request_uid)
+        .await?
+        .filter(|request| &request.user_uid == user_uid)
+        .ok_or_else(|| api::Error::not_found("unknown request"))?;
+    if request.status != RequestStatus::Pending {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "request already decided",
+        ));
+    }
+
+    let request =
+        request.decide(RequestStatus::Withdrawn, Some(&state.user_uid), None);
+    request.update(tx.as_mut()).await?;
+    Ok(Res { request })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The request that was withdrawn.
+    pub request: Request,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.request.status, RequestStatus::Withdrawn);
+        assert_eq!(res.request.decided_by, Some(children.0.uid.clone()));
+        assert_eq!(
+            Request::read(conn.as_mut(), &candidate.uid).await.unwrap(),
+            Some(res.request),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_parent() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid user"));
+        assert_eq!(
+            Request::read(conn.as_mut(), &candidate.uid).await.unwrap(),
+            Some(candidate),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let candidate = requests
+            .iter()
+            .filter(|r| r.user_uid == children.0.uid)
+            .next()
+            .unwrap()
+            .clone();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.1.uid.clone(),
+                    role: children.1.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &children.0.uid,
+                &candidate.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid user"));
+    }
+}
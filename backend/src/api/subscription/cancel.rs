@@ -0,0 +1,166 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::Subscription;
+use crate::db::values::{Role, Timestamp, UID};
+
+/// Cancels a subscription.
+///
+/// A subscription may be cancelled by the child paying for it and by its
+/// parents. Deductions already made are kept.
+#[post("subscription/{user_uid}/{subscription_uid}/cancel")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, subscription_uid) = path.into_inner();
+    {
+        let res = execute(&mut tx, state.clone(), &user_uid, &subscription_uid)
+            .await?;
+        Notify::MemberAndParents {
+            event: Event::SubscriptionCancelled {
+                subscription: res.subscription.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    user_uid: &UID,
+    subscription_uid: &UID,
+) -> Result<Res, api::Error> {
+    let (user, subscription) =
+        super::load(tx, user_uid, subscription_uid).await?;
+    let state = state.assert_family(&user.family_uid)?;
+    if state.role == Role::Child {
+        state.assert_user(&user.uid)?;
+    }
+    if subscription.cancelled.is_some() {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "subscription already cancelled",
+        ));
+    }
+
+    let subscription = Subscription {
+        cancelled: Some(Timestamp::now()),
+        ..subscription
+    };
+    subscription.update(tx.as_mut()).await?;
+
+    Ok(Res { subscription })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The cancelled subscription.
+    pub subscription: Subscription,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let subscription = create::subscription(
+            &mut conn,
+            requests
+                .iter()
+                .find(|r| r.user_uid == children.0.uid)
+                .unwrap(),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &children.0.uid,
+                &subscription.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert!(res.subscription.cancelled.is_some());
+        assert_eq!(
+            Subscription::read(conn.as_mut(), &subscription.uid)
+                .await
+                .unwrap(),
+            Some(res.subscription),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_other_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let subscription = create::subscription(
+            &mut conn,
+            requests
+                .iter()
+                .find(|r| r.user_uid == children.0.uid)
+                .unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.1.uid.clone(),
+                    role: children.1.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &children.0.uid,
+                &subscription.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid user"));
+        assert_eq!(
+            Subscription::read(conn.as_mut(), &subscription.uid)
+                .await
+                .unwrap(),
+            Some(subscription),
+        );
+    }
+}
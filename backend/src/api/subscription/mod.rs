@@ -0,0 +1,29 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::db::entities::{Subscription, User};
+use crate::db::values::UID;
+
+pub mod cancel;
+pub mod pause;
+
+/// Loads a subscription and the child paying for it.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `user_uid` - The unique ID of the child.
+/// *  `subscription_uid` - The unique ID of the subscription.
+pub async fn load<'a>(
+    tx: &mut Tx<'a>,
+    user_uid: &UID,
+    subscription_uid: &UID,
+) -> Result<(User, Subscription), api::Error> {
+    let user = User::read(tx.as_mut(), user_uid)
+        .await?
+        .ok_or_else(|| api::Error::not_found("unknown subscription"))?;
+    let subscription = Subscription::read(tx.as_mut(), subscription_uid)
+        .await?
+        .filter(|subscription| subscription.user_uid == user.uid)
+        .ok_or_else(|| api::Error::not_found("unknown subscription"))?;
+    Ok((user, subscription))
+}
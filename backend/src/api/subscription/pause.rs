@@ -0,0 +1,180 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::Subscription;
+use crate::db::values::{Role, UID};
+
+/// Pauses or resumes a subscription.
+///
+/// No deductions are made while a subscription is paused.
+#[post("subscription/{user_uid}/{subscription_uid}/pause")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, subscription_uid) = path.into_inner();
+    {
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &req.into_inner(),
+            &user_uid,
+            &subscription_uid,
+        )
+        .await?;
+        Notify::MemberAndParents {
+            event: Event::SubscriptionPaused {
+                subscription: res.subscription.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+    subscription_uid: &UID,
+) -> Result<Res, api::Error> {
+    let (user, subscription) =
+        super::load(tx, user_uid, subscription_uid).await?;
+    state
+        .assert_role(Role::Parent)?
+        .assert_family(&user.family_uid)?;
+    if subscription.cancelled.is_some() {
+        return Err(api::Error::Static(
+            StatusCode::CONFLICT,
+            "subscription already cancelled",
+        ));
+    }
+
+    let subscription = Subscription {
+        paused: req.paused,
+        ..subscription
+    };
+    subscription.update(tx.as_mut()).await?;
+
+    Ok(Res { subscription })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Req {
+    /// Whether the subscription should be paused.
+    pub paused: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The updated subscription.
+    pub subscription: Subscription,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let subscription = create::subscription(
+            &mut conn,
+            requests
+                .iter()
+                .find(|r| r.user_uid == children.0.uid)
+                .unwrap(),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req { paused: true },
+                &children.0.uid,
+                &subscription.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert!(res.subscription.paused);
+        assert_eq!(
+            Subscription::read(conn.as_mut(), &subscription.uid)
+                .await
+                .unwrap(),
+            Some(res.subscription),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, requests) =
+            tests::populate(&mut conn).unwrap();
+        let subscription = create::subscription(
+            &mut conn,
+            requests
+                .iter()
+                .find(|r| r.user_uid == children.0.uid)
+                .unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req { paused: true },
+                &children.0.uid,
+                &subscription.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+        assert_eq!(
+            Subscription::read(conn.as_mut(), &subscription.uid)
+                .await
+                .unwrap(),
+            Some(subscription),
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use crate::prelude::*;
+
+use std::collections::HashSet;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{Transaction, User};
+use crate::db::values::{Role, Timestamp, TransactionType, UID};
+
+/// Generates transactions for several children of a family at once.
+///
+/// Every recipient receives either their own amount, the common amount, or
+/// an even share of a total. All transactions are booked in a single
+/// database transaction, and each child is notified of their transactions.
+#[post("transactions/{family_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    family_uid: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    {
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &req.into_inner(),
+            &family_uid.into_inner(),
+        )
+        .await?;
+        for user_uid in res
+            .transactions
+            .iter()
+            .map(|transaction| &transaction.user_uid)
+            .collect::<HashSet<_>>()
+        {
+            Notify::Member {
+                event: Event::TransactionsBooked {
+                    transactions: res
+                        .transactions
+                        .iter()
+                        .filter(|transaction| &transaction.user_uid == user_uid)
+                        .cloned()
+                        .collect(),
+                    by: state.user_uid.clone(),
+                },
+                user: user_uid.clone(),
+            }
+            .send(&mut tx, &channel, &state.user_uid)
+            .await;
+        }
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    family_uid: &UID,
+) -> Result<Res, api::Error> {
+    state.assert_role(Role::Parent)?.assert_family(family_uid)?;
+    api::ledger::check(tx, family_uid, req.ledger_uid.as_ref()).await?;
+    let user_uids = req
+        .recipients
+        .iter()
+        .map(|recipient| &recipient.user_uid)
+        .collect::<HashSet<_>>();
+    if req.recipients.is_empty() || user_uids.len() != req.recipients.len() {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid recipients",
+        ));
+    }
+    for user_uid in user_uids {
+        User::read(tx.as_mut(), user_uid)
+            .await?
+            .filter(|user| {
+                user.role == Role::Child && &user.family_uid == family_uid
+            })
+            .ok_or_else(|| api::Error::not_found("unknown user"))?;
+    }
+
+    let amounts = req.amounts()?;
+    let time = Timestamp::now();
+    let mut transactions = Vec::new();
+    for (recipient, amount) in req.recipients.iter().zip(amounts) {
+        transactions.extend(
+            super::book(
+                tx,
+                req.transaction_type,
+                recipient.user_uid.clone(),
+                req.ledger_uid.clone(),
+                None,
+                req.description.clone(),
+                amount,
+                time,
+            )
+            .await?,
+        );
+    }
+
+    Ok(Res { transactions })
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Req {
+    /// The type of the transactions.
+    pub transaction_type: TransactionType,
+
+    /// The description of the transactions.
+    pub description: String,
+
+    /// The ledger of the transactions, or `None` for the monetary ledger.
+    #[serde(default)]
+    pub ledger_uid: Option<UID>,
+
+    /// The children receiving the transactions.
+    pub recipients: Vec<Recipient>,
+
+    /// The amount for recipients without an amount of their own.
+    #[serde(default)]
+    pub amount: Option<i64>,
+
+    /// A total split evenly between all recipients.
+    ///
+    /// This cannot be combined with other amounts.
+    #[serde(default)]
+    pub total: Option<i64>,
+}
+
+impl Req {
+    /// The amounts for the recipients, in order.
+    ///
+    /// When a total is split, the remainder is distributed among the first
+    /// recipients, so that the amounts add up to the total.
+    pub fn amounts(&self) -> Result<Vec<i64>, api::Error> {
+        let invalid =
+            || api::Error::Static(StatusCode::BAD_REQUEST, "invalid amount");
+        let amounts = match self.total {
+            Some(total) => {
+                if self.amount.is_some()
+                    || self.recipients.iter().any(|r| r.amount.is_some())
+                {
+                    return Err(invalid());
+                }
+                let count = self.recipients.len() as i64;
+                (0..count)
+                    .map(|i| {
+                        total / count
+                            + if i < (total % count).abs() {
+                                total.signum()
+                            } else {
+                                0
+                            }
+                    })
+                    .collect::<Vec<_>>()
+            }
+            None => self
+                .recipients
+                .iter()
+                .map(|recipient| recipient.amount.or(self.amount))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?,
+        };
+        if amounts.iter().any(|&amount| amount == 0) {
+            Err(invalid())
+        } else {
+            Ok(amounts)
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Recipient {
+    /// The child receiving the transaction.
+    pub user_uid: UID,
+
+    /// The amount for this child, or `None` to use the common amount.
+    #[serde(default)]
+    pub amount: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The generated transactions.
+    ///
+    /// Gifts not targeting a specific ledger are split between the jars of
+    /// each child, which generates one transaction per jar.
+    pub transactions: Vec<Transaction>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    fn recipient(user_uid: &UID, amount: Option<i64>) -> Recipient {
+        Recipient {
+            user_uid: user_uid.clone(),
+            amount,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    transaction_type: TransactionType::Gift,
+                    description: "Christmas".into(),
+                    ledger_uid: None,
+                    recipients: vec![
+                        recipient(&children.0.uid, None),
+                        recipient(&children.1.uid, Some(150)),
+                    ],
+                    amount: Some(100),
+                    total: None,
+                },
+                &family.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            res.transactions
+                .iter()
+                .map(|t| (t.user_uid.clone(), t.amount))
+                .collect::<Vec<_>>(),
+            vec![(children.0.uid.clone(), 100), (children.1.uid.clone(), 150)],
+        );
+        for transaction in res.transactions {
+            assert_eq!(transaction.transaction_type, TransactionType::Gift);
+            assert_eq!(transaction.description, "Christmas");
+            assert_eq!(
+                Transaction::read(conn.as_mut(), &transaction.uid)
+                    .await
+                    .unwrap(),
+                Some(transaction),
+            );
+        }
+    }
+
+    #[actix_rt::test]
+    async fn unknown_user() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    transaction_type: TransactionType::Gift,
+                    description: "Christmas".into(),
+                    ledger_uid: None,
+                    recipients: vec![
+                        recipient(&children.0.uid, None),
+                        recipient(&parent.uid, None),
+                    ],
+                    amount: Some(100),
+                    total: None,
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown user"));
+        assert_eq!(Transaction::list(conn.as_mut()).await.unwrap().len(), 0);
+    }
+
+    #[actix_rt::test]
+    async fn forbidden() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    transaction_type: TransactionType::Gift,
+                    description: "Christmas".into(),
+                    ledger_uid: None,
+                    recipients: vec![recipient(&children.0.uid, None)],
+                    amount: Some(100),
+                    total: None,
+                },
+                &family.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+
+    #[test]
+    fn amounts() {
+        let (a, b, c) = (UID::new(), UID::new(), UID::new());
+        let req = |amount, total, own| Req {
+            transaction_type: TransactionType::Gift,
+            description: "Christmas".into(),
+            ledger_uid: None,
+            recipients: vec![
+                recipient(&a, own),
+                recipient(&b, None),
+                recipient(&c, None),
+            ],
+            amount,
+            total,
+        };
+        let invalid = Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid amount",
+        ));
+
+        assert_eq!(req(Some(10), None, Some(5)).amounts(), Ok(vec![5, 10, 10]));
+        assert_eq!(req(None, Some(100), None).amounts(), Ok(vec![34, 33, 33]));
+        assert_eq!(
+            req(None, Some(-100), None).amounts(),
+            Ok(vec![-34, -33, -33]),
+        );
+        assert_eq!(req(None, None, Some(5)).amounts(), invalid);
+        assert_eq!(req(Some(10), Some(100), None).amounts(), invalid);
+        assert_eq!(req(None, Some(2), None).amounts(), invalid);
+    }
+}
@@ -37,31 +37,44 @@ pub async fn execute<'a>(
     state
         .assert_role(Role::Parent)?
         .assert_family(&user.family_uid)?;
+    let ledger_uid = req.ledger_uid.clone().flatten();
+    api::ledger::check(tx, &user.family_uid, ledger_uid.as_ref()).await?;
+    let jar_uid = req.jar_uid.clone().flatten();
+    api::user::jars::check(tx, &user.uid, jar_uid.as_ref()).await?;
 
-    let transaction = Transaction::create_with_auto_uid(
+    let transaction_type = api::argument(req.transaction_type)?;
+    let description = api::argument(req.description.clone())?;
+    let amount = api::argument(req.amount)?;
+    let transactions = super::book(
         tx,
-        api::argument(req.transaction_type)?,
+        transaction_type,
         user_uid.clone(),
-        api::argument(req.description.clone())?,
-        api::argument(req.amount)?,
+        ledger_uid,
+        jar_uid,
+        description,
+        amount,
         Timestamp::now(),
     )
     .await?;
 
-    Ok(Res { transaction })
+    Ok(Res { transactions })
 }
 
 pub type Req = transaction::TransactionDescription;
 
 #[derive(Deserialize, Serialize)]
 pub struct Res {
-    /// The generated transaction.
-    pub transaction: Transaction,
+    /// The generated transactions.
+    ///
+    /// Gifts not targeting a specific jar are split between the jars of the
+    /// user, which generates one transaction per jar.
+    pub transactions: Vec<Transaction>,
 }
 
 #[cfg(test)]
 mod tests {
     use crate::api::tests;
+    use crate::db::entities::create;
     use crate::db::test_engine;
     use crate::db::values::TransactionType;
 
@@ -97,17 +110,114 @@ mod tests {
             r
         };
 
-        assert_eq!(res.transaction.transaction_type, TransactionType::Gift);
-        assert_eq!(res.transaction.description, String::from("A description!"));
-        assert_eq!(res.transaction.amount, amount);
+        assert_eq!(res.transactions.len(), 1);
+        let transaction = res.transactions[0].clone();
+        assert_eq!(transaction.transaction_type, TransactionType::Gift);
+        assert_eq!(transaction.description, String::from("A description!"));
+        assert_eq!(transaction.amount, amount);
         assert_eq!(
-            Transaction::read(conn.as_mut(), &res.transaction.uid)
+            Transaction::read(conn.as_mut(), &transaction.uid)
                 .await
                 .unwrap(),
-            Some(res.transaction),
+            Some(transaction),
         );
     }
 
+    #[actix_rt::test]
+    async fn success_jars() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (_, parent, children, _, _) = tests::populate(&mut conn).unwrap();
+        let spend = create::jar(&mut conn, &children.0.uid, "spend", 50);
+        let save = create::jar(&mut conn, &children.0.uid, "save", 50);
+        let state = State {
+            user_uid: parent.uid.clone(),
+            role: parent.role.clone(),
+            family_uid: parent.family_uid.clone(),
+        };
+
+        let (split, single) = {
+            let mut tx = conn.begin().await.unwrap();
+            let split = execute(
+                &mut tx,
+                state.clone(),
+                &Req {
+                    transaction_type: Some(TransactionType::Gift),
+                    description: Some("A description!".into()),
+                    amount: Some(100),
+                    ..Default::default()
+                },
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            let single = execute(
+                &mut tx,
+                state.clone(),
+                &Req {
+                    transaction_type: Some(TransactionType::Gift),
+                    description: Some("A description!".into()),
+                    amount: Some(100),
+                    jar_uid: Some(Some(save.uid.clone())),
+                    ..Default::default()
+                },
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            (split, single)
+        };
+
+        assert_eq!(split.transactions.len(), 2);
+        assert_eq!(single.transactions.len(), 1);
+        let mut tx = conn.begin().await.unwrap();
+        assert_eq!(
+            Transaction::jar_balances(&mut tx, &children.0.uid)
+                .await
+                .unwrap(),
+            [(spend.uid.clone(), 50), (save.uid.clone(), 150)]
+                .iter()
+                .cloned()
+                .collect(),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn unknown_jar() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (_, parent, children, _, _) = tests::populate(&mut conn).unwrap();
+        let jar = create::jar(&mut conn, &children.1.uid, "save", 100);
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: parent.family_uid.clone(),
+                },
+                &Req {
+                    transaction_type: Some(TransactionType::Gift),
+                    description: Some("A description!".into()),
+                    amount: Some(100),
+                    jar_uid: Some(Some(jar.uid.clone())),
+                    ..Default::default()
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown jar"));
+    }
+
     #[actix_rt::test]
     async fn forbidden() {
         let database = test_engine().await;
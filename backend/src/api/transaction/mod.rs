@@ -1,2 +1,65 @@
+use crate::prelude::*;
+
+use crate::db::entities::Transaction;
+use crate::db::values::{Timestamp, TransactionType, UID};
+
+pub mod bulk;
 pub mod create;
 pub mod list;
+pub mod pending;
+
+/// Books a transaction for a user.
+///
+/// Gifts not targeting a specific ledger or jar are split between the jars of
+/// the user, which generates one transaction per jar.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `transaction_type` - The transaction type.
+/// *  `user_uid` - The user involved in the transaction.
+/// *  `ledger_uid` - The ledger, or `None` for the monetary ledger.
+/// *  `jar_uid` - The jar, or `None` to split gifts between jars.
+/// *  `description` - A description of the transaction.
+/// *  `amount` - The amount.
+/// *  `time` - The time of the transaction.
+#[allow(clippy::too_many_arguments)]
+pub async fn book<'a>(
+    tx: &mut Tx<'a>,
+    transaction_type: TransactionType,
+    user_uid: UID,
+    ledger_uid: Option<UID>,
+    jar_uid: Option<UID>,
+    description: String,
+    amount: i64,
+    time: Timestamp,
+) -> Result<Vec<Transaction>, DatabaseError> {
+    if transaction_type == TransactionType::Gift
+        && amount > 0
+        && ledger_uid.is_none()
+        && jar_uid.is_none()
+    {
+        Transaction::create_split_with_auto_uid(
+            tx,
+            transaction_type,
+            user_uid,
+            description,
+            amount,
+            time,
+        )
+        .await
+    } else {
+        Ok(vec![
+            Transaction::create_with_auto_uid(
+                tx,
+                transaction_type,
+                user_uid,
+                ledger_uid,
+                jar_uid,
+                description,
+                amount,
+                time,
+            )
+            .await?,
+        ])
+    }
+}
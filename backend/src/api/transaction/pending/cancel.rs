@@ -0,0 +1,139 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::PendingTransaction;
+use crate::db::values::{Role, UID};
+
+/// Cancels a pending transaction before it is booked.
+#[delete("transaction/{user_uid}/pending/{pending_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, pending_uid) = path.into_inner();
+    {
+        let res =
+            execute(&mut tx, state.clone(), &user_uid, &pending_uid).await?;
+        Notify::Parents {
+            event: Event::PendingTransactionCancelled {
+                pending: res.pending.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    user_uid: &UID,
+    pending_uid: &UID,
+) -> Result<Res, api::Error> {
+    let (user, pending) = super::load(tx, user_uid, pending_uid).await?;
+    state
+        .assert_role(Role::Parent)?
+        .assert_family(&user.family_uid)?;
+
+    pending.delete(tx.as_mut()).await?;
+
+    Ok(Res { pending })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The cancelled pending transaction.
+    pub pending: PendingTransaction,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let pending = create::pending_transaction(
+            &mut conn,
+            &children.0.uid,
+            "Birthday",
+            5000,
+            "2999-06-03T00:00:00Z".parse().unwrap(),
+        );
+
+        {
+            let mut tx = conn.begin().await.unwrap();
+            execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &children.0.uid,
+                &pending.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        assert!(PendingTransaction::read(conn.as_mut(), &pending.uid)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let pending = create::pending_transaction(
+            &mut conn,
+            &children.0.uid,
+            "Pay back",
+            -1000,
+            "2999-06-03T00:00:00Z".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &children.0.uid,
+                &pending.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
@@ -0,0 +1,200 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{pending_transaction, PendingTransaction, User};
+use crate::db::values::{Role, Timestamp, UID};
+
+/// Schedules a transaction to be booked at a later time.
+#[post("transaction/{user_uid}/pending")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let user_uid = path.into_inner();
+    {
+        let res = execute(&mut tx, state.clone(), &req.into_inner(), &user_uid)
+            .await?;
+        Notify::Parents {
+            event: Event::PendingTransactionCreated {
+                pending: res.pending.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = User::read(tx.as_mut(), user_uid)
+        .await?
+        .ok_or_else(|| api::Error::not_found("unknown user"))?;
+    state
+        .assert_role(Role::Parent)?
+        .assert_family(&user.family_uid)?;
+
+    let now = Timestamp::now();
+    let pending = api::argument(
+        pending_transaction::PendingTransactionDescription {
+            ledger_uid: Some(None),
+            ..Default::default()
+        }
+        .merge(req.clone())
+        .merge(pending_transaction::PendingTransactionDescription {
+            user_uid: Some(user.uid.clone()),
+            created: Some(now),
+            ..Default::default()
+        })
+        .entity(UID::new()),
+    )?;
+    super::check(tx, &user, &pending, now).await?;
+    pending.create(tx.as_mut()).await?;
+
+    Ok(Res { pending })
+}
+
+pub type Req = pending_transaction::PendingTransactionDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new pending transaction.
+    pub pending: PendingTransaction,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+    use crate::db::values::TransactionType;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    transaction_type: Some(TransactionType::Gift),
+                    description: Some("Birthday".into()),
+                    amount: Some(5000),
+                    due: Some("2999-06-03T00:00:00Z".parse().unwrap()),
+                    ..Default::default()
+                },
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.pending.user_uid, children.0.uid);
+        assert_eq!(res.pending.ledger_uid, None);
+        assert_eq!(
+            PendingTransaction::read(conn.as_mut(), &res.pending.uid)
+                .await
+                .unwrap(),
+            Some(res.pending),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_due() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    transaction_type: Some(TransactionType::Gift),
+                    description: Some("Birthday".into()),
+                    amount: Some(5000),
+                    due: Some("2000-06-03T00:00:00Z".parse().unwrap()),
+                    ..Default::default()
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid due"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    transaction_type: Some(TransactionType::Gift),
+                    description: Some("Birthday".into()),
+                    amount: Some(5000),
+                    due: Some("2999-06-03T00:00:00Z".parse().unwrap()),
+                    ..Default::default()
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
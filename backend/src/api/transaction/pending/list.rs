@@ -0,0 +1,124 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::session::State;
+use crate::db::entities::{PendingTransaction, User};
+use crate::db::values::{Role, UID};
+
+/// Retrieves all pending transactions for a user.
+#[get("transaction/{user_uid}/pending")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    session: Session,
+    user_uid: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    {
+        let res = execute(&mut tx, state, &user_uid.into_inner()).await?;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    user_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = User::read(tx.as_mut(), &user_uid)
+        .await?
+        .ok_or_else(|| api::Error::forbidden("invalid user"))?;
+    match state.role {
+        Role::Parent => state.assert_family(&user.family_uid)?,
+        Role::Child => state.assert_user(&user.uid)?,
+    };
+
+    let pending = PendingTransaction::read_for_user(tx, &user.uid).await?;
+
+    Ok(Res { pending })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The pending transactions, the earliest first.
+    pending: Vec<PendingTransaction>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Timestamp;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let pending = create::pending_transaction(
+            &mut conn,
+            &children.0.uid,
+            "Birthday",
+            5000,
+            Timestamp::now(),
+        );
+        create::pending_transaction(
+            &mut conn,
+            &children.1.uid,
+            "Birthday",
+            5000,
+            Timestamp::now(),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.pending, vec![pending]);
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &children.1.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid user"));
+    }
+}
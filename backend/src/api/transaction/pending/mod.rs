@@ -0,0 +1,56 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::db::entities::{PendingTransaction, User};
+use crate::db::values::{Timestamp, UID};
+
+pub mod cancel;
+pub mod create;
+pub mod list;
+pub mod update;
+
+/// Loads a pending transaction and the user involved in it.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `user_uid` - The unique ID of the user.
+/// *  `pending_uid` - The unique ID of the pending transaction.
+pub async fn load<'a>(
+    tx: &mut Tx<'a>,
+    user_uid: &UID,
+    pending_uid: &UID,
+) -> Result<(User, PendingTransaction), api::Error> {
+    let user = User::read(tx.as_mut(), user_uid)
+        .await?
+        .ok_or_else(|| api::Error::not_found("unknown transaction"))?;
+    let pending = PendingTransaction::read(tx.as_mut(), pending_uid)
+        .await?
+        .filter(|pending| pending.user_uid == user.uid)
+        .ok_or_else(|| api::Error::not_found("unknown transaction"))?;
+    Ok((user, pending))
+}
+
+/// Ensures that a pending transaction can be booked.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `user` - The user involved in the transaction.
+/// *  `pending` - The pending transaction.
+/// *  `now` - The current time.
+pub async fn check<'a>(
+    tx: &mut Tx<'a>,
+    user: &User,
+    pending: &PendingTransaction,
+    now: Timestamp,
+) -> Result<(), api::Error> {
+    if pending.due.0 <= now.0 {
+        return Err(api::Error::Static(StatusCode::BAD_REQUEST, "invalid due"));
+    }
+    if pending.amount == 0 {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid amount",
+        ));
+    }
+    api::ledger::check(tx, &user.family_uid, pending.ledger_uid.as_ref()).await
+}
@@ -0,0 +1,178 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{pending_transaction, PendingTransaction};
+use crate::db::values::{Role, Timestamp, UID};
+
+/// Changes a pending transaction before it is booked.
+#[put("transaction/{user_uid}/pending/{pending_uid}")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, pending_uid) = path.into_inner();
+    {
+        let res = execute(
+            &mut tx,
+            state.clone(),
+            &req.into_inner(),
+            &user_uid,
+            &pending_uid,
+        )
+        .await?;
+        Notify::Parents {
+            event: Event::PendingTransactionUpdated {
+                pending: res.pending.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+    pending_uid: &UID,
+) -> Result<Res, api::Error> {
+    let (user, pending) = super::load(tx, user_uid, pending_uid).await?;
+    state
+        .assert_role(Role::Parent)?
+        .assert_family(&user.family_uid)?;
+
+    let description =
+        req.clone()
+            .merge(pending_transaction::PendingTransactionDescription {
+                user_uid: Some(pending.user_uid.clone()),
+                created: Some(pending.created),
+                ..Default::default()
+            });
+    let pending = pending.merge(description);
+    super::check(tx, &user, &pending, Timestamp::now()).await?;
+    pending.update(tx.as_mut()).await?;
+
+    Ok(Res { pending })
+}
+
+pub type Req = pending_transaction::PendingTransactionDescription;
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The updated pending transaction.
+    pub pending: PendingTransaction,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let pending = create::pending_transaction(
+            &mut conn,
+            &children.0.uid,
+            "Birthday",
+            5000,
+            "2999-06-03T00:00:00Z".parse().unwrap(),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    amount: Some(2500),
+                    user_uid: Some(children.1.uid.clone()),
+                    ..Default::default()
+                },
+                &children.0.uid,
+                &pending.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            res.pending,
+            PendingTransaction {
+                amount: 2500,
+                ..pending
+            },
+        );
+        assert_eq!(
+            PendingTransaction::read(conn.as_mut(), &res.pending.uid)
+                .await
+                .unwrap(),
+            Some(res.pending),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn unknown_transaction() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let pending = create::pending_transaction(
+            &mut conn,
+            &children.1.uid,
+            "Birthday",
+            5000,
+            "2999-06-03T00:00:00Z".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    amount: Some(2500),
+                    ..Default::default()
+                },
+                &children.0.uid,
+                &pending.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown transaction"));
+    }
+}
@@ -3,10 +3,20 @@ use crate::prelude::*;
 use crate::api;
 use crate::api::notify::{Event, Notify};
 use crate::api::session::State;
-use crate::db::entities::{allowance, Allowance, User};
-use crate::db::values::{Role, UID};
+use crate::db::entities::{
+    allowance, Allowance, AllowanceChange, FamilySettings, User,
+};
+use crate::db::values::{Role, Timestamp, UID};
 
 /// Changes the allowance for a user.
+///
+/// Changes to the amount or schedule are recorded in the history of the
+/// allowance. If an effective time in the future is passed, only the amount
+/// and schedule may change, and they are applied once the change is due. The
+/// amount of an allowance with an age scale cannot be changed in advance.
+///
+/// If the allowance has an age scale and the birth date of the user is known,
+/// the amount is derived from the current age of the user.
 #[put("user/{user_uid}/allowance/{allowance_uid}")]
 pub async fn handle(
     database: web::Data<DatabaseEngine>,
@@ -29,9 +39,17 @@ pub async fn handle(
         )
         .await?;
         Notify::MemberAndParents {
-            event: Event::AllowanceUpdated {
-                allowance: res.allowance.clone(),
-                by: state.user_uid.clone(),
+            event: match &res.change {
+                Some(change) if !change.applied => {
+                    Event::AllowanceChangeScheduled {
+                        change: change.clone(),
+                        by: state.user_uid.clone(),
+                    }
+                }
+                _ => Event::AllowanceUpdated {
+                    allowance: res.allowance.clone(),
+                    by: Some(state.user_uid.clone()),
+                },
             },
             uid: user_uid,
             family: state.family_uid,
@@ -51,27 +69,109 @@ pub async fn execute<'a>(
     allowance_uid: &UID,
 ) -> Result<Res, api::Error> {
     let user = api::expect(User::read(tx.as_mut(), user_uid).await?)?;
+    let parent_uid = state.user_uid.clone();
     state
         .assert_family(&user.family_uid)?
         .assert_role(Role::Parent)?;
+    if let Some(ledger_uid) = &req.allowance.ledger_uid {
+        api::ledger::check(tx, &user.family_uid, ledger_uid.as_ref()).await?;
+    }
+
+    let current =
+        api::expect(Allowance::read(tx.as_mut(), &allowance_uid).await?)?;
+    if current.user_uid != user.uid {
+        return Err(api::Error::not_found("unknown allowance"));
+    }
+    let mut allowance = current.clone().merge(req.allowance.clone().merge(
+        allowance::AllowanceDescription {
+            user_uid: Some(user.uid.clone()),
+            ..Default::default()
+        },
+    ));
+    let now = Timestamp::now();
+    let effective = req.effective.filter(|effective| effective.0 > now.0);
+    if effective.is_none() {
+        if let Some(birth_date) = user.birth_date {
+            let settings =
+                FamilySettings::read_or_default(tx, &user.family_uid).await?;
+            allowance.amount = allowance
+                .amount_at(birth_date.years_until(settings.local_date(now)));
+        }
+    }
+    if allowance.required_share.map(|s| s > 100).unwrap_or(false) {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid requirement",
+        ));
+    }
 
-    let allowance =
-        api::expect(Allowance::read(tx.as_mut(), &allowance_uid).await?)?
-            .merge(req.clone().merge(allowance::AllowanceDescription {
-                user_uid: Some(user.uid.clone()),
-                ..Default::default()
-            }));
-    allowance.update(tx.as_mut()).await?;
+    let change = if allowance.amount != current.amount
+        || allowance.schedule != current.schedule
+    {
+        Some(AllowanceChange::record(
+            &current,
+            allowance.amount,
+            allowance.schedule.clone(),
+            effective.unwrap_or(now),
+            req.prorate_payment,
+            Some(parent_uid),
+        ))
+    } else {
+        None
+    };
 
-    Ok(Res { allowance })
+    // Only the amount and schedule can be changed in advance, and the amount
+    // only if it is not derived from the age of the child
+    if effective.is_some() {
+        let unchanged = Allowance {
+            amount: current.amount,
+            schedule: current.schedule.clone(),
+            ..allowance
+        };
+        if unchanged != current
+            || (current.age_scale.is_some()
+                && allowance.amount != current.amount)
+        {
+            return Err(api::Error::Static(
+                StatusCode::BAD_REQUEST,
+                "invalid change",
+            ));
+        }
+        allowance = current;
+    } else {
+        allowance.update(tx.as_mut()).await?;
+    }
+    if let Some(change) = &change {
+        change.create(tx.as_mut()).await?;
+    }
+
+    Ok(Res { allowance, change })
 }
 
-pub type Req = allowance::AllowanceDescription;
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct Req {
+    /// The changes to the allowance.
+    #[serde(flatten)]
+    pub allowance: allowance::AllowanceDescription,
+
+    /// The time from which changes to the amount and schedule apply, or
+    /// `None` to apply them immediately.
+    #[serde(default)]
+    pub effective: Option<Timestamp>,
+
+    /// Whether the next payment is prorated between the previous and the new
+    /// amount.
+    #[serde(default)]
+    pub prorate_payment: bool,
+}
 
 #[derive(Deserialize, Serialize)]
 pub struct Res {
-    /// The new allowance.
+    /// The current allowance.
     allowance: Allowance,
+
+    /// The recorded change, if the amount or schedule changed.
+    change: Option<AllowanceChange>,
 }
 
 #[cfg(test)]
@@ -105,8 +205,11 @@ mod tests {
                     role: parent.role.clone(),
                 },
                 &Req {
-                    amount: Some(84),
-                    schedule: Some("tue".parse().unwrap()),
+                    allowance: allowance::AllowanceDescription {
+                        amount: Some(84),
+                        schedule: Some("tue".parse().unwrap()),
+                        ..Default::default()
+                    },
                     ..Default::default()
                 },
                 &allowance.user_uid,
@@ -124,6 +227,228 @@ mod tests {
         assert_eq!(allowance.user_uid, children.0.uid);
         assert_eq!(allowance.amount, 84);
         assert_eq!(allowance.schedule, "tue".parse().unwrap());
+
+        let mut tx = conn.begin().await.unwrap();
+        let changes =
+            AllowanceChange::read_for_allowance(&mut tx, &allowance.uid)
+                .await
+                .unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].previous_amount, 42);
+        assert_eq!(changes[0].amount, 84);
+        assert_eq!(changes[0].schedule, "tue".parse().unwrap());
+        assert_eq!(changes[0].created_by, Some(parent.uid));
+        assert!(changes[0].applied);
+    }
+
+    #[actix_rt::test]
+    async fn success_scheduled() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let allowance = create::allowance(
+            &mut conn,
+            &children.0.uid,
+            42,
+            "mon".parse().unwrap(),
+        );
+        let effective =
+            Timestamp::from(Timestamp::now().0 + chrono::Duration::days(7));
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    allowance: allowance::AllowanceDescription {
+                        amount: Some(84),
+                        ..Default::default()
+                    },
+                    effective: Some(effective),
+                    prorate_payment: true,
+                },
+                &allowance.user_uid,
+                &allowance.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.allowance, allowance);
+        let change = res.change.unwrap();
+        assert_eq!(change.amount, 84);
+        assert_eq!(change.effective, effective);
+        assert!(change.prorate);
+        assert!(!change.applied);
+        assert_eq!(
+            Allowance::read(conn.as_mut(), &allowance.uid)
+                .await
+                .unwrap(),
+            Some(allowance),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_change() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let allowance = create::allowance(
+            &mut conn,
+            &children.0.uid,
+            42,
+            "mon".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    allowance: allowance::AllowanceDescription {
+                        amount: Some(84),
+                        required_share: Some(Some(50)),
+                        ..Default::default()
+                    },
+                    effective: Some(Timestamp::from(
+                        Timestamp::now().0 + chrono::Duration::days(7),
+                    )),
+                    ..Default::default()
+                },
+                &allowance.user_uid,
+                &allowance.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid change"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_change_age_scale() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let allowance = Allowance {
+            age_scale: Some("100+25".parse().unwrap()),
+            ..create::allowance(
+                &mut conn,
+                &children.0.uid,
+                42,
+                "mon".parse().unwrap(),
+            )
+        };
+        allowance.update(conn.as_mut()).await.unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    allowance: allowance::AllowanceDescription {
+                        amount: Some(84),
+                        ..Default::default()
+                    },
+                    effective: Some(Timestamp::from(
+                        Timestamp::now().0 + chrono::Duration::days(7),
+                    )),
+                    ..Default::default()
+                },
+                &allowance.user_uid,
+                &allowance.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid change"),
+        );
+        assert_eq!(
+            Allowance::read(conn.as_mut(), &allowance.uid)
+                .await
+                .unwrap(),
+            Some(allowance),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn unknown_allowance() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let allowance = create::allowance(
+            &mut conn,
+            &children.1.uid,
+            42,
+            "mon".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    allowance: allowance::AllowanceDescription {
+                        amount: Some(84),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                &children.0.uid,
+                &allowance.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::not_found("unknown allowance"));
+        assert_eq!(
+            Allowance::read(conn.as_mut(), &allowance.uid)
+                .await
+                .unwrap(),
+            Some(allowance),
+        );
     }
 
     #[actix_rt::test]
@@ -149,9 +474,12 @@ mod tests {
                     role: parent.role.clone(),
                 },
                 &Req {
-                    user_uid: Some(children.1.uid.clone()),
-                    amount: Some(84),
-                    schedule: Some("tue".parse().unwrap()),
+                    allowance: allowance::AllowanceDescription {
+                        user_uid: Some(children.1.uid.clone()),
+                        amount: Some(84),
+                        schedule: Some("tue".parse().unwrap()),
+                        ..Default::default()
+                    },
                     ..Default::default()
                 },
                 &allowance.user_uid,
@@ -171,6 +499,55 @@ mod tests {
         assert_eq!(allowance.schedule, "tue".parse().unwrap());
     }
 
+    #[actix_rt::test]
+    async fn success_age_scale() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        User {
+            birth_date: Some("2015-06-03".parse().unwrap()),
+            ..children.0.clone()
+        }
+        .update(conn.as_mut())
+        .await
+        .unwrap();
+        let allowance = create::allowance(
+            &mut conn,
+            &children.0.uid,
+            42,
+            "mon".parse().unwrap(),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    allowance: allowance::AllowanceDescription {
+                        amount: Some(84),
+                        age_scale: Some(Some("0:150".parse().unwrap())),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                &allowance.user_uid,
+                &allowance.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.allowance.amount, 150);
+    }
+
     #[actix_rt::test]
     async fn forbidden_parent() {
         let database = test_engine().await;
@@ -201,8 +578,11 @@ mod tests {
                     role: children.0.role.clone(),
                 },
                 &Req {
-                    amount: Some(84),
-                    schedule: Some("tue".parse().unwrap()),
+                    allowance: allowance::AllowanceDescription {
+                        amount: Some(84),
+                        schedule: Some("tue".parse().unwrap()),
+                        ..Default::default()
+                    },
                     ..Default::default()
                 },
                 &allowance.user_uid,
@@ -218,6 +598,51 @@ mod tests {
         assert_eq!(err, api::Error::forbidden("invalid family"));
     }
 
+    #[actix_rt::test]
+    async fn invalid_requirement() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let allowance = create::allowance(
+            &mut conn,
+            &children.0.uid,
+            42,
+            "mon".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    allowance: allowance::AllowanceDescription {
+                        required_share: Some(Some(101)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                &allowance.user_uid,
+                &allowance.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid requirement"),
+        );
+    }
+
     #[actix_rt::test]
     async fn forbidden_child() {
         let database = test_engine().await;
@@ -240,8 +665,11 @@ mod tests {
                     role: children.0.role.clone(),
                 },
                 &Req {
-                    amount: Some(84),
-                    schedule: Some("tue".parse().unwrap()),
+                    allowance: allowance::AllowanceDescription {
+                        amount: Some(84),
+                        schedule: Some("tue".parse().unwrap()),
+                        ..Default::default()
+                    },
                     ..Default::default()
                 },
                 &allowance.user_uid,
@@ -0,0 +1,140 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::session::State;
+use crate::db::entities::{Allowance, AllowanceChange, User};
+use crate::db::values::{Role, UID};
+
+/// Lists the changes to an allowance, including changes that have not taken
+/// effect yet.
+#[get("user/{user_uid}/allowance/{allowance_uid}/history")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    session: Session,
+    path: web::Path<(UID, UID)>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let (user_uid, allowance_uid) = path.into_inner();
+    {
+        let res = execute(&mut tx, state, &user_uid, &allowance_uid).await?;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    user_uid: &UID,
+    allowance_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = api::expect(User::read(tx.as_mut(), user_uid).await?)?;
+    let state = state.assert_family(&user.family_uid)?;
+    if state.role != Role::Parent {
+        state.assert_user(user_uid)?;
+    }
+    let allowance =
+        api::expect(Allowance::read(tx.as_mut(), allowance_uid).await?)?;
+    if allowance.user_uid != user.uid {
+        return Err(api::Error::not_found("unknown allowance"));
+    }
+
+    Ok(Res {
+        changes: AllowanceChange::read_for_allowance(tx, &allowance.uid)
+            .await?,
+    })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The changes, the earliest first.
+    changes: Vec<AllowanceChange>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Timestamp;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let allowance = create::allowance(
+            &mut conn,
+            &children.0.uid,
+            42,
+            "mon".parse().unwrap(),
+        );
+        let change = AllowanceChange::record(
+            &allowance,
+            84,
+            "mon".parse().unwrap(),
+            Timestamp::now(),
+            false,
+            None,
+        );
+        change.create(conn.as_mut()).await.unwrap();
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &children.0.uid,
+                &allowance.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.changes, vec![change]);
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_other_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+        let allowance = create::allowance(
+            &mut conn,
+            &children.0.uid,
+            42,
+            "mon".parse().unwrap(),
+        );
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.1.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.1.role.clone(),
+                },
+                &children.0.uid,
+                &allowance.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid user"));
+    }
+}
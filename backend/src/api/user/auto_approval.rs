@@ -0,0 +1,260 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{auto_approval, AutoApproval, User};
+use crate::db::values::{Categories, Period, Role, UID};
+
+/// Changes the rule for automatically granting requests made by a child.
+///
+/// If no rule is passed, the current rule is removed.
+#[put("user/{user_uid}/auto-approval")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let user_uid = path.into_inner();
+    {
+        let res = execute(&mut tx, state.clone(), &req.into_inner(), &user_uid)
+            .await?;
+        Notify::MemberAndParents {
+            event: Event::AutoApprovalUpdated {
+                user_uid: user_uid.clone(),
+                rule: res.rule.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = api::expect(User::read(tx.as_mut(), user_uid).await?)?;
+    state
+        .assert_family(&user.family_uid)?
+        .assert_role(Role::Parent)?;
+    if user.role != Role::Child {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid rule",
+        ));
+    }
+
+    let current = AutoApproval::read(tx.as_mut(), user_uid).await?;
+    let rule = match (&req.rule, current) {
+        (None, None) => None,
+        (None, Some(current)) => {
+            current.delete(tx.as_mut()).await?;
+            None
+        }
+        (Some(description), current) => {
+            let exists = current.is_some();
+            let rule = current
+                .unwrap_or_else(|| {
+                    AutoApproval::new(
+                        user.uid.clone(),
+                        0,
+                        Categories::default(),
+                        None,
+                        Period::Weekly,
+                    )
+                })
+                .merge(description.clone().merge(
+                    auto_approval::AutoApprovalDescription {
+                        user_uid: Some(user.uid.clone()),
+                        ..Default::default()
+                    },
+                ));
+            if rule.max_amount < 0 || rule.budget.unwrap_or(0) < 0 {
+                return Err(api::Error::Static(
+                    StatusCode::BAD_REQUEST,
+                    "invalid rule",
+                ));
+            }
+            if exists {
+                rule.update(tx.as_mut()).await?;
+            } else {
+                rule.create(tx.as_mut()).await?;
+            }
+            Some(rule)
+        }
+    };
+
+    Ok(Res { rule })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Req {
+    /// The changes to the rule, or `None` to remove the rule.
+    #[serde(default)]
+    pub rule: Option<auto_approval::AutoApprovalDescription>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The new rule, if any.
+    pub rule: Option<AutoApproval>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let state = State {
+            user_uid: parent.uid.clone(),
+            role: parent.role.clone(),
+            family_uid: family.uid.clone(),
+        };
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                state.clone(),
+                &Req {
+                    rule: Some(auto_approval::AutoApprovalDescription {
+                        max_amount: Some(500),
+                        categories: Some("sweets,books".parse().unwrap()),
+                        ..Default::default()
+                    }),
+                },
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            res.rule,
+            Some(AutoApproval::new(
+                children.0.uid.clone(),
+                500,
+                "sweets,books".parse().unwrap(),
+                None,
+                Period::Weekly,
+            )),
+        );
+        assert_eq!(
+            AutoApproval::read(conn.as_mut(), &children.0.uid)
+                .await
+                .unwrap(),
+            res.rule,
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r =
+                execute(&mut tx, state, &Req { rule: None }, &children.0.uid)
+                    .await
+                    .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.rule, None);
+        assert_eq!(
+            AutoApproval::read(conn.as_mut(), &children.0.uid)
+                .await
+                .unwrap(),
+            None,
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    rule: Some(auto_approval::AutoApprovalDescription {
+                        max_amount: Some(-1),
+                        ..Default::default()
+                    }),
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid rule"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    rule: Some(auto_approval::AutoApprovalDescription {
+                        max_amount: Some(10000),
+                        ..Default::default()
+                    }),
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
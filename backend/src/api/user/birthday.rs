@@ -0,0 +1,260 @@
+use crate::prelude::*;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{BirthdayGift, User};
+use crate::db::values::{Date, Role, Timestamp, UID};
+
+/// Changes the birth date of a family member, and the gift booked on the
+/// birthdays of a child.
+///
+/// If no gift is passed, the current gift is removed.
+#[put("user/{user_uid}/birthday")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let user_uid = path.into_inner();
+    {
+        let res = execute(&mut tx, state.clone(), &req.into_inner(), &user_uid)
+            .await?;
+        Notify::Parents {
+            event: Event::BirthdayUpdated {
+                user: res.user.clone(),
+                gift: res.gift.clone(),
+                by: state.user_uid.clone(),
+            },
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = api::expect(User::read(tx.as_mut(), user_uid).await?)?;
+    state
+        .assert_family(&user.family_uid)?
+        .assert_role(Role::Parent)?;
+    if req
+        .birth_date
+        .map(|birth_date| birth_date.0 > Timestamp::now().0.date_naive())
+        .unwrap_or(false)
+    {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid birth date",
+        ));
+    }
+    if req
+        .gift
+        .map(|gift| gift <= 0 || user.role != Role::Child)
+        .unwrap_or(false)
+    {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid gift",
+        ));
+    }
+
+    let user = User {
+        birth_date: req.birth_date,
+        ..user
+    };
+    user.update(tx.as_mut()).await?;
+
+    let current = BirthdayGift::read(tx.as_mut(), &user.uid).await?;
+    let gift = match (req.gift, current) {
+        (None, None) => None,
+        (None, Some(current)) => {
+            current.delete(tx.as_mut()).await?;
+            None
+        }
+        (Some(amount), Some(current)) => {
+            let gift = BirthdayGift { amount, ..current };
+            gift.update(tx.as_mut()).await?;
+            Some(gift)
+        }
+        (Some(amount), None) => {
+            let gift = BirthdayGift::new(user.uid.clone(), amount, None);
+            gift.create(tx.as_mut()).await?;
+            Some(gift)
+        }
+    };
+
+    Ok(Res { user, gift })
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Req {
+    /// The birth date, or `None` if unknown.
+    #[serde(default)]
+    pub birth_date: Option<Date>,
+
+    /// The amount of the birthday gift, or `None` to remove the gift.
+    #[serde(default)]
+    pub gift: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The updated user.
+    pub user: User,
+
+    /// The birthday gift, if any.
+    pub gift: Option<BirthdayGift>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let state = State {
+            user_uid: parent.uid.clone(),
+            role: parent.role.clone(),
+            family_uid: family.uid.clone(),
+        };
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                state.clone(),
+                &Req {
+                    birth_date: Some("2015-06-03".parse().unwrap()),
+                    gift: Some(5000),
+                },
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.user.birth_date, Some("2015-06-03".parse().unwrap()));
+        assert_eq!(
+            User::read(conn.as_mut(), &children.0.uid).await.unwrap(),
+            Some(res.user),
+        );
+        assert_eq!(
+            BirthdayGift::read(conn.as_mut(), &children.0.uid)
+                .await
+                .unwrap(),
+            Some(BirthdayGift::new(children.0.uid.clone(), 5000, None)),
+        );
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                state,
+                &Req {
+                    birth_date: Some("2015-06-03".parse().unwrap()),
+                    gift: None,
+                },
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(res.gift, None);
+        assert_eq!(
+            BirthdayGift::read(conn.as_mut(), &children.0.uid)
+                .await
+                .unwrap(),
+            None,
+        );
+    }
+
+    #[actix_rt::test]
+    async fn invalid_gift() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, _, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    role: parent.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    birth_date: None,
+                    gift: Some(5000),
+                },
+                &parent.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid gift"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    role: children.0.role.clone(),
+                    family_uid: family.uid.clone(),
+                },
+                &Req {
+                    birth_date: None,
+                    gift: Some(5000),
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
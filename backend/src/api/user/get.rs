@@ -2,8 +2,8 @@ use crate::prelude::*;
 
 use crate::api;
 use crate::api::session::State;
-use crate::db::entities::{Allowance, User};
-use crate::db::values::UID;
+use crate::db::entities::{Allowance, AutoApproval, Jar, Subscription, User};
+use crate::db::values::{Timestamp, UID};
 
 /// Retrieves information about a user.
 #[get("user/{user_uid}")]
@@ -34,8 +34,18 @@ pub async fn execute<'a>(
         .await?
         .into_iter()
         .next();
-
-    Ok(Res { user, allowance })
+    let jars = Jar::read_for_user(tx, &user.uid).await?;
+    let auto_approval = AutoApproval::read(tx.as_mut(), &user.uid).await?;
+    let subscriptions =
+        Subscription::read_for_user(tx, &user.uid, Timestamp::now()).await?;
+
+    Ok(Res {
+        user,
+        allowance,
+        jars,
+        auto_approval,
+        subscriptions,
+    })
 }
 
 #[derive(Deserialize, Serialize)]
@@ -45,6 +55,15 @@ pub struct Res {
 
     /// The allowance schedule.
     allowance: Option<Allowance>,
+
+    /// The jars between which incoming money is split.
+    jars: Vec<Jar>,
+
+    /// The rule for automatically granting requests.
+    auto_approval: Option<AutoApproval>,
+
+    /// The active subscriptions, including paused ones.
+    subscriptions: Vec<Subscription>,
 }
 
 #[cfg(test)]
@@ -84,6 +103,7 @@ mod tests {
 
         assert_eq!(res.user, children.0);
         assert_eq!(res.allowance, Some(allowance));
+        assert_eq!(res.jars, Vec::new());
     }
 
     #[actix_rt::test]
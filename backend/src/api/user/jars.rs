@@ -0,0 +1,261 @@
+use crate::prelude::*;
+
+use std::collections::HashSet;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::api::session::State;
+use crate::db::entities::{Jar, User};
+use crate::db::values::{Role, UID};
+
+/// Replaces the jars of a user.
+///
+/// Jars with the same name as an existing jar keep their identity, and thus
+/// their balance. Jars not present in the request are removed, and their
+/// balance is no longer allocated to a jar.
+#[put("user/{user_uid}/jars")]
+pub async fn handle(
+    database: web::Data<DatabaseEngine>,
+    channel: web::Data<ChannelEngine>,
+    session: Session,
+    req: web::Json<Req>,
+    path: web::Path<UID>,
+) -> impl Responder {
+    let mut conn = database.connection().await?;
+    let mut tx = conn.begin().await?;
+    let state = State::load(&session)?;
+    let user_uid = path.into_inner();
+    {
+        let res = execute(&mut tx, state.clone(), &req.into_inner(), &user_uid)
+            .await?;
+        Notify::MemberAndParents {
+            event: Event::JarsUpdated {
+                user_uid: user_uid.clone(),
+                jars: res.jars.clone(),
+                by: state.user_uid.clone(),
+            },
+            uid: user_uid,
+            family: state.family_uid,
+        }
+        .send(&mut tx, &channel, &state.user_uid)
+        .await;
+        tx.commit().await?;
+        api::ok(res)
+    }
+}
+
+pub async fn execute<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    req: &Req,
+    user_uid: &UID,
+) -> Result<Res, api::Error> {
+    let user = api::expect(User::read(tx.as_mut(), user_uid).await?)?;
+    state
+        .assert_family(&user.family_uid)?
+        .assert_role(Role::Parent)?;
+
+    let names = req.jars.iter().map(|j| &j.name).collect::<HashSet<_>>();
+    let total = req.jars.iter().map(|j| j.percentage).sum::<u32>();
+    if names.len() != req.jars.len() || (!req.jars.is_empty() && total != 100) {
+        return Err(api::Error::Static(
+            StatusCode::BAD_REQUEST,
+            "invalid jars",
+        ));
+    }
+
+    let existing = Jar::read_for_user(tx, &user.uid).await?;
+    for jar in existing.iter().filter(|j| !names.contains(&j.name)) {
+        jar.delete(tx.as_mut()).await?;
+    }
+    for spec in req.jars.iter() {
+        if let Some(jar) = existing.iter().find(|j| j.name == spec.name) {
+            Jar {
+                percentage: spec.percentage,
+                ..jar.clone()
+            }
+            .update(tx.as_mut())
+            .await?;
+        } else {
+            Jar::new(
+                UID::new(),
+                user.uid.clone(),
+                spec.name.clone(),
+                spec.percentage,
+            )
+            .create(tx.as_mut())
+            .await?;
+        }
+    }
+
+    Ok(Res {
+        jars: Jar::read_for_user(tx, &user.uid).await?,
+    })
+}
+
+/// Ensures that a jar, if specified, belongs to a user.
+///
+/// # Arguments
+/// *  `tx` - The database transaction.
+/// *  `user_uid` - The user UID.
+/// *  `jar_uid` - The jar UID, or `None` for no jar.
+pub async fn check<'a>(
+    tx: &mut Tx<'a>,
+    user_uid: &UID,
+    jar_uid: Option<&UID>,
+) -> Result<(), api::Error> {
+    if let Some(jar_uid) = jar_uid {
+        Jar::read(tx.as_mut(), jar_uid)
+            .await?
+            .filter(|jar| &jar.user_uid == user_uid)
+            .ok_or_else(|| api::Error::not_found("unknown jar"))?;
+    }
+    Ok(())
+}
+
+/// A description of a jar.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct JarSpec {
+    /// The jar name.
+    pub name: String,
+
+    /// The percentage of incoming money put in this jar.
+    pub percentage: u32,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Req {
+    /// The new jars.
+    ///
+    /// The percentages must add up to 100. An empty list removes all jars.
+    pub jars: Vec<JarSpec>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Res {
+    /// The jars of the user.
+    pub jars: Vec<Jar>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::tests;
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    fn spec(name: &str, percentage: u32) -> JarSpec {
+        JarSpec {
+            name: name.into(),
+            percentage,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn success() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+        let save = create::jar(&mut conn, &children.0.uid, "save", 50);
+        create::jar(&mut conn, &children.0.uid, "fun", 50);
+
+        let res = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    jars: vec![
+                        spec("spend", 60),
+                        spec("save", 30),
+                        spec("give", 10),
+                    ],
+                },
+                &children.0.uid,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            res.jars
+                .iter()
+                .map(|j| (j.name.as_str(), j.percentage))
+                .collect::<Vec<_>>(),
+            vec![("spend", 60), ("save", 30), ("give", 10)],
+        );
+        assert_eq!(res.jars[1].uid, save.uid);
+    }
+
+    #[actix_rt::test]
+    async fn invalid_percentage() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, parent, children, _, _) =
+            tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: parent.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: parent.role.clone(),
+                },
+                &Req {
+                    jars: vec![spec("spend", 60), spec("save", 30)],
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(
+            err,
+            api::Error::Static(StatusCode::BAD_REQUEST, "invalid jars"),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn forbidden_child() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let (family, _, children, _, _) = tests::populate(&mut conn).unwrap();
+
+        let err = {
+            let mut tx = conn.begin().await.unwrap();
+            let r = execute(
+                &mut tx,
+                State {
+                    user_uid: children.0.uid.clone(),
+                    family_uid: family.uid.clone(),
+                    role: children.0.role.clone(),
+                },
+                &Req {
+                    jars: vec![spec("spend", 100)],
+                },
+                &children.0.uid,
+            )
+            .await
+            .err()
+            .unwrap();
+            tx.commit().await.unwrap();
+            r
+        };
+
+        assert_eq!(err, api::Error::forbidden("invalid role"));
+    }
+}
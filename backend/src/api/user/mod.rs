@@ -1,2 +1,6 @@
 pub mod allowance;
+pub mod allowance_history;
+pub mod auto_approval;
+pub mod birthday;
 pub mod get;
+pub mod jars;
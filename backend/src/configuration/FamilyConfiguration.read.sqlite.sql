@@ -1,4 +1,8 @@
-SELECT family_uid, Currencies.name as name, Currencies.format as format
+SELECT family_uid, Currencies.name as name, Currencies.format as format,
+    Currencies.exponent as exponent,
+    Currencies.decimal_separator as decimal_separator,
+    Currencies.grouping_separator as grouping_separator,
+    Currencies.negative_style as negative_style
 FROM Configurations
 LEFT JOIN Currencies
     ON Configurations.currency = Currencies.name
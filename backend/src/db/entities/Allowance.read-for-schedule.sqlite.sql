@@ -0,0 +1,4 @@
+SELECT uid, user_uid, ledger_uid, amount, schedule, required_share, prorate,
+    age_scale
+FROM Allowances
+WHERE schedule = ?
@@ -1,3 +1,4 @@
-SELECT uid, user_uid, amount, schedule
+SELECT uid, user_uid, ledger_uid, amount, schedule, required_share, prorate,
+    age_scale
 FROM Allowances
 WHERE user_uid = ?
@@ -0,0 +1,5 @@
+SELECT uid, allowance_uid, previous_amount, previous_schedule, amount,
+    schedule, effective, prorate, applied, created, created_by
+FROM AllowanceChanges
+WHERE applied = 0 AND effective <= ?
+ORDER BY effective, created
@@ -0,0 +1,5 @@
+SELECT uid, allowance_uid, previous_amount, previous_schedule, amount,
+    schedule, effective, prorate, applied, created, created_by
+FROM AllowanceChanges
+WHERE allowance_uid = ?
+ORDER BY effective, created
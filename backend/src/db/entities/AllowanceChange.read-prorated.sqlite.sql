@@ -0,0 +1,7 @@
+SELECT uid, allowance_uid, previous_amount, previous_schedule, amount,
+    schedule, effective, prorate, applied, created, created_by
+FROM AllowanceChanges
+WHERE allowance_uid = ? AND applied = 1 AND prorate = 1
+    AND effective > ? AND effective <= ?
+ORDER BY effective DESC, created DESC
+LIMIT 1
@@ -0,0 +1,4 @@
+SELECT uid, approval_uid, user_uid, name, amount
+FROM ApprovedContributions
+WHERE approval_uid = ?
+ORDER BY amount DESC, name ASC
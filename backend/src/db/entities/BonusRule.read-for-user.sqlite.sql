@@ -0,0 +1,5 @@
+SELECT uid, user_uid, name, kind, period, streak, amount, current, evaluated,
+    created
+FROM BonusRules
+WHERE user_uid = ?
+ORDER BY created
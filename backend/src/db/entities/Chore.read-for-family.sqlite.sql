@@ -0,0 +1,5 @@
+SELECT uid, family_uid, title, reward, due, assignee_uid, claimed_by, status,
+    created, approved, approved_by, template_uid
+FROM Chores
+WHERE family_uid = ? AND status IN ('open', 'claimed', 'done')
+ORDER BY due IS NULL, due, created
@@ -0,0 +1,7 @@
+SELECT uid, family_uid, title, reward, due, assignee_uid, claimed_by, status,
+    created, approved, approved_by, template_uid
+FROM Chores
+WHERE (assignee_uid = ? OR (assignee_uid IS NULL AND claimed_by = ?))
+    AND due > ?
+    AND due <= ?
+ORDER BY due
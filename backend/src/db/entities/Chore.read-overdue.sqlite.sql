@@ -0,0 +1,5 @@
+SELECT uid, family_uid, title, reward, due, assignee_uid, claimed_by, status,
+    created, approved, approved_by, template_uid
+FROM Chores
+WHERE status = 'open' AND due IS NOT NULL AND due <= ?
+ORDER BY family_uid, due
@@ -0,0 +1,10 @@
+SELECT c.uid, c.family_uid, c.title, c.reward, c.due, c.assignee_uid,
+    c.claimed_by, c.status, c.created, c.approved, c.approved_by,
+    c.template_uid
+FROM Chores c
+    JOIN ChoreTemplates t ON c.template_uid = t.uid
+WHERE t.required
+    AND (c.assignee_uid = ? OR (c.assignee_uid IS NULL AND c.claimed_by = ?))
+    AND c.due > ?
+    AND c.due <= ?
+ORDER BY c.due
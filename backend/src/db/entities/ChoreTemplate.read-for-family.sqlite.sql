@@ -0,0 +1,5 @@
+SELECT uid, family_uid, title, reward, assignee_uid, schedule, penalty,
+    created, generated, required
+FROM ChoreTemplates
+WHERE family_uid = ?
+ORDER BY created
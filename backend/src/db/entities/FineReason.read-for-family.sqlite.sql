@@ -0,0 +1,4 @@
+SELECT uid, family_uid, name, amount, created
+FROM FineReasons
+WHERE family_uid = ?
+ORDER BY name
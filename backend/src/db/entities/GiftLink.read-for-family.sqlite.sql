@@ -0,0 +1,7 @@
+SELECT GiftLinks.uid, user_uid, expires, giver, message, amount, recorded,
+    created, created_by
+FROM GiftLinks
+LEFT JOIN Users
+    ON GiftLinks.user_uid = Users.uid
+WHERE Users.family_uid = ?
+ORDER BY created
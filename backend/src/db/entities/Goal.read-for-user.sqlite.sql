@@ -0,0 +1,5 @@
+SELECT uid, user_uid, name, amount, url, deadline, earmarked, completed
+FROM Goals
+WHERE user_uid = ?
+    AND completed IS NULL
+ORDER BY deadline IS NULL, deadline, name
@@ -0,0 +1,4 @@
+SELECT uid, user_uid, name, percentage
+FROM Jars
+WHERE user_uid = ?
+ORDER BY percentage DESC, name
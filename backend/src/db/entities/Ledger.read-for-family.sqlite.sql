@@ -0,0 +1,3 @@
+SELECT uid, family_uid, name, unit
+FROM Ledgers
+WHERE family_uid = ?
@@ -0,0 +1,5 @@
+SELECT uid, user_uid, name, kind, per, matched, cap, period, jar_uid,
+    goal_uid, created
+FROM MatchRules
+WHERE user_uid = ?
+ORDER BY created
@@ -0,0 +1,5 @@
+SELECT uid, user_uid, transaction_type, ledger_uid, description, amount, due,
+    created
+FROM PendingTransactions
+WHERE user_uid = ?
+ORDER BY due
@@ -1,4 +1,5 @@
-INSERT INTO Requests (user_uid, name, description, amount, url, time)
-VALUES (?, ?, ?, ?, ?, ?);
+INSERT INTO Requests (user_uid, ledger_uid, name, description, amount, url,
+        category, schedule, ends, time)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
 
 SELECT last_insert_rowid()
@@ -0,0 +1,14 @@
+SELECT Requests.uid, user_uid, ledger_uid, Requests.name, description, amount,
+    url, time, status, decided, decided_by, transaction_uid,
+    reason, category, schedule, ends
+FROM Requests
+LEFT JOIN Users
+    ON Requests.user_uid = Users.uid
+WHERE Users.family_uid = ?
+    AND (? IS NULL OR user_uid = ?)
+    AND (? IS NULL OR status = ?)
+    AND (? IS NULL OR time >= ?)
+    AND (? IS NULL OR time < ?)
+ORDER BY time DESC
+LIMIT ?
+OFFSET ?
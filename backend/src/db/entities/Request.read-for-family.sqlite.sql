@@ -1,5 +1,7 @@
-SELECT Requests.uid, user_uid, Requests.name, description, amount, url, time
+SELECT Requests.uid, user_uid, ledger_uid, Requests.name, description, amount,
+    url, time, status, decided, decided_by, transaction_uid,
+    reason, category, schedule, ends
 FROM Requests
 LEFT JOIN Users
     ON Requests.user_uid = Users.uid
-WHERE Users.family_uid = ?
+WHERE Users.family_uid = ? AND status = 'pending'
@@ -1,3 +1,5 @@
-SELECT Requests.uid, user_uid, Requests.name, description, amount, url, time
+SELECT Requests.uid, user_uid, ledger_uid, Requests.name, description, amount,
+    url, time, status, decided, decided_by, transaction_uid,
+    reason, category, schedule, ends
 FROM Requests
-WHERE user_uid = ?
+WHERE user_uid = ? AND status = 'pending'
@@ -0,0 +1,4 @@
+SELECT uid, request_uid, user_uid, time, cost, jar_uid
+FROM RequestApprovals
+WHERE request_uid = ?
+ORDER BY time ASC
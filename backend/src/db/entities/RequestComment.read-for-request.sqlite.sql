@@ -0,0 +1,4 @@
+SELECT uid, request_uid, user_uid, text, time
+FROM RequestComments
+WHERE request_uid = ?
+ORDER BY time ASC
@@ -0,0 +1,4 @@
+SELECT uid, request_uid, user_uid, name, amount, transaction_uid
+FROM RequestContributions
+WHERE request_uid = ?
+ORDER BY amount DESC, name ASC
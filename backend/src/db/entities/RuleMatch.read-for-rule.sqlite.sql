@@ -0,0 +1,4 @@
+SELECT transaction_uid, rule_uid, period
+FROM Matches
+WHERE rule_uid = ?
+    AND period = ?
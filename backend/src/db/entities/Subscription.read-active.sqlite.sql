@@ -0,0 +1,5 @@
+SELECT uid, user_uid, request_uid, ledger_uid, jar_uid, name, amount, schedule,
+    ends, paused, cancelled, booked
+FROM Subscriptions
+WHERE cancelled IS NULL
+    AND (ends IS NULL OR ends > ?)
@@ -0,0 +1,5 @@
+SELECT COALESCE(SUM(-amount), 0)
+FROM Transactions
+WHERE user_uid = ?
+    AND transaction_type = 'auto-approved'
+    AND time >= ?
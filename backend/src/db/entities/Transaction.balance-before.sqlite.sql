@@ -0,0 +1,6 @@
+SELECT COALESCE(SUM(amount), 0)
+FROM Transactions
+WHERE user_uid = ?
+    AND ledger_uid IS NULL
+    AND (? IS NULL OR jar_uid = ?)
+    AND time < ?
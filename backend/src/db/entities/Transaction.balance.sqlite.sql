@@ -1,3 +1,4 @@
 SELECT SUM(amount)
 FROM Transactions
 WHERE user_uid = ?
+    AND ledger_uid IS ?
@@ -1,4 +1,12 @@
-INSERT INTO Transactions (transaction_type, user_uid, description, amount, time)
-VALUES (?, ?, ?, ?, ?);
+INSERT INTO Transactions (
+    transaction_type,
+    user_uid,
+    ledger_uid,
+    jar_uid,
+    description,
+    amount,
+    time
+)
+VALUES (?, ?, ?, ?, ?, ?, ?);
 
 SELECT last_insert_rowid()
@@ -0,0 +1,9 @@
+SELECT COALESCE(SUM(amount), 0)
+FROM Transactions
+WHERE user_uid = ?
+    AND ledger_uid IS NULL
+    AND (? IS NULL OR jar_uid = ?)
+    AND amount > 0
+    AND transaction_type != ?
+    AND time >= ?
+    AND time < ?
@@ -0,0 +1,6 @@
+SELECT jar_uid, SUM(amount)
+FROM Transactions
+WHERE user_uid = ?
+    AND ledger_uid IS NULL
+    AND jar_uid IS NOT NULL
+GROUP BY jar_uid
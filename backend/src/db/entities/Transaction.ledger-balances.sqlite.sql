@@ -0,0 +1,5 @@
+SELECT ledger_uid, SUM(amount)
+FROM Transactions
+WHERE user_uid = ?
+    AND ledger_uid IS NOT NULL
+GROUP BY ledger_uid
@@ -1,4 +1,5 @@
-SELECT uid, transaction_type, user_uid, description, amount, time
+SELECT uid, transaction_type, user_uid, ledger_uid, jar_uid, description,
+    amount, time
 FROM Transactions
 WHERE user_uid = ?
 ORDER BY time DESC
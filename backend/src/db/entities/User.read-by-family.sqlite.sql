@@ -1,3 +1,3 @@
-SELECT uid, role, name, email, family_uid
+SELECT uid, role, name, email, family_uid, birth_date
 FROM Users
 WHERE family_uid = ?
@@ -2,7 +2,7 @@ use crate::prelude::*;
 
 use weru::database::entity;
 
-use crate::db::values::{Schedule, UID};
+use crate::db::values::{AgeScale, Schedule, UID};
 
 /// The allowance for a user.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
@@ -14,11 +14,25 @@ pub struct Allowance {
     /// The user receiving this allowance.
     pub user_uid: UID,
 
+    /// The ledger of this allowance, or `None` for the monetary ledger.
+    pub ledger_uid: Option<UID>,
+
     /// The amount.
     pub amount: u32,
 
     /// The schedule of the allowance.
     pub schedule: Schedule,
+
+    /// The percentage of required chores that must be completed for the
+    /// allowance to be paid in full, or `None` if it is unconditional.
+    pub required_share: Option<u32>,
+
+    /// Whether a prorated amount is paid when the requirement is not met.
+    pub prorate: bool,
+
+    /// The scale from which the amount is derived using the age of the
+    /// child, or `None` if the amount is fixed.
+    pub age_scale: Option<AgeScale>,
 }
 
 impl Allowance {
@@ -26,6 +40,39 @@ impl Allowance {
     const READ_FOR_USER: &'static str =
         sql_from_file!("Allowance.read-for-user");
 
+    /// The SQL statement used to load all allowances with a schedule.
+    const READ_FOR_SCHEDULE: &'static str =
+        sql_from_file!("Allowance.read-for-schedule");
+
+    /// The amount of this allowance for a child of a specific age.
+    ///
+    /// If the allowance has no age scale, or the scale has no amount for the
+    /// age, the current amount is returned.
+    ///
+    /// # Arguments
+    /// *  `age` - The age of the child in years.
+    pub fn amount_at(&self, age: u32) -> u32 {
+        self.age_scale
+            .as_ref()
+            .and_then(|age_scale| age_scale.amount(age))
+            .unwrap_or(self.amount)
+    }
+
+    /// Loads all allowances with a specific schedule.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `schedule` - The schedule.
+    pub async fn read_for_schedule<'a>(
+        tx: &mut Tx<'a>,
+        schedule: &Schedule,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_SCHEDULE)
+            .bind(schedule)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
     /// Loads all allowances for a user.
     ///
     /// # Arguments
@@ -47,11 +94,16 @@ entity_tests! {
         entity: |id| Allowance {
             uid: id,
             user_uid: UID::new(),
+            ledger_uid: None,
             amount: 42,
             schedule: "mon".parse::<Schedule>().unwrap(),
+            required_share: None,
+            prorate: false,
+            age_scale: None,
         };
         modify: |e| Allowance {
             schedule: "tue".parse::<Schedule>().unwrap(),
+            age_scale: Some("100+25".parse().unwrap()),
             ..e
         };
         prepare: |tx, e| {
@@ -116,4 +168,16 @@ mod impl_tests {
         assert!(allowances.contains(&allowance1));
         assert!(allowances.contains(&allowance2));
     }
+
+    #[test]
+    fn amount_at() {
+        let allowance = tests::entity();
+        assert_eq!(allowance.amount_at(8), 42);
+        let allowance = Allowance {
+            age_scale: Some("6:100,8:150".parse().unwrap()),
+            ..allowance
+        };
+        assert_eq!(allowance.amount_at(5), 42);
+        assert_eq!(allowance.amount_at(9), 150);
+    }
 }
@@ -0,0 +1,323 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::entities::Allowance;
+use crate::db::values::{Schedule, Timestamp, UID};
+
+/// A change to the amount or schedule of an allowance.
+///
+/// Changes form the history of an allowance. A change with an effective time
+/// in the future is applied once it is due.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(AllowanceChanges)]
+pub struct AllowanceChange {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The allowance that was changed.
+    pub allowance_uid: UID,
+
+    /// The amount before the change.
+    pub previous_amount: u32,
+
+    /// The schedule before the change.
+    pub previous_schedule: Schedule,
+
+    /// The amount after the change.
+    pub amount: u32,
+
+    /// The schedule after the change.
+    pub schedule: Schedule,
+
+    /// The timestamp from which the change applies.
+    pub effective: Timestamp,
+
+    /// Whether the next payment is prorated between the previous and the new
+    /// amount.
+    pub prorate: bool,
+
+    /// Whether the change has been applied to the allowance.
+    pub applied: bool,
+
+    /// The timestamp when the change was made.
+    pub created: Timestamp,
+
+    /// The parent who made the change, or `None` if it was made
+    /// automatically.
+    pub created_by: Option<UID>,
+}
+
+impl AllowanceChange {
+    /// The SQL statement used to load all changes to an allowance.
+    const READ_FOR_ALLOWANCE: &'static str =
+        sql_from_file!("AllowanceChange.read-for-allowance");
+
+    /// The SQL statement used to load all changes that are due.
+    const READ_DUE: &'static str = sql_from_file!("AllowanceChange.read-due");
+
+    /// The SQL statement used to load the latest prorated change in a period.
+    const READ_PRORATED: &'static str =
+        sql_from_file!("AllowanceChange.read-prorated");
+
+    /// Records a change from the current state of an allowance.
+    ///
+    /// # Arguments
+    /// *  `previous` - The allowance before the change.
+    /// *  `amount` - The amount after the change.
+    /// *  `schedule` - The schedule after the change.
+    /// *  `effective` - The timestamp from which the change applies.
+    /// *  `prorate` - Whether to prorate the next payment.
+    /// *  `created_by` - The parent making the change, if any.
+    pub fn record(
+        previous: &Allowance,
+        amount: u32,
+        schedule: Schedule,
+        effective: Timestamp,
+        prorate: bool,
+        created_by: Option<UID>,
+    ) -> Self {
+        let now = Timestamp::now();
+        Self::new(
+            UID::new(),
+            previous.uid.clone(),
+            previous.amount,
+            previous.schedule.clone(),
+            amount,
+            schedule,
+            effective,
+            prorate,
+            effective.0 <= now.0,
+            now,
+            created_by,
+        )
+    }
+
+    /// Applies this change to an allowance.
+    ///
+    /// # Arguments
+    /// *  `allowance` - The allowance to change.
+    pub fn apply(&self, allowance: Allowance) -> Allowance {
+        Allowance {
+            amount: self.amount,
+            schedule: self.schedule.clone(),
+            ..allowance
+        }
+    }
+
+    /// The amount to pay for a period during which this change took effect.
+    ///
+    /// The previous and new amounts are weighted by the time before and after
+    /// the change took effect.
+    ///
+    /// # Arguments
+    /// *  `start` - The start of the period.
+    /// *  `end` - The end of the period.
+    pub fn prorated(&self, start: Timestamp, end: Timestamp) -> i64 {
+        let total = (end.0 - start.0).num_seconds();
+        if total <= 0 {
+            return self.amount as i64;
+        }
+        let before = (self.effective.0 - start.0).num_seconds().clamp(0, total);
+        (self.previous_amount as i64 * before
+            + self.amount as i64 * (total - before))
+            / total
+    }
+
+    /// Loads all changes to an allowance, the earliest first.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `allowance_uid` - The allowance UID.
+    pub async fn read_for_allowance<'a>(
+        tx: &mut Tx<'a>,
+        allowance_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_ALLOWANCE)
+            .bind(allowance_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Loads all changes that are due but not yet applied, the earliest first.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `now` - The current time.
+    pub async fn read_due<'a>(
+        tx: &mut Tx<'a>,
+        now: Timestamp,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_DUE)
+            .bind(now)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Loads the latest applied change to an allowance that took effect
+    /// during a period and requested proration.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `allowance_uid` - The allowance UID.
+    /// *  `start` - The start of the period, exclusive.
+    /// *  `end` - The end of the period, inclusive.
+    pub async fn read_prorated<'a>(
+        tx: &mut Tx<'a>,
+        allowance_uid: &UID,
+        start: Timestamp,
+        end: Timestamp,
+    ) -> Result<Option<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_PRORATED)
+            .bind(allowance_uid)
+            .bind(start)
+            .bind(end)
+            .fetch_optional(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    AllowanceChange[UID = UID::new()] {
+        entity: |id| AllowanceChange {
+            uid: id,
+            allowance_uid: UID::new(),
+            previous_amount: 42,
+            previous_schedule: "mon".parse::<Schedule>().unwrap(),
+            amount: 50,
+            schedule: "mon".parse::<Schedule>().unwrap(),
+            effective: Timestamp::now(),
+            prorate: false,
+            applied: true,
+            created: Timestamp::now(),
+            created_by: None,
+        };
+        modify: |e| AllowanceChange {
+            applied: false,
+            ..e
+        };
+        prepare: |tx, e| {
+            let a = crate::db::entities::allowance::tests::entity_with_id(
+                e.allowance_uid.clone(),
+            );
+            crate::db::entities::allowance::tests::prepare(tx, &a).await?;
+            a.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use actix_rt;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Role;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn read() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let allowance =
+            create::allowance(&mut conn, &user.uid, 42, Schedule::Daily);
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let past = AllowanceChange::record(
+            &allowance,
+            50,
+            Schedule::Daily,
+            at("2024-06-03T12:00:00Z"),
+            true,
+            None,
+        );
+        let future = AllowanceChange {
+            applied: false,
+            ..AllowanceChange::record(
+                &allowance,
+                60,
+                Schedule::Daily,
+                at("2024-06-07T00:00:00Z"),
+                false,
+                None,
+            )
+        };
+        let mut tx = conn.begin().await.unwrap();
+        past.create(tx.as_mut()).await.unwrap();
+        future.create(tx.as_mut()).await.unwrap();
+
+        assert_eq!(
+            AllowanceChange::read_for_allowance(&mut tx, &allowance.uid)
+                .await
+                .unwrap(),
+            vec![past.clone(), future.clone()],
+        );
+        assert_eq!(
+            AllowanceChange::read_due(&mut tx, at("2024-06-05T00:00:00Z"))
+                .await
+                .unwrap(),
+            vec![],
+        );
+        assert_eq!(
+            AllowanceChange::read_due(&mut tx, at("2024-06-07T00:00:00Z"))
+                .await
+                .unwrap(),
+            vec![future],
+        );
+        assert_eq!(
+            AllowanceChange::read_prorated(
+                &mut tx,
+                &allowance.uid,
+                at("2024-06-03T00:00:00Z"),
+                at("2024-06-04T00:00:00Z"),
+            )
+            .await
+            .unwrap(),
+            Some(past),
+        );
+        assert_eq!(
+            AllowanceChange::read_prorated(
+                &mut tx,
+                &allowance.uid,
+                at("2024-06-04T00:00:00Z"),
+                at("2024-06-05T00:00:00Z"),
+            )
+            .await
+            .unwrap(),
+            None,
+        );
+    }
+
+    #[test]
+    fn prorated() {
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let change = AllowanceChange {
+            previous_amount: 100,
+            amount: 200,
+            effective: at("2024-06-04T00:00:00Z"),
+            ..tests::entity()
+        };
+        assert_eq!(
+            change.prorated(
+                at("2024-06-01T00:00:00Z"),
+                at("2024-06-08T00:00:00Z")
+            ),
+            (100 * 3 + 200 * 4) / 7,
+        );
+        assert_eq!(
+            change.prorated(
+                at("2024-06-05T00:00:00Z"),
+                at("2024-06-08T00:00:00Z")
+            ),
+            200,
+        );
+    }
+}
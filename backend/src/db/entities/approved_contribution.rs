@@ -0,0 +1,69 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::UID;
+
+/// A contribution towards the cost of a request, as approved by a parent.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(ApprovedContributions)]
+pub struct ApprovedContribution {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The approval including the contribution.
+    pub approval_uid: UID,
+
+    /// The family member contributing, or `None` for someone outside the
+    /// family.
+    pub user_uid: Option<UID>,
+
+    /// The name of the contributor.
+    pub name: String,
+
+    /// The amount contributed.
+    pub amount: i64,
+}
+
+impl ApprovedContribution {
+    /// The SQL statement used to load all contributions of an approval.
+    const READ_FOR_APPROVAL: &'static str =
+        sql_from_file!("ApprovedContribution.read-for-approval");
+
+    /// Loads all contributions of an approval, largest first.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `approval_uid` - The approval UID.
+    pub async fn read_for_approval<'a>(
+        tx: &mut Tx<'a>,
+        approval_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_APPROVAL)
+            .bind(approval_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    ApprovedContribution[UID = UID::new()] {
+        entity: |id| ApprovedContribution {
+            uid: id,
+            approval_uid: UID::new(),
+            user_uid: None,
+            name: "Grandma".into(),
+            amount: 42,
+        };
+        modify: |e| ApprovedContribution {
+            amount: 43,
+            ..e
+        };
+        prepare: |tx, e| {
+            use crate::db::entities::request_approval::tests as approval;
+            let a = approval::entity_with_id(e.approval_uid.clone());
+            approval::prepare(tx, &a).await?;
+            a.create(tx.as_mut()).await
+        };
+    }
+}
@@ -0,0 +1,166 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::entities::{Request, Transaction};
+use crate::db::values::{Categories, Period, Timestamp, UID};
+
+/// A rule for automatically granting small requests made by a child.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(AutoApprovals)]
+pub struct AutoApproval {
+    /// The child whose requests are granted.
+    pub user_uid: UID,
+
+    /// The maximum amount of a request granted automatically.
+    pub max_amount: i64,
+
+    /// The categories that may be granted automatically.
+    ///
+    /// If this is empty, requests of all categories may be granted.
+    pub categories: Categories,
+
+    /// The maximum total amount granted automatically per period.
+    pub budget: Option<i64>,
+
+    /// The period over which the budget applies.
+    pub period: Period,
+}
+
+impl AutoApproval {
+    /// Determines whether a request may be granted automatically.
+    ///
+    /// Only monetary requests that do not recur are granted automatically.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `request` - The request.
+    /// *  `now` - The current time.
+    pub async fn allows<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        request: &Request,
+        now: Timestamp,
+    ) -> Result<bool, DatabaseError> {
+        if request.user_uid != self.user_uid
+            || request.ledger_uid.is_some()
+            || request.schedule.is_some()
+            || request.amount < 0
+            || request.amount > self.max_amount
+        {
+            return Ok(false);
+        }
+        if !self.categories.is_empty()
+            && !request
+                .category
+                .as_ref()
+                .map(|c| self.categories.contains(c))
+                .unwrap_or(false)
+        {
+            return Ok(false);
+        }
+        if let Some(budget) = self.budget {
+            let used = Transaction::auto_approved(
+                tx,
+                &self.user_uid,
+                self.period.start(now),
+            )
+            .await?;
+            if used + request.amount > budget {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+entity_tests! {
+    AutoApproval[UID = UID::new()] {
+        entity: |id| AutoApproval {
+            user_uid: id,
+            max_amount: 100,
+            categories: Categories::default(),
+            budget: None,
+            period: Period::Weekly,
+        };
+        modify: |e| AutoApproval {
+            categories: Categories(vec!["sweets".into()]),
+            budget: Some(300),
+            ..e
+        };
+        prepare: |tx, e| {
+            let u = crate::db::entities::user::tests::entity_with_id(
+                e.user_uid.clone(),
+            );
+            crate::db::entities::user::tests::prepare(tx, &u).await?;
+            u.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::{Role, TransactionType};
+
+    #[actix_rt::test]
+    async fn allows() {
+        use super::*;
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User 1",
+            "test1@example.com",
+            &family.uid,
+        );
+        let now = Timestamp::now();
+        create::transaction(
+            &mut conn,
+            TransactionType::AutoApproved,
+            &user.uid,
+            "sweets",
+            -250,
+            now,
+        );
+        let rule = AutoApproval::new(
+            user.uid.clone(),
+            100,
+            Categories(vec!["sweets".into()]),
+            Some(300),
+            Period::Weekly,
+        );
+        let request = |amount: i64, category: Option<&str>| Request {
+            user_uid: user.uid.clone(),
+            amount,
+            category: category.map(String::from),
+            ..crate::db::entities::request::tests::entity()
+        };
+        let mut tx = conn.begin().await.unwrap();
+
+        assert!(rule
+            .allows(&mut tx, &request(50, Some("sweets")), now)
+            .await
+            .unwrap());
+        assert!(!rule
+            .allows(&mut tx, &request(51, Some("sweets")), now)
+            .await
+            .unwrap());
+        assert!(!rule
+            .allows(&mut tx, &request(10, Some("toys")), now)
+            .await
+            .unwrap());
+        assert!(!rule.allows(&mut tx, &request(10, None), now).await.unwrap());
+        assert!(AutoApproval {
+            budget: None,
+            ..rule.clone()
+        }
+        .allows(&mut tx, &request(100, Some("sweets")), now)
+        .await
+        .unwrap());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::prelude::*;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use weru::database::entity;
+
+use crate::db::values::{Date, UID};
+
+/// A gift booked automatically on the birthday of a child.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(BirthdayGifts)]
+pub struct BirthdayGift {
+    /// The child receiving the gift.
+    pub user_uid: UID,
+
+    /// The amount of the gift.
+    pub amount: i64,
+
+    /// The latest birthday for which a gift was booked.
+    pub booked: Option<Date>,
+}
+
+impl BirthdayGift {
+    /// The birthday for which a gift should be booked on a specific date, if
+    /// any.
+    ///
+    /// Since the date may be checked only once per day and not at a fixed
+    /// time, a birthday the day before is also considered, unless a gift has
+    /// already been booked for it.
+    ///
+    /// # Arguments
+    /// *  `birth_date` - The birth date of the child.
+    /// *  `today` - The current date in the time zone of the family.
+    pub fn due(
+        &self,
+        birth_date: &Date,
+        today: NaiveDate,
+    ) -> Option<NaiveDate> {
+        [today - Duration::days(1), today]
+            .into_iter()
+            .filter(|date| {
+                date.year() > birth_date.0.year()
+                    && &birth_date.anniversary(date.year()) == date
+            })
+            .filter(|date| {
+                self.booked.map(|booked| &booked.0 != date).unwrap_or(true)
+            })
+            .last()
+    }
+}
+
+entity_tests! {
+    BirthdayGift[UID = UID::new()] {
+        entity: |id| BirthdayGift {
+            user_uid: id,
+            amount: 5000,
+            booked: None,
+        };
+        modify: |e| BirthdayGift {
+            amount: 2500,
+            booked: Some("2024-06-03".parse().unwrap()),
+            ..e
+        };
+        prepare: |tx, e| {
+            let u = crate::db::entities::user::tests::entity_with_id(
+                e.user_uid.clone(),
+            );
+            crate::db::entities::user::tests::prepare(tx, &u).await?;
+            u.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use super::*;
+
+    #[test]
+    fn due() {
+        let date = |s: &str| s.parse::<Date>().unwrap();
+        let gift = BirthdayGift::new(UID::new(), 5000, None);
+        let birth_date = date("2015-06-03");
+        assert_eq!(gift.due(&birth_date, date("2024-06-02").0), None);
+        assert_eq!(
+            gift.due(&birth_date, date("2024-06-03").0),
+            Some(date("2024-06-03").0),
+        );
+        assert_eq!(
+            gift.due(&birth_date, date("2024-06-04").0),
+            Some(date("2024-06-03").0),
+        );
+        assert_eq!(gift.due(&birth_date, date("2024-06-05").0), None);
+        assert_eq!(gift.due(&birth_date, date("2015-06-03").0), None);
+        assert_eq!(
+            BirthdayGift {
+                booked: Some(date("2024-06-03")),
+                ..gift.clone()
+            }
+            .due(&birth_date, date("2024-06-04").0),
+            None,
+        );
+        assert_eq!(
+            gift.due(&date("2014-12-31"), date("2025-01-01").0),
+            Some(date("2024-12-31").0),
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::entities::{Chore, Transaction};
+use crate::db::values::{BonusKind, Period, Timestamp, UID};
+
+/// A rule rewarding a child with a bonus for a streak of consistent
+/// behaviour.
+///
+/// A bonus of `amount` is booked every time `streak` periods in a row were
+/// successful, after which the streak starts over.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(BonusRules)]
+pub struct BonusRule {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The child receiving the bonus.
+    pub user_uid: UID,
+
+    /// A short name, used as description of bonus transactions.
+    pub name: String,
+
+    /// What is rewarded.
+    pub kind: BonusKind,
+
+    /// The period over which the rule is evaluated.
+    pub period: Period,
+
+    /// The number of successful periods in a row required for a bonus.
+    pub streak: u32,
+
+    /// The amount of the bonus.
+    pub amount: i64,
+
+    /// The number of successful periods in a row so far.
+    pub current: u32,
+
+    /// The start of the latest evaluated period.
+    pub evaluated: Option<Timestamp>,
+
+    /// The timestamp when this rule was created.
+    pub created: Timestamp,
+}
+
+impl BonusRule {
+    /// The SQL statement used to load all rules for a user.
+    const READ_FOR_USER: &'static str =
+        sql_from_file!("BonusRule.read-for-user");
+
+    /// Loads all rules for a user.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    pub async fn read_for_user<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_USER)
+            .bind(user_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Determines whether a time span was successful.
+    ///
+    /// Time spans without any chores are neither successful nor unsuccessful
+    /// for chore rules, in which case `None` is returned.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `start` - The start of the time span.
+    /// *  `end` - The end of the time span.
+    pub async fn evaluate<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        start: Timestamp,
+        end: Timestamp,
+    ) -> Result<Option<bool>, DatabaseError> {
+        Ok(match self.kind {
+            BonusKind::Chores => {
+                let chores =
+                    Chore::read_for_user(tx, &self.user_uid, start, end)
+                        .await?;
+                if chores.is_empty() {
+                    None
+                } else {
+                    Some(chores.iter().all(|chore| chore.is_completed()))
+                }
+            }
+            BonusKind::NoSpending => Some(
+                Transaction::spending(tx, &self.user_uid, start).await?
+                    == Transaction::spending(tx, &self.user_uid, end).await?,
+            ),
+        })
+    }
+}
+
+entity_tests! {
+    BonusRule[UID = UID::new()] {
+        entity: |id| BonusRule {
+            uid: id,
+            user_uid: UID::new(),
+            name: "No spending".into(),
+            kind: BonusKind::NoSpending,
+            period: Period::Monthly,
+            streak: 1,
+            amount: 5,
+            current: 0,
+            evaluated: None,
+            created: Timestamp::now(),
+        };
+        modify: |e| BonusRule {
+            kind: BonusKind::Chores,
+            streak: 4,
+            ..e
+        };
+        prepare: |tx, e| {
+            let u = crate::db::entities::user::tests::entity_with_id(
+                e.user_uid.clone(),
+            );
+            crate::db::entities::user::tests::prepare(tx, &u).await?;
+            u.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use actix_rt;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::{ChoreStatus, Role, TransactionType};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn evaluate() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        create::transaction(
+            &mut conn,
+            TransactionType::Gift,
+            &user.uid,
+            "candy",
+            -10,
+            at("2024-03-12T12:00:00Z"),
+        );
+        for (due, status) in [
+            ("2024-03-05T00:00:00Z", ChoreStatus::Approved),
+            ("2024-03-06T00:00:00Z", ChoreStatus::Done),
+            ("2024-03-13T00:00:00Z", ChoreStatus::Missed),
+        ] {
+            Chore {
+                due: Some(at(due)),
+                status,
+                ..create::chore(
+                    &mut conn,
+                    &family.uid,
+                    "Dishes",
+                    10,
+                    Some(&user.uid),
+                )
+            }
+            .update(conn.as_mut())
+            .await
+            .unwrap();
+        }
+        let rule = |kind| BonusRule {
+            user_uid: user.uid.clone(),
+            kind,
+            ..tests::entity()
+        };
+        let week = |start: &str, end: &str| (at(start), at(end));
+        let mut tx = conn.begin().await.unwrap();
+
+        for (kind, (start, end), expected) in [
+            (
+                BonusKind::Chores,
+                week("2024-03-04T00:00:00Z", "2024-03-11T00:00:00Z"),
+                Some(true),
+            ),
+            (
+                BonusKind::Chores,
+                week("2024-03-11T00:00:00Z", "2024-03-18T00:00:00Z"),
+                Some(false),
+            ),
+            (
+                BonusKind::Chores,
+                week("2024-03-18T00:00:00Z", "2024-03-25T00:00:00Z"),
+                None,
+            ),
+            (
+                BonusKind::NoSpending,
+                week("2024-03-04T00:00:00Z", "2024-03-11T00:00:00Z"),
+                Some(true),
+            ),
+            (
+                BonusKind::NoSpending,
+                week("2024-03-11T00:00:00Z", "2024-03-18T00:00:00Z"),
+                Some(false),
+            ),
+        ] {
+            assert_eq!(
+                rule(kind).evaluate(&mut tx, start, end).await.unwrap(),
+                expected,
+            );
+        }
+    }
+}
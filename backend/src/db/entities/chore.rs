@@ -0,0 +1,242 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::{ChoreStatus, Timestamp, UID};
+
+/// A chore that children can do for a reward.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(Chores)]
+pub struct Chore {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The family of this chore.
+    pub family_uid: UID,
+
+    /// A short title.
+    pub title: String,
+
+    /// The reward paid when this chore is approved.
+    pub reward: i64,
+
+    /// An optional due date.
+    pub due: Option<Timestamp>,
+
+    /// The child this chore is assigned to, or `None` if it is open to all
+    /// children.
+    pub assignee_uid: Option<UID>,
+
+    /// The child that claimed this chore.
+    pub claimed_by: Option<UID>,
+
+    /// The status of this chore.
+    pub status: ChoreStatus,
+
+    /// The timestamp when this chore was created.
+    pub created: Timestamp,
+
+    /// The timestamp when this chore was approved.
+    pub approved: Option<Timestamp>,
+
+    /// The parent that approved this chore.
+    pub approved_by: Option<UID>,
+
+    /// The template from which this chore was generated, if any.
+    pub template_uid: Option<UID>,
+}
+
+impl Chore {
+    /// The SQL statement used to load all chores of a family that have
+    /// neither been approved nor missed.
+    const READ_FOR_FAMILY: &'static str =
+        sql_from_file!("Chore.read-for-family");
+
+    /// The SQL statement used to load the chores of a child.
+    const READ_FOR_USER: &'static str = sql_from_file!("Chore.read-for-user");
+
+    /// The SQL statement used to load the required chores of a child.
+    const READ_REQUIRED_FOR_USER: &'static str =
+        sql_from_file!("Chore.read-required-for-user");
+
+    /// The SQL statement used to load all open chores that are past due.
+    const READ_OVERDUE: &'static str = sql_from_file!("Chore.read-overdue");
+
+    /// Loads all chores of a family that have neither been approved nor
+    /// missed.
+    ///
+    /// Chores are ordered by due date, with chores without a due date last.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `family_uid` - The family UID.
+    pub async fn read_for_family<'a>(
+        tx: &mut Tx<'a>,
+        family_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_FAMILY)
+            .bind(family_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Loads all open chores that were due at a specific time.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `now` - The current time.
+    pub async fn read_overdue<'a>(
+        tx: &mut Tx<'a>,
+        now: Timestamp,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_OVERDUE)
+            .bind(now)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Loads the chores of a child due within a time span.
+    ///
+    /// Chores open to all children only count for the child that claimed
+    /// them.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The child.
+    /// *  `after` - The exclusive start of the time span.
+    /// *  `until` - The inclusive end of the time span.
+    pub async fn read_for_user<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+        after: Timestamp,
+        until: Timestamp,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_USER)
+            .bind(user_uid)
+            .bind(user_uid)
+            .bind(after)
+            .bind(until)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Loads the chores of a child due within a time span that count towards
+    /// allowance requirements.
+    ///
+    /// Chores open to all children only count for the child that claimed
+    /// them.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The child.
+    /// *  `after` - The exclusive start of the time span.
+    /// *  `until` - The inclusive end of the time span.
+    pub async fn read_required_for_user<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+        after: Timestamp,
+        until: Timestamp,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_REQUIRED_FOR_USER)
+            .bind(user_uid)
+            .bind(user_uid)
+            .bind(after)
+            .bind(until)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Whether this chore has been completed by a child.
+    pub fn is_completed(&self) -> bool {
+        matches!(self.status, ChoreStatus::Done | ChoreStatus::Approved)
+    }
+
+    /// Whether a child may claim and complete this chore.
+    ///
+    /// # Arguments
+    /// *  `user_uid` - The child.
+    pub fn is_available_to(&self, user_uid: &UID) -> bool {
+        match self.status {
+            ChoreStatus::Open => self
+                .assignee_uid
+                .as_ref()
+                .map(|assignee_uid| assignee_uid == user_uid)
+                .unwrap_or(true),
+            ChoreStatus::Claimed => self.claimed_by.as_ref() == Some(user_uid),
+            _ => false,
+        }
+    }
+
+    /// Whether this chore is visible to a child.
+    ///
+    /// # Arguments
+    /// *  `user_uid` - The child.
+    pub fn is_visible_to(&self, user_uid: &UID) -> bool {
+        self.is_available_to(user_uid)
+            || self.claimed_by.as_ref() == Some(user_uid)
+    }
+}
+
+entity_tests! {
+    Chore[UID = UID::new()] {
+        entity: |id| Chore {
+            uid: id,
+            family_uid: UID::new(),
+            title: "title".into(),
+            reward: 42,
+            due: None,
+            assignee_uid: None,
+            claimed_by: None,
+            status: ChoreStatus::Open,
+            created: Timestamp::now(),
+            approved: None,
+            approved_by: None,
+            template_uid: None,
+        };
+        modify: |e| Chore {
+            title: "another title".into(),
+            ..e
+        };
+        prepare: |tx, e| {
+            crate::db::entities::family::tests::entity_with_id(
+                e.family_uid.clone(),
+            ).create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use super::*;
+
+    #[test]
+    fn is_available_to() {
+        let (user1, user2) = (UID::new(), UID::new());
+        let chore = tests::entity();
+        assert!(chore.is_available_to(&user1));
+        assert!(Chore {
+            assignee_uid: Some(user1.clone()),
+            ..chore.clone()
+        }
+        .is_available_to(&user1));
+        assert!(!Chore {
+            assignee_uid: Some(user1.clone()),
+            ..chore.clone()
+        }
+        .is_available_to(&user2));
+        let claimed = Chore {
+            status: ChoreStatus::Claimed,
+            claimed_by: Some(user1.clone()),
+            ..chore.clone()
+        };
+        assert!(claimed.is_available_to(&user1));
+        assert!(!claimed.is_available_to(&user2));
+        let done = Chore {
+            status: ChoreStatus::Done,
+            ..claimed
+        };
+        assert!(!done.is_available_to(&user1));
+        assert!(done.is_visible_to(&user1));
+        assert!(!done.is_visible_to(&user2));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::prelude::*;
+
+use chrono::{NaiveTime, TimeZone, Utc};
+use weru::database::entity;
+
+use crate::db::entities::Chore;
+use crate::db::values::{ChoreStatus, Schedule, Timestamp, UID};
+
+/// A template from which chores are generated on a schedule.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(ChoreTemplates)]
+pub struct ChoreTemplate {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The family of this template.
+    pub family_uid: UID,
+
+    /// The title of the generated chores.
+    pub title: String,
+
+    /// The reward of the generated chores.
+    pub reward: i64,
+
+    /// The child the generated chores are assigned to, or `None` if they are
+    /// open to all children.
+    pub assignee_uid: Option<UID>,
+
+    /// The schedule on which chores are generated.
+    pub schedule: Schedule,
+
+    /// The amount deducted from the assignee when a generated chore is
+    /// missed.
+    pub penalty: i64,
+
+    /// The timestamp when this template was created.
+    pub created: Timestamp,
+
+    /// The timestamp when a chore was last generated.
+    pub generated: Option<Timestamp>,
+
+    /// Whether the generated chores count towards allowance requirements.
+    pub required: bool,
+}
+
+impl ChoreTemplate {
+    /// The SQL statement used to load all templates of a family.
+    const READ_FOR_FAMILY: &'static str =
+        sql_from_file!("ChoreTemplate.read-for-family");
+
+    /// Loads all templates of a family.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `family_uid` - The family UID.
+    pub async fn read_for_family<'a>(
+        tx: &mut Tx<'a>,
+        family_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_FAMILY)
+            .bind(family_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Whether a chore should be generated at a specific time.
+    ///
+    /// At most one chore is generated per day.
+    ///
+    /// # Arguments
+    /// *  `now` - The time to check.
+    pub fn is_due(&self, now: Timestamp) -> bool {
+        let date = now.0.date_naive();
+        self.schedule.is_due(date)
+            && self
+                .generated
+                .map(|generated| generated.0.date_naive() != date)
+                .unwrap_or(true)
+    }
+
+    /// Generates a chore from this template.
+    ///
+    /// The chore is due at the start of the next day on which the schedule is
+    /// due.
+    ///
+    /// # Arguments
+    /// *  `now` - The current time.
+    pub fn instantiate(&self, now: Timestamp) -> Chore {
+        let due = self.schedule.next_after(now.0.date_naive());
+        let due = Timestamp(
+            Utc.from_utc_datetime(&due.and_time(NaiveTime::MIN)).into(),
+        );
+        Chore::new(
+            UID::new(),
+            self.family_uid.clone(),
+            self.title.clone(),
+            self.reward,
+            Some(due),
+            self.assignee_uid.clone(),
+            None,
+            ChoreStatus::Open,
+            now,
+            None,
+            None,
+            Some(self.uid.clone()),
+        )
+    }
+}
+
+entity_tests! {
+    ChoreTemplate[UID = UID::new()] {
+        entity: |id| ChoreTemplate {
+            uid: id,
+            family_uid: UID::new(),
+            title: "title".into(),
+            reward: 42,
+            assignee_uid: None,
+            schedule: Schedule::Daily,
+            penalty: 0,
+            created: Timestamp::now(),
+            generated: None,
+            required: false,
+        };
+        modify: |e| ChoreTemplate {
+            title: "another title".into(),
+            ..e
+        };
+        prepare: |tx, e| {
+            crate::db::entities::family::tests::entity_with_id(
+                e.family_uid.clone(),
+            ).create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use super::*;
+
+    #[test]
+    fn is_due() {
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let template = ChoreTemplate {
+            schedule: "Sat".parse().unwrap(),
+            ..tests::entity()
+        };
+        assert!(template.is_due(at("2024-03-02T08:00:00Z")));
+        assert!(!template.is_due(at("2024-03-03T08:00:00Z")));
+        assert!(!ChoreTemplate {
+            generated: Some(at("2024-03-02T01:00:00Z")),
+            ..template.clone()
+        }
+        .is_due(at("2024-03-02T08:00:00Z")));
+    }
+
+    #[test]
+    fn instantiate() {
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let template = ChoreTemplate {
+            schedule: "Sat".parse().unwrap(),
+            ..tests::entity()
+        };
+        let chore = template.instantiate(at("2024-03-02T08:00:00Z"));
+        assert_eq!(chore.title, template.title);
+        assert_eq!(chore.status, ChoreStatus::Open);
+        assert_eq!(chore.due, Some(at("2024-03-09T00:00:00Z")));
+        assert_eq!(chore.template_uid, Some(template.uid));
+    }
+}
@@ -7,10 +7,10 @@ use crate::db::values::UID;
 /// The configuration used by a family.
 pub struct Configuration {
     /// The family using this configuration.
-    family_uid: UID,
+    pub family_uid: UID,
 
     /// The currency name.
-    currency: String,
+    pub currency: String,
 }
 
 entity_tests! {
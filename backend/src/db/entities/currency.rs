@@ -2,17 +2,81 @@ use crate::prelude::*;
 
 use weru::database::entity;
 
-use crate::db::values::CurrencyFormat;
+use crate::db::values::{CurrencyFormat, NegativeStyle};
 
 /// A description of a supported currency.
+///
+/// All amounts are stored as integers in the minor unit of the currency, so
+/// an amount of `4250` in a currency with exponent `2` is `42.50`.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[entity(Currencies)]
 pub struct Currency {
     /// The currency name.
-    name: String,
+    pub name: String,
 
     /// The format string used to stringify values in this currency.
-    format: CurrencyFormat,
+    pub format: CurrencyFormat,
+
+    /// The number of digits of the minor unit.
+    #[serde(default)]
+    pub exponent: u32,
+
+    /// The symbol separating the major and minor units.
+    ///
+    /// This is only used when the exponent is non-zero.
+    #[serde(default)]
+    pub decimal_separator: String,
+
+    /// The symbol separating groups of thousands, or empty for no grouping.
+    #[serde(default)]
+    pub grouping_separator: String,
+
+    /// The way negative values are presented.
+    #[serde(default)]
+    pub negative_style: NegativeStyle,
+}
+
+impl Currency {
+    /// Formats an amount in this currency.
+    ///
+    /// # Arguments
+    /// *  `amount` - The amount, in the minor unit of this currency.
+    pub fn format_amount(&self, amount: i64) -> String {
+        let magnitude = amount.unsigned_abs();
+        let divisor = 10u64.pow(self.exponent);
+        let major = magnitude / divisor;
+        let minor = magnitude % divisor;
+
+        let mut value = String::new();
+        let digits = major.to_string();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                value.push_str(&self.grouping_separator);
+            }
+            value.push(digit);
+        }
+        if self.exponent > 0 {
+            value.push_str(&self.decimal_separator);
+            value.push_str(&format!(
+                "{:0width$}",
+                minor,
+                width = self.exponent as usize,
+            ));
+        }
+
+        let prefix = self.format.prefix();
+        let suffix = self.format.suffix();
+        if amount >= 0 {
+            format!("{}{}{}", prefix, value, suffix)
+        } else {
+            use NegativeStyle::*;
+            match self.negative_style {
+                Leading => format!("-{}{}{}", prefix, value, suffix),
+                Inner => format!("{}-{}{}", prefix, value, suffix),
+                Parentheses => format!("({}{}{})", prefix, value, suffix),
+            }
+        }
+    }
 }
 
 entity_tests! {
@@ -20,9 +84,14 @@ entity_tests! {
         entity: |id| Currency {
             name: id,
             format: CurrencyFormat::new("#{}"),
+            exponent: 2,
+            decimal_separator: ".".into(),
+            grouping_separator: ",".into(),
+            negative_style: NegativeStyle::Leading,
         };
         modify: |e| Currency {
             format: CurrencyFormat::new("%{}"),
+            negative_style: NegativeStyle::Parentheses,
             ..e
         };
         prepare: |_c, _e| {
@@ -30,3 +99,61 @@ entity_tests! {
         };
     }
 }
+
+#[cfg(test)]
+mod impl_tests {
+    use super::*;
+
+    /// Creates a currency with a specific format.
+    fn currency(
+        format: &str,
+        exponent: u32,
+        decimal_separator: &str,
+        grouping_separator: &str,
+        negative_style: NegativeStyle,
+    ) -> Currency {
+        Currency::new(
+            "TST".into(),
+            CurrencyFormat::new(format),
+            exponent,
+            decimal_separator.into(),
+            grouping_separator.into(),
+            negative_style,
+        )
+    }
+
+    #[test]
+    fn format_amount_no_minor_unit() {
+        let c = currency("{} kr", 0, ",", "", NegativeStyle::Leading);
+        assert_eq!(c.format_amount(0), "0 kr");
+        assert_eq!(c.format_amount(42), "42 kr");
+        assert_eq!(c.format_amount(1234567), "1234567 kr");
+    }
+
+    #[test]
+    fn format_amount_minor_unit() {
+        let c = currency("${}", 2, ".", ",", NegativeStyle::Leading);
+        assert_eq!(c.format_amount(0), "$0.00");
+        assert_eq!(c.format_amount(5), "$0.05");
+        assert_eq!(c.format_amount(4250), "$42.50");
+        assert_eq!(c.format_amount(123456789), "$1,234,567.89");
+
+        let c = currency("{} €", 2, ",", ".", NegativeStyle::Leading);
+        assert_eq!(c.format_amount(123456789), "1.234.567,89 €");
+
+        let c = currency("{} KWD", 3, ".", " ", NegativeStyle::Leading);
+        assert_eq!(c.format_amount(1234567), "1 234.567 KWD");
+    }
+
+    #[test]
+    fn format_amount_negative() {
+        let c = currency("${}", 2, ".", ",", NegativeStyle::Leading);
+        assert_eq!(c.format_amount(-123456), "-$1,234.56");
+
+        let c = currency("${}", 2, ".", ",", NegativeStyle::Inner);
+        assert_eq!(c.format_amount(-123456), "$-1,234.56");
+
+        let c = currency("${}", 2, ".", ",", NegativeStyle::Parentheses);
+        assert_eq!(c.format_amount(-123456), "($1,234.56)");
+    }
+}
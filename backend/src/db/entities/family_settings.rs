@@ -0,0 +1,202 @@
+use crate::prelude::*;
+
+use chrono::{Duration, NaiveDate};
+use weru::database::entity;
+
+use crate::db::values::{Timestamp, UID};
+
+/// The settings of a family.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(FamilySettings)]
+pub struct FamilySettings {
+    /// The family.
+    pub family_uid: UID,
+
+    /// The amount from which requests require approval by several parents.
+    ///
+    /// If this is `None`, a single approval always suffices.
+    pub approval_threshold: Option<i64>,
+
+    /// The number of distinct parents required to approve requests whose
+    /// amount reaches the threshold.
+    pub approvals_required: i64,
+
+    /// The number of days after which parents are reminded of a pending
+    /// request.
+    ///
+    /// If this is `None`, no reminders are sent.
+    pub request_reminder: Option<i64>,
+
+    /// The number of days after which a pending request expires.
+    ///
+    /// If this is `None`, requests never expire.
+    pub request_expiry: Option<i64>,
+
+    /// How far below zero the balance of a child may fall because of fines.
+    pub overdraft_limit: i64,
+
+    /// The time zone of the family, as the offset from UTC in minutes.
+    pub utc_offset: i32,
+}
+
+impl FamilySettings {
+    /// The settings used by families that have not changed them.
+    ///
+    /// # Arguments
+    /// *  `family_uid` - The family UID.
+    pub fn default_for(family_uid: &UID) -> Self {
+        Self::new(family_uid.clone(), None, 1, None, None, 0, 0)
+    }
+
+    /// Loads the settings of a family, falling back on the default settings.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `family_uid` - The family UID.
+    pub async fn read_or_default<'a>(
+        tx: &mut Tx<'a>,
+        family_uid: &UID,
+    ) -> Result<Self, DatabaseError> {
+        Ok(Self::read(tx.as_mut(), family_uid)
+            .await?
+            .unwrap_or_else(|| Self::default_for(family_uid)))
+    }
+
+    /// Stores these settings, creating them if necessary.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    pub async fn store<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+    ) -> Result<(), DatabaseError> {
+        if Self::read(tx.as_mut(), &self.family_uid).await?.is_some() {
+            self.update(tx.as_mut()).await
+        } else {
+            self.create(tx.as_mut()).await
+        }
+    }
+
+    /// The number of approvals required for a request.
+    ///
+    /// # Arguments
+    /// *  `amount` - The amount of the request.
+    pub fn approvals_for(&self, amount: i64) -> usize {
+        match self.approval_threshold {
+            Some(threshold) if amount >= threshold => {
+                self.approvals_required.max(1) as usize
+            }
+            _ => 1,
+        }
+    }
+
+    /// The date in the time zone of the family at a specific time.
+    ///
+    /// # Arguments
+    /// *  `timestamp` - The timestamp.
+    pub fn local_date(&self, timestamp: Timestamp) -> NaiveDate {
+        (timestamp.0.naive_utc() + Duration::minutes(self.utc_offset.into()))
+            .date()
+    }
+}
+
+entity_tests! {
+    FamilySettings[UID = UID::new()] {
+        entity: |id| FamilySettings {
+            family_uid: id,
+            approval_threshold: None,
+            approvals_required: 1,
+            request_reminder: None,
+            request_expiry: None,
+            overdraft_limit: 0,
+            utc_offset: 0,
+        };
+        modify: |e| FamilySettings {
+            approval_threshold: Some(1000),
+            approvals_required: 2,
+            request_reminder: Some(3),
+            request_expiry: Some(14),
+            overdraft_limit: 500,
+            utc_offset: 60,
+            ..e
+        };
+        prepare: |tx, e| {
+            crate::db::entities::family::tests::entity_with_id(
+                e.family_uid.clone(),
+            ).create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    #[actix_rt::test]
+    async fn read_or_default() {
+        use super::*;
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let mut tx = conn.begin().await.unwrap();
+
+        let settings = FamilySettings::read_or_default(&mut tx, &family.uid)
+            .await
+            .unwrap();
+        assert_eq!(settings, FamilySettings::default_for(&family.uid));
+
+        let settings = FamilySettings {
+            approval_threshold: Some(1000),
+            approvals_required: 2,
+            ..settings
+        };
+        settings.store(&mut tx).await.unwrap();
+        assert_eq!(
+            FamilySettings::read_or_default(&mut tx, &family.uid)
+                .await
+                .unwrap(),
+            settings,
+        );
+    }
+
+    #[test]
+    fn approvals_for() {
+        use super::*;
+        let settings =
+            FamilySettings::new(UID::new(), Some(1000), 2, None, None, 0, 0);
+        assert_eq!(settings.approvals_for(999), 1);
+        assert_eq!(settings.approvals_for(1000), 2);
+        assert_eq!(
+            FamilySettings::default_for(&UID::new()).approvals_for(1000),
+            1
+        );
+    }
+
+    #[test]
+    fn local_date() {
+        use super::*;
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let settings = FamilySettings {
+            utc_offset: -300,
+            ..FamilySettings::default_for(&UID::new())
+        };
+        assert_eq!(
+            settings.local_date(at("2024-06-03T04:00:00Z")),
+            date("2024-06-02")
+        );
+        assert_eq!(
+            settings.local_date(at("2024-06-03T06:00:00Z")),
+            date("2024-06-03")
+        );
+        assert_eq!(
+            FamilySettings {
+                utc_offset: 120,
+                ..settings
+            }
+            .local_date(at("2024-06-02T23:00:00Z")),
+            date("2024-06-03"),
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::{Timestamp, UID};
+
+/// A reason for which parents may fine their children.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(FineReasons)]
+pub struct FineReason {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The family of this reason.
+    pub family_uid: UID,
+
+    /// A short description, used as description of fines.
+    pub name: String,
+
+    /// The default amount of fines for this reason.
+    pub amount: i64,
+
+    /// The timestamp when this reason was created.
+    pub created: Timestamp,
+}
+
+impl FineReason {
+    /// The SQL statement used to load all reasons of a family.
+    const READ_FOR_FAMILY: &'static str =
+        sql_from_file!("FineReason.read-for-family");
+
+    /// Loads all reasons of a family.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `family_uid` - The family UID.
+    pub async fn read_for_family<'a>(
+        tx: &mut Tx<'a>,
+        family_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_FAMILY)
+            .bind(family_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    FineReason[UID = UID::new()] {
+        entity: |id| FineReason {
+            uid: id,
+            family_uid: UID::new(),
+            name: "Rudeness".into(),
+            amount: 100,
+            created: Timestamp::now(),
+        };
+        modify: |e| FineReason {
+            amount: 200,
+            ..e
+        };
+        prepare: |tx, e| {
+            crate::db::entities::family::tests::entity_with_id(
+                e.family_uid.clone(),
+            ).create(tx.as_mut()).await
+        };
+    }
+}
@@ -0,0 +1,158 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::{Timestamp, UID};
+
+/// A link through which a relative without an account can announce a gift
+/// for a child.
+///
+/// The unique identifier is the token shared with the relative. A link can
+/// be used to record a single gift before it expires.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(GiftLinks)]
+pub struct GiftLink {
+    /// The unique identifier, which is also the token of the link.
+    pub uid: UID,
+
+    /// The child receiving the gift.
+    pub user_uid: UID,
+
+    /// The timestamp after which the link can no longer be used.
+    pub expires: Timestamp,
+
+    /// The name of the giver, or `None` if no gift has been recorded.
+    pub giver: Option<String>,
+
+    /// The message from the giver, or `None` if no gift has been recorded.
+    pub message: Option<String>,
+
+    /// The amount of the gift, or `None` if no gift has been recorded.
+    pub amount: Option<i64>,
+
+    /// The timestamp when the gift was recorded, or `None` if no gift has
+    /// been recorded.
+    pub recorded: Option<Timestamp>,
+
+    /// The timestamp when the link was created.
+    pub created: Timestamp,
+
+    /// The parent who created the link.
+    pub created_by: Option<UID>,
+}
+
+impl GiftLink {
+    /// The SQL statement used to load all links for the children of a family.
+    const READ_FOR_FAMILY: &'static str =
+        sql_from_file!("GiftLink.read-for-family");
+
+    /// Whether this link has expired at a specific time.
+    ///
+    /// # Arguments
+    /// *  `now` - The current time.
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.expires.0 <= now.0
+    }
+
+    /// Whether a gift has been recorded through this link.
+    pub fn is_recorded(&self) -> bool {
+        self.recorded.is_some()
+    }
+
+    /// Loads all links for the children of a family, the oldest first.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `family_uid` - The family UID.
+    pub async fn read_for_family<'a>(
+        tx: &mut Tx<'a>,
+        family_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_FAMILY)
+            .bind(family_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    GiftLink[UID = UID::new()] {
+        entity: |id| GiftLink {
+            uid: id,
+            user_uid: UID::new(),
+            expires: Timestamp::now(),
+            giver: None,
+            message: None,
+            amount: None,
+            recorded: None,
+            created: Timestamp::now(),
+            created_by: None,
+        };
+        modify: |e| GiftLink {
+            giver: Some("Grandma".into()),
+            message: Some("Happy birthday!".into()),
+            amount: Some(5000),
+            recorded: Some(Timestamp::now()),
+            ..e
+        };
+        prepare: |tx, e| {
+            let u = crate::db::entities::user::tests::entity_with_id(
+                e.user_uid.clone(),
+            );
+            crate::db::entities::user::tests::prepare(tx, &u).await?;
+            u.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use actix_rt;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Role;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn read_for_family() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let other_family = create::family(&mut conn, "Other Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let other_user = create::user(
+            &mut conn,
+            Role::Child,
+            "Other User",
+            "other@example.com",
+            &other_family.uid,
+        );
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let link =
+            create::gift_link(&mut conn, &user.uid, at("2024-06-10T00:00:00Z"));
+        create::gift_link(
+            &mut conn,
+            &other_user.uid,
+            at("2024-06-10T00:00:00Z"),
+        );
+        let mut tx = conn.begin().await.unwrap();
+
+        assert_eq!(
+            GiftLink::read_for_family(&mut tx, &family.uid)
+                .await
+                .unwrap(),
+            vec![link.clone()],
+        );
+        assert!(!link.is_expired(at("2024-06-09T23:59:59Z")));
+        assert!(link.is_expired(at("2024-06-10T00:00:00Z")));
+        assert!(!link.is_recorded());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::{Timestamp, UID, URL};
+
+/// A savings goal for a user.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(Goals)]
+pub struct Goal {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The user saving towards this goal.
+    pub user_uid: UID,
+
+    /// A short name.
+    pub name: String,
+
+    /// The target amount.
+    pub amount: i64,
+
+    /// A relevant URL.
+    pub url: Option<URL>,
+
+    /// An optional deadline.
+    pub deadline: Option<Timestamp>,
+
+    /// The part of the balance reserved for this goal.
+    pub earmarked: i64,
+
+    /// The timestamp when this goal was completed.
+    pub completed: Option<Timestamp>,
+}
+
+impl Goal {
+    /// The SQL statement used to load all active goals for a user.
+    const READ_FOR_USER: &'static str = sql_from_file!("Goal.read-for-user");
+
+    /// Loads all active goals for a user.
+    ///
+    /// Goals are ordered by deadline, with goals without a deadline last.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    pub async fn read_for_user<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_USER)
+            .bind(user_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    Goal[UID = UID::new()] {
+        entity: |id| Goal {
+            uid: id,
+            user_uid: UID::new(),
+            name: "name".into(),
+            amount: 42,
+            url: None,
+            deadline: None,
+            earmarked: 0,
+            completed: None,
+        };
+        modify: |e| Goal {
+            earmarked: 10,
+            completed: Some(Timestamp::now()),
+            ..e
+        };
+        prepare: |tx, e| {
+            let u = crate::db::entities::user::tests::entity_with_id(
+                e.user_uid.clone(),
+            );
+            crate::db::entities::user::tests::prepare(tx, &u).await?;
+            u.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use actix_rt;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Role;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn read_for_user() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user1 = create::user(
+            &mut conn,
+            Role::Child,
+            "User 1",
+            "test1@example.com",
+            &family.uid,
+        );
+        let user2 = create::user(
+            &mut conn,
+            Role::Child,
+            "User 2",
+            "test2@example.com",
+            &family.uid,
+        );
+        let goal1 = create::goal(&mut conn, &user1.uid, "Bicycle", 1000);
+        let goal2 = create::goal(&mut conn, &user1.uid, "Game", 400);
+        let completed = Goal {
+            completed: Some(Timestamp::now()),
+            ..create::goal(&mut conn, &user1.uid, "Book", 100)
+        };
+        completed.update(conn.as_mut()).await.unwrap();
+        create::goal(&mut conn, &user2.uid, "Skateboard", 500);
+        let mut tx = conn.begin().await.unwrap();
+
+        let goals = Goal::read_for_user(&mut tx, &user1.uid).await.unwrap();
+        assert_eq!(goals, vec![goal1, goal2]);
+    }
+}
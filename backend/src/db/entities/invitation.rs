@@ -63,8 +63,12 @@ impl Invitation {
             let amount = self.allowance_amount?;
             let schedule = self.allowance_schedule.clone()?;
             Some(AllowanceDescription {
+                ledger_uid: Some(None),
                 amount: Some(amount),
                 schedule: Some(schedule),
+                required_share: Some(None),
+                prorate: Some(false),
+                age_scale: Some(None),
                 ..Default::default()
             })
         } else {
@@ -79,6 +83,7 @@ impl Invitation {
             name: Some(self.name.clone()),
             email: Some(Some(self.email.clone())),
             family_uid: Some(self.family_uid.clone()),
+            birth_date: Some(None),
         }
     }
 }
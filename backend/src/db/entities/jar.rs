@@ -0,0 +1,166 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::UID;
+
+/// A named sub-account, between which incoming money is split.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(Jars)]
+pub struct Jar {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The user owning this jar.
+    pub user_uid: UID,
+
+    /// The jar name.
+    pub name: String,
+
+    /// The percentage of incoming money put in this jar.
+    pub percentage: u32,
+}
+
+impl Jar {
+    /// The SQL statement used to load all jars of a user.
+    const READ_FOR_USER: &'static str = sql_from_file!("Jar.read-for-user");
+
+    /// Loads all jars for a user.
+    ///
+    /// Jars are ordered by descending percentage.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    pub async fn read_for_user<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_USER)
+            .bind(user_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Splits an amount between jars.
+    ///
+    /// The amount put in each jar is rounded towards zero, and the remainder
+    /// is put in the first jar. Jars receiving nothing are not included. If
+    /// there are no jars, the entire amount is returned unallocated.
+    ///
+    /// # Arguments
+    /// *  `jars` - The jars between which to split the amount.
+    /// *  `amount` - The amount to split.
+    pub fn split(jars: &[Self], amount: i64) -> Vec<(Option<UID>, i64)> {
+        if jars.is_empty() {
+            return vec![(None, amount)];
+        }
+
+        let mut parts = jars
+            .iter()
+            .map(|jar| {
+                (Some(jar.uid.clone()), amount * jar.percentage as i64 / 100)
+            })
+            .collect::<Vec<_>>();
+        let remainder = amount - parts.iter().map(|(_, a)| a).sum::<i64>();
+        parts[0].1 += remainder;
+        parts.into_iter().filter(|(_, a)| *a != 0).collect()
+    }
+}
+
+entity_tests! {
+    Jar[UID = UID::new()] {
+        entity: |id| Jar {
+            uid: id,
+            user_uid: UID::new(),
+            name: "save".into(),
+            percentage: 50,
+        };
+        modify: |e| Jar {
+            percentage: 40,
+            ..e
+        };
+        prepare: |tx, e| {
+            let u = crate::db::entities::user::tests::entity_with_id(
+                e.user_uid.clone(),
+            );
+            crate::db::entities::user::tests::prepare(tx, &u).await?;
+            u.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use actix_rt;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Role;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn read_for_user() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user1 = create::user(
+            &mut conn,
+            Role::Child,
+            "User 1",
+            "test1@example.com",
+            &family.uid,
+        );
+        let user2 = create::user(
+            &mut conn,
+            Role::Child,
+            "User 2",
+            "test2@example.com",
+            &family.uid,
+        );
+        let jar1 = create::jar(&mut conn, &user1.uid, "give", 10);
+        let jar2 = create::jar(&mut conn, &user1.uid, "spend", 60);
+        let jar3 = create::jar(&mut conn, &user1.uid, "save", 30);
+        create::jar(&mut conn, &user2.uid, "spend", 100);
+        let mut tx = conn.begin().await.unwrap();
+
+        assert_eq!(
+            Jar::read_for_user(&mut tx, &user1.uid).await.unwrap(),
+            vec![jar2, jar3, jar1],
+        );
+    }
+
+    #[test]
+    fn split_empty() {
+        assert_eq!(Jar::split(&[], 42), vec![(None, 42)]);
+    }
+
+    #[test]
+    fn split() {
+        let jar = |percentage| Jar {
+            uid: UID::new(),
+            user_uid: UID::new(),
+            name: "jar".into(),
+            percentage,
+        };
+        let jars = vec![jar(60), jar(30), jar(10), jar(0)];
+
+        assert_eq!(
+            Jar::split(&jars, 1000),
+            vec![
+                (Some(jars[0].uid.clone()), 600),
+                (Some(jars[1].uid.clone()), 300),
+                (Some(jars[2].uid.clone()), 100),
+            ],
+        );
+        assert_eq!(
+            Jar::split(&jars, 15),
+            vec![
+                (Some(jars[0].uid.clone()), 10),
+                (Some(jars[1].uid.clone()), 4),
+                (Some(jars[2].uid.clone()), 1),
+            ],
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::UID;
+
+/// A non-monetary ledger, such as screen time minutes or reward points.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(Ledgers)]
+pub struct Ledger {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The unique identifier of the family.
+    pub family_uid: UID,
+
+    /// The ledger name.
+    pub name: String,
+
+    /// The unit of values in this ledger.
+    pub unit: String,
+}
+
+impl Ledger {
+    /// The SQL statement used to load all ledgers of a family.
+    const READ_FOR_FAMILY: &'static str =
+        sql_from_file!("Ledger.read-for-family");
+
+    /// Loads all ledgers for a family.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `family_uid` - The family UID.
+    pub async fn read_for_family<'a>(
+        tx: &mut Tx<'a>,
+        family_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_FAMILY)
+            .bind(family_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    Ledger[UID = UID::new()] {
+        entity: |id| Ledger {
+            uid: id,
+            family_uid: UID::new(),
+            name: "Screen time".into(),
+            unit: "minutes".into(),
+        };
+        modify: |e| Ledger {
+            name: "Stars".into(),
+            unit: "stars".into(),
+            ..e
+        };
+        prepare: |tx, e| {
+            crate::db::entities::family::tests::entity_with_id(
+                e.family_uid.clone(),
+            ).create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use actix_rt;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn read_for_family() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family1 = create::family(&mut conn, "Family 1");
+        let family2 = create::family(&mut conn, "Family 2");
+        let ledger1 =
+            create::ledger(&mut conn, &family1.uid, "Screen time", "minutes");
+        let ledger2 = create::ledger(&mut conn, &family1.uid, "Stars", "stars");
+        create::ledger(&mut conn, &family2.uid, "Stars", "stars");
+        let mut tx = conn.begin().await.unwrap();
+
+        let ledgers = Ledger::read_for_family(&mut tx, &family1.uid)
+            .await
+            .unwrap();
+        assert_eq!(ledgers.len(), 2);
+        assert!(ledgers.contains(&ledger1));
+        assert!(ledgers.contains(&ledger2));
+    }
+}
@@ -0,0 +1,225 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::entities::Transaction;
+use crate::db::values::{MatchKind, Period, Timestamp, UID};
+
+/// A rule for parent contributions matching the savings of a user.
+///
+/// For every whole multiple of `per` saved, `matched` is contributed.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(MatchRules)]
+pub struct MatchRule {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The user whose savings are matched.
+    pub user_uid: UID,
+
+    /// A short name, used as description of matching transactions.
+    pub name: String,
+
+    /// What is matched.
+    pub kind: MatchKind,
+
+    /// The amount required for a contribution.
+    pub per: i64,
+
+    /// The amount contributed for every whole multiple of `per`.
+    pub matched: i64,
+
+    /// The maximum contribution per period.
+    pub cap: Option<i64>,
+
+    /// The period over which the rule is evaluated.
+    pub period: Period,
+
+    /// The jar whose money is matched and which receives contributions.
+    ///
+    /// If this is not set, the entire balance is matched.
+    pub jar_uid: Option<UID>,
+
+    /// A goal for which contributions are earmarked.
+    pub goal_uid: Option<UID>,
+
+    /// The timestamp when this rule was created.
+    pub created: Timestamp,
+}
+
+impl MatchRule {
+    /// The SQL statement used to load all rules for a user.
+    const READ_FOR_USER: &'static str =
+        sql_from_file!("MatchRule.read-for-user");
+
+    /// Loads all rules for a user.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    pub async fn read_for_user<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_USER)
+            .bind(user_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Calculates the contribution of this rule for a time span.
+    ///
+    /// Deposits made before this rule was created are not matched.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `start` - The start of the time span.
+    /// *  `end` - The end of the time span.
+    pub async fn evaluate<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        start: Timestamp,
+        end: Timestamp,
+    ) -> Result<i64, DatabaseError> {
+        let base = match self.kind {
+            MatchKind::Savings => {
+                Transaction::balance_before(
+                    tx,
+                    &self.user_uid,
+                    self.jar_uid.as_ref(),
+                    end,
+                )
+                .await?
+            }
+            MatchKind::Deposits => {
+                let start = if self.created.0 > start.0 {
+                    self.created
+                } else {
+                    start
+                };
+                Transaction::deposits(
+                    tx,
+                    &self.user_uid,
+                    self.jar_uid.as_ref(),
+                    start..end,
+                )
+                .await?
+            }
+        };
+        let amount = if self.per > 0 {
+            base.max(0) / self.per * self.matched
+        } else {
+            0
+        };
+        Ok(self.cap.map(|cap| amount.min(cap)).unwrap_or(amount))
+    }
+}
+
+entity_tests! {
+    MatchRule[UID = UID::new()] {
+        entity: |id| MatchRule {
+            uid: id,
+            user_uid: UID::new(),
+            name: "Savings bonus".into(),
+            kind: MatchKind::Savings,
+            per: 10,
+            matched: 1,
+            cap: None,
+            period: Period::Monthly,
+            jar_uid: None,
+            goal_uid: None,
+            created: Timestamp::now(),
+        };
+        modify: |e| MatchRule {
+            kind: MatchKind::Deposits,
+            cap: Some(100),
+            ..e
+        };
+        prepare: |tx, e| {
+            let u = crate::db::entities::user::tests::entity_with_id(
+                e.user_uid.clone(),
+            );
+            crate::db::entities::user::tests::prepare(tx, &u).await?;
+            u.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use actix_rt;
+    use chrono::Duration;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::{Role, TransactionType};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn evaluate() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let end = Timestamp::now();
+        let start: Timestamp = (end.0 - Duration::days(30)).into();
+        let created: Timestamp = (end.0 - Duration::days(10)).into();
+        for (days, amount) in [(40, 200), (20, 100), (5, 150), (1, -90)].iter()
+        {
+            create::transaction(
+                &mut conn,
+                TransactionType::Gift,
+                &user.uid,
+                "gift",
+                *amount,
+                (end.0 - Duration::days(*days)).into(),
+            );
+        }
+        let rule = |kind, cap| MatchRule {
+            uid: UID::new(),
+            user_uid: user.uid.clone(),
+            name: "Bonus".into(),
+            kind,
+            per: 100,
+            matched: 10,
+            cap,
+            period: Period::Monthly,
+            jar_uid: None,
+            goal_uid: None,
+            created,
+        };
+        let mut tx = conn.begin().await.unwrap();
+
+        // The balance is 360
+        assert_eq!(
+            rule(MatchKind::Savings, None)
+                .evaluate(&mut tx, start, end)
+                .await
+                .unwrap(),
+            30,
+        );
+        assert_eq!(
+            rule(MatchKind::Savings, Some(25))
+                .evaluate(&mut tx, start, end)
+                .await
+                .unwrap(),
+            25,
+        );
+
+        // Only 150 was deposited after the rule was created
+        assert_eq!(
+            rule(MatchKind::Deposits, None)
+                .evaluate(&mut tx, start, end)
+                .await
+                .unwrap(),
+            10,
+        );
+    }
+}
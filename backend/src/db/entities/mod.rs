@@ -158,18 +158,60 @@ macro_rules! entity_tests {
 
 pub mod allowance;
 pub use self::allowance::Allowance;
+pub mod allowance_change;
+pub use self::allowance_change::AllowanceChange;
+pub mod approved_contribution;
+pub use self::approved_contribution::ApprovedContribution;
+pub mod auto_approval;
+pub use self::auto_approval::AutoApproval;
+pub mod birthday_gift;
+pub use self::birthday_gift::BirthdayGift;
+pub mod bonus_rule;
+pub use self::bonus_rule::BonusRule;
+pub mod chore;
+pub use self::chore::Chore;
+pub mod chore_template;
+pub use self::chore_template::ChoreTemplate;
 pub mod configuration;
 pub use self::configuration::Configuration;
 pub mod currency;
 pub use self::currency::Currency;
 pub mod family;
 pub use self::family::Family;
+pub mod family_settings;
+pub use self::family_settings::FamilySettings;
+pub mod fine_reason;
+pub use self::fine_reason::FineReason;
+pub mod gift_link;
+pub use self::gift_link::GiftLink;
+pub mod goal;
+pub use self::goal::Goal;
 pub mod invitation;
 pub use self::invitation::Invitation;
+pub mod jar;
+pub use self::jar::Jar;
+pub mod ledger;
+pub use self::ledger::Ledger;
+pub mod match_rule;
+pub use self::match_rule::MatchRule;
 pub mod password;
 pub use self::password::Password;
+pub mod pending_transaction;
+pub use self::pending_transaction::PendingTransaction;
 pub mod request;
 pub use self::request::Request;
+pub mod request_approval;
+pub use self::request_approval::RequestApproval;
+pub mod request_comment;
+pub use self::request_comment::RequestComment;
+pub mod request_contribution;
+pub use self::request_contribution::RequestContribution;
+pub mod request_reminder;
+pub use self::request_reminder::RequestReminder;
+pub mod rule_match;
+pub use self::rule_match::RuleMatch;
+pub mod subscription;
+pub use self::subscription::Subscription;
 pub mod transaction;
 pub use self::transaction::Transaction;
 pub mod user;
@@ -193,8 +235,41 @@ pub mod create {
         amount: u32,
         schedule: Schedule,
     ) -> Allowance {
-        let result =
-            Allowance::new(UID::new(), user_uid.clone(), amount, schedule);
+        let result = Allowance::new(
+            UID::new(),
+            user_uid.clone(),
+            None,
+            amount,
+            schedule,
+            None,
+            false,
+            None,
+        );
+        block_on(result.create(conn.as_mut())).unwrap();
+        result
+    }
+
+    pub fn chore(
+        conn: &mut Connection,
+        family_uid: &UID,
+        title: &str,
+        reward: i64,
+        assignee_uid: Option<&UID>,
+    ) -> Chore {
+        let result = Chore::new(
+            UID::new(),
+            family_uid.clone(),
+            title.into(),
+            reward,
+            None,
+            assignee_uid.cloned(),
+            None,
+            ChoreStatus::Open,
+            Timestamp::now(),
+            None,
+            None,
+            None,
+        );
         block_on(result.create(conn.as_mut())).unwrap();
         result
     }
@@ -205,6 +280,63 @@ pub mod create {
         result
     }
 
+    pub fn fine_reason(
+        conn: &mut Connection,
+        family_uid: &UID,
+        name: &str,
+        amount: i64,
+    ) -> FineReason {
+        let result = FineReason::new(
+            UID::new(),
+            family_uid.clone(),
+            name.into(),
+            amount,
+            Timestamp::now(),
+        );
+        block_on(result.create(conn.as_mut())).unwrap();
+        result
+    }
+
+    pub fn gift_link(
+        conn: &mut Connection,
+        user_uid: &UID,
+        expires: Timestamp,
+    ) -> GiftLink {
+        let result = GiftLink::new(
+            UID::new(),
+            user_uid.clone(),
+            expires,
+            None,
+            None,
+            None,
+            None,
+            Timestamp::now(),
+            None,
+        );
+        block_on(result.create(conn.as_mut())).unwrap();
+        result
+    }
+
+    pub fn goal(
+        conn: &mut Connection,
+        user_uid: &UID,
+        name: &str,
+        amount: i64,
+    ) -> Goal {
+        let result = Goal::new(
+            UID::new(),
+            user_uid.clone(),
+            name.into(),
+            amount,
+            None,
+            None,
+            0,
+            None,
+        );
+        block_on(result.create(conn.as_mut())).unwrap();
+        result
+    }
+
     pub fn invitation(
         conn: &mut Connection,
         role: Role,
@@ -231,6 +363,58 @@ pub mod create {
         result
     }
 
+    pub fn jar(
+        conn: &mut Connection,
+        user_uid: &UID,
+        name: &str,
+        percentage: u32,
+    ) -> Jar {
+        let result =
+            Jar::new(UID::new(), user_uid.clone(), name.into(), percentage);
+        block_on(result.create(conn.as_mut())).unwrap();
+        result
+    }
+
+    pub fn ledger(
+        conn: &mut Connection,
+        family_uid: &UID,
+        name: &str,
+        unit: &str,
+    ) -> Ledger {
+        let result = Ledger::new(
+            UID::new(),
+            family_uid.clone(),
+            name.into(),
+            unit.into(),
+        );
+        block_on(result.create(conn.as_mut())).unwrap();
+        result
+    }
+
+    pub fn ledger_transaction(
+        conn: &mut Connection,
+        transaction_type: TransactionType,
+        user_uid: &UID,
+        ledger_uid: &UID,
+        description: &str,
+        amount: i64,
+        timestamp: Timestamp,
+    ) -> Transaction {
+        let result = Transaction {
+            ledger_uid: Some(ledger_uid.clone()),
+            ..transaction(
+                conn,
+                transaction_type,
+                user_uid,
+                description,
+                amount,
+                timestamp,
+            )
+        };
+        block_on(result.update(conn.as_mut())).unwrap();
+        result
+    }
+
     pub fn password(
         conn: &mut Connection,
         s: &str,
@@ -244,6 +428,27 @@ pub mod create {
         result
     }
 
+    pub fn pending_transaction(
+        conn: &mut Connection,
+        user_uid: &UID,
+        description: &str,
+        amount: i64,
+        due: Timestamp,
+    ) -> PendingTransaction {
+        let result = PendingTransaction::new(
+            UID::new(),
+            user_uid.clone(),
+            TransactionType::Gift,
+            None,
+            description.into(),
+            amount,
+            due,
+            Timestamp::now(),
+        );
+        block_on(result.create(conn.as_mut())).unwrap();
+        result
+    }
+
     #[allow(static_mut_refs)]
     pub fn request(
         conn: &mut Connection,
@@ -258,17 +463,65 @@ pub mod create {
         let result = Request::new(
             uid,
             user_uid.clone(),
+            None,
             name.into(),
             description.into(),
             amount,
             Some(url.parse().unwrap()),
             Timestamp::now(),
+            RequestStatus::Pending,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        block_on(result.create(conn.as_mut())).unwrap();
+        result
+    }
+
+    pub fn request_comment(
+        conn: &mut Connection,
+        request_uid: &i64,
+        user_uid: &UID,
+        text: &str,
+    ) -> RequestComment {
+        let result = RequestComment::new(
+            UID::new(),
+            *request_uid,
+            user_uid.clone(),
+            text.into(),
+            Timestamp::now(),
         );
         block_on(result.create(conn.as_mut())).unwrap();
         result
     }
 
     #[allow(static_mut_refs)]
+    pub fn subscription(
+        conn: &mut Connection,
+        request: &Request,
+    ) -> Subscription {
+        let result = Subscription::new(
+            UID::new(),
+            request.user_uid.clone(),
+            request.uid,
+            request.ledger_uid.clone(),
+            None,
+            request.name.clone(),
+            request.amount,
+            Schedule::Monthly(1),
+            None,
+            false,
+            None,
+            None,
+        );
+        block_on(result.create(conn.as_mut())).unwrap();
+        result
+    }
+
     pub fn transaction(
         conn: &mut Connection,
         transaction_type: TransactionType,
@@ -283,6 +536,8 @@ pub mod create {
             uid,
             transaction_type,
             user_uid.clone(),
+            None,
+            None,
             description.into(),
             amount,
             timestamp,
@@ -304,6 +559,7 @@ pub mod create {
             name.into(),
             Some(email.parse().unwrap()),
             family_uid.clone(),
+            None,
         );
         block_on(result.create(conn.as_mut())).unwrap();
         result
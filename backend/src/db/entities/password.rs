@@ -81,6 +81,7 @@ mod impl_tests {
             "Test User".into(),
             Some(email.clone()),
             UID::new(),
+            None,
         );
         db::entities::user::tests::prepare(&mut tx, &user)
             .await
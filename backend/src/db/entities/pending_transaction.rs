@@ -0,0 +1,163 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::{Timestamp, TransactionType, UID};
+
+/// A transaction scheduled to be booked at a later time.
+///
+/// Once booked, the pending transaction is removed.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(PendingTransactions)]
+pub struct PendingTransaction {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The user involved in the transaction.
+    pub user_uid: UID,
+
+    /// The type of the transaction.
+    pub transaction_type: TransactionType,
+
+    /// The ledger of the transaction, or `None` for the monetary ledger.
+    pub ledger_uid: Option<UID>,
+
+    /// A description of the transaction.
+    pub description: String,
+
+    /// The amount of the transaction.
+    pub amount: i64,
+
+    /// The timestamp when the transaction is booked.
+    pub due: Timestamp,
+
+    /// The timestamp when the transaction was scheduled.
+    pub created: Timestamp,
+}
+
+impl PendingTransaction {
+    /// The SQL statement used to load all pending transactions of a user.
+    const READ_FOR_USER: &'static str =
+        sql_from_file!("PendingTransaction.read-for-user");
+
+    /// The SQL statement used to load all pending transactions that are due.
+    const READ_DUE: &'static str =
+        sql_from_file!("PendingTransaction.read-due");
+
+    /// Loads all pending transactions of a user, the earliest first.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    pub async fn read_for_user<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_USER)
+            .bind(user_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Loads all pending transactions due at a specific time, the earliest
+    /// first.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `now` - The current time.
+    pub async fn read_due<'a>(
+        tx: &mut Tx<'a>,
+        now: Timestamp,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_DUE)
+            .bind(now)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    PendingTransaction[UID = UID::new()] {
+        entity: |id| PendingTransaction {
+            uid: id,
+            user_uid: UID::new(),
+            transaction_type: TransactionType::Gift,
+            ledger_uid: None,
+            description: "Birthday".into(),
+            amount: 5000,
+            due: Timestamp::now(),
+            created: Timestamp::now(),
+        };
+        modify: |e| PendingTransaction {
+            amount: 2500,
+            ..e
+        };
+        prepare: |tx, e| {
+            let u = crate::db::entities::user::tests::entity_with_id(
+                e.user_uid.clone(),
+            );
+            crate::db::entities::user::tests::prepare(tx, &u).await?;
+            u.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use actix_rt;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Role;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn read_due() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let earlier = create::pending_transaction(
+            &mut conn,
+            &user.uid,
+            "Birthday",
+            5000,
+            at("2024-06-03T00:00:00Z"),
+        );
+        let later = create::pending_transaction(
+            &mut conn,
+            &user.uid,
+            "Pay back",
+            -1000,
+            at("2024-06-07T00:00:00Z"),
+        );
+        let mut tx = conn.begin().await.unwrap();
+
+        assert_eq!(
+            PendingTransaction::read_due(&mut tx, at("2024-06-01T00:00:00Z"))
+                .await
+                .unwrap(),
+            vec![],
+        );
+        assert_eq!(
+            PendingTransaction::read_due(&mut tx, at("2024-06-05T00:00:00Z"))
+                .await
+                .unwrap(),
+            vec![earlier.clone()],
+        );
+        assert_eq!(
+            PendingTransaction::read_for_user(&mut tx, &user.uid)
+                .await
+                .unwrap(),
+            vec![earlier, later],
+        );
+    }
+}
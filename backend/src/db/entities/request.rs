@@ -1,9 +1,11 @@
 use crate::prelude::*;
 
+use std::ops::Range;
 use weru::database::entity;
+
 use weru::futures::StreamExt;
 
-use crate::db::values::{Timestamp, UID, URL};
+use crate::db::values::{RequestStatus, Schedule, Timestamp, UID, URL};
 
 /// A description of a request.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
@@ -18,6 +20,9 @@ pub struct Request {
     /// The user making the request.
     pub user_uid: UID,
 
+    /// The ledger of this request, or `None` for the monetary ledger.
+    pub ledger_uid: Option<UID>,
+
     /// A short name.
     pub name: String,
 
@@ -32,6 +37,48 @@ pub struct Request {
 
     /// The timestamp of this request.
     pub time: Timestamp,
+
+    /// The status of this request.
+    pub status: RequestStatus,
+
+    /// The timestamp when this request was decided, if it is not pending.
+    pub decided: Option<Timestamp>,
+
+    /// The user that decided this request, if it is not pending.
+    pub decided_by: Option<UID>,
+
+    /// The transaction generated when this request was granted.
+    pub transaction_uid: Option<i64>,
+
+    /// The reason given when this request was declined.
+    pub reason: Option<String>,
+
+    /// An optional category, used for automatic approval.
+    pub category: Option<String>,
+
+    /// The schedule of a recurring request.
+    ///
+    /// When a recurring request is granted, a subscription is created.
+    pub schedule: Option<Schedule>,
+
+    /// The timestamp after which a recurring request no longer recurs.
+    pub ends: Option<Timestamp>,
+}
+
+/// Filters applied when loading the request history of a family.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HistoryFilter {
+    /// Only include requests made by this user.
+    pub user_uid: Option<UID>,
+
+    /// Only include requests with this status.
+    pub status: Option<RequestStatus>,
+
+    /// Only include requests made at or after this time.
+    pub since: Option<Timestamp>,
+
+    /// Only include requests made before this time.
+    pub until: Option<Timestamp>,
 }
 
 impl Request {
@@ -39,38 +86,56 @@ impl Request {
     const CREATE_WITH_AUTO_UID: &'static str =
         sql_from_file!("Request.create-with-auto-id");
 
-    /// The SQL statement used to load all requests from a user.
+    /// The SQL statement used to load all pending requests from a user.
     const READ_FOR_USER: &'static str = sql_from_file!("Request.read-for-user");
 
-    /// The SQL statement used to load all requests from members of a family.
+    /// The SQL statement used to load all pending requests from members of a
+    /// family.
     const READ_FOR_FAMILY: &'static str =
         sql_from_file!("Request.read-for-family");
 
+    /// The SQL statement used to load the filtered request history of a
+    /// family.
+    const HISTORY: &'static str = sql_from_file!("Request.history");
+
     /// Creates a transaction in the database, delegating selection of UID.
     ///
     /// # Arguments
     /// *  `tx` - The database transaction.
     /// *  `user_uid` - The user UID.
+    /// *  `ledger_uid` - The ledger UID, or `None` for the monetary ledger.
     /// *  `name` - A short name.
     /// *  `description` - A description.
     /// *  `amount` - The amount. This should generally be a positive value.
     /// *  `url` - An optional URL describing the request.
+    /// *  `category` - An optional category.
+    /// *  `schedule` - The schedule, if this is a recurring request.
+    /// *  `ends` - The end of a recurring request.
     /// *  `time` - The timestamp of the request.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_with_auto_uid<'a>(
         tx: &mut Tx<'a>,
         user_uid: UID,
+        ledger_uid: Option<UID>,
         name: String,
         description: String,
         amount: i64,
         url: Option<URL>,
+        category: Option<String>,
+        schedule: Option<Schedule>,
+        ends: Option<Timestamp>,
         time: Timestamp,
     ) -> Result<Self, DatabaseError> {
         let mut stream = sqlx::query(Self::CREATE_WITH_AUTO_UID)
             .bind(user_uid.clone())
+            .bind(ledger_uid.clone())
             .bind(name.clone())
             .bind(description.clone())
             .bind(amount)
             .bind(url.clone())
+            .bind(category.clone())
+            .bind(schedule.clone())
+            .bind(ends)
             .bind(time)
             .fetch(tx.as_mut());
         while let Some(row) = stream.next().await {
@@ -78,18 +143,27 @@ impl Request {
             return Ok(Self {
                 uid,
                 user_uid,
+                ledger_uid,
                 name,
                 description,
                 amount,
                 url,
                 time,
+                status: RequestStatus::Pending,
+                decided: None,
+                decided_by: None,
+                transaction_uid: None,
+                reason: None,
+                category,
+                schedule,
+                ends,
             });
         }
 
         Err(DatabaseError::RowNotFound)
     }
 
-    /// Loads all requests for a user.
+    /// Loads all pending requests for a user.
     ///
     /// # Arguments
     /// *  `tx` - The database transaction.
@@ -104,7 +178,7 @@ impl Request {
             .await
     }
 
-    /// Loads all requests for a family.
+    /// Loads all pending requests for a family.
     ///
     /// # Arguments
     /// *  `tx` - The database transaction.
@@ -118,6 +192,59 @@ impl Request {
             .fetch_all(tx.as_mut())
             .await
     }
+
+    /// Loads requests of any status for a family, most recent first.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `family_uid` - The family UID.
+    /// *  `filter` - Filters to apply.
+    /// *  `range` - The range of requests to load.
+    pub async fn history<'a>(
+        tx: &mut Tx<'a>,
+        family_uid: &UID,
+        filter: &HistoryFilter,
+        range: Range<usize>,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::HISTORY)
+            .bind(family_uid)
+            .bind(&filter.user_uid)
+            .bind(&filter.user_uid)
+            .bind(filter.status)
+            .bind(filter.status)
+            .bind(filter.since)
+            .bind(filter.since)
+            .bind(filter.until)
+            .bind(filter.until)
+            .bind((range.end - range.start) as u32)
+            .bind(range.start as u32)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Marks this request as decided.
+    ///
+    /// The request is not updated in the database.
+    ///
+    /// # Arguments
+    /// *  `status` - The new status.
+    /// *  `by` - The user deciding the request, or `None` if it was decided
+    ///    automatically.
+    /// *  `transaction_uid` - The transaction generated, if any.
+    pub fn decide(
+        self,
+        status: RequestStatus,
+        by: Option<&UID>,
+        transaction_uid: Option<i64>,
+    ) -> Self {
+        Self {
+            status,
+            decided: Some(Timestamp::now()),
+            decided_by: by.cloned(),
+            transaction_uid,
+            ..self
+        }
+    }
 }
 
 entity_tests! {
@@ -125,11 +252,20 @@ entity_tests! {
         entity: |id| Request {
             uid: id,
             user_uid: UID::new(),
+            ledger_uid: None,
             name: "name".into(),
             description: "description".into(),
             amount: 42,
             url: None,
             time: Timestamp::now(),
+            status: RequestStatus::Pending,
+            decided: None,
+            decided_by: None,
+            transaction_uid: None,
+            reason: None,
+            category: None,
+            schedule: None,
+            ends: None,
         };
         modify: |e| Request {
             description: "another description".into(),
@@ -170,10 +306,14 @@ mod impl_tests {
         let request = Request::create_with_auto_uid(
             &mut tx,
             user.uid.clone(),
+            None,
             "name".into(),
             "description".into(),
             42,
             None,
+            None,
+            Some(Schedule::Monthly(1)),
+            None,
             Timestamp::now(),
         )
         .await
@@ -305,4 +445,96 @@ mod impl_tests {
         assert!(requests.contains(&request2));
         assert!(requests.contains(&request3));
     }
+
+    #[actix_rt::test]
+    async fn history() {
+        use super::*;
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user1 = create::user(
+            &mut conn,
+            Role::Parent,
+            "User 1",
+            "test1@example.com",
+            &family.uid,
+        );
+        let user2 = create::user(
+            &mut conn,
+            Role::Child,
+            "User 2",
+            "test2@example.com",
+            &family.uid,
+        );
+        let request1 = create::request(
+            &mut conn,
+            &user2.uid,
+            "name1",
+            "description1",
+            42,
+            "https://example.com/",
+        );
+        let request2 = create::request(
+            &mut conn,
+            &user2.uid,
+            "name2",
+            "description2",
+            43,
+            "https://example.com/",
+        )
+        .decide(RequestStatus::Declined, Some(&user1.uid), None);
+        request2.update(conn.as_mut()).await.unwrap();
+        let mut tx = conn.begin().await.unwrap();
+
+        assert_eq!(
+            Request::read_for_user(&mut tx, &user2.uid).await.unwrap(),
+            vec![request1.clone()],
+        );
+        assert_eq!(
+            Request::read_for_family(&mut tx, &family.uid)
+                .await
+                .unwrap(),
+            vec![request1.clone()],
+        );
+        let requests = Request::history(
+            &mut tx,
+            &family.uid,
+            &HistoryFilter::default(),
+            0..10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.contains(&request1));
+        assert!(requests.contains(&request2));
+        assert_eq!(
+            Request::history(
+                &mut tx,
+                &family.uid,
+                &HistoryFilter {
+                    user_uid: Some(user2.uid.clone()),
+                    status: Some(RequestStatus::Declined),
+                    ..Default::default()
+                },
+                0..10,
+            )
+            .await
+            .unwrap(),
+            vec![request2.clone()],
+        );
+        assert_eq!(
+            Request::history(
+                &mut tx,
+                &family.uid,
+                &HistoryFilter {
+                    user_uid: Some(user1.uid.clone()),
+                    ..Default::default()
+                },
+                0..10,
+            )
+            .await
+            .unwrap(),
+            Vec::new(),
+        );
+    }
 }
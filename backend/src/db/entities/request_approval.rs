@@ -0,0 +1,75 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::{Timestamp, UID};
+
+/// The approval of a request by a parent.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(RequestApprovals)]
+pub struct RequestApproval {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The request being approved.
+    pub request_uid: i64,
+
+    /// The parent approving the request.
+    pub user_uid: UID,
+
+    /// The timestamp of the approval.
+    pub time: Timestamp,
+
+    /// The approved cost.
+    pub cost: i64,
+
+    /// The approved jar from which to draw the cost.
+    pub jar_uid: Option<UID>,
+}
+
+impl RequestApproval {
+    /// The SQL statement used to load all approvals of a request.
+    const READ_FOR_REQUEST: &'static str =
+        sql_from_file!("RequestApproval.read-for-request");
+
+    /// Loads all approvals of a request, oldest first.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `request_uid` - The request UID.
+    pub async fn read_for_request<'a>(
+        tx: &mut Tx<'a>,
+        request_uid: &i64,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_REQUEST)
+            .bind(request_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    RequestApproval[UID = UID::new()] {
+        entity: |id| RequestApproval {
+            uid: id,
+            request_uid: 1,
+            user_uid: UID::new(),
+            time: Timestamp::now(),
+            cost: 42,
+            jar_uid: None,
+        };
+        modify: |e| RequestApproval {
+            time: Timestamp::now(),
+            ..e
+        };
+        prepare: |tx, e| {
+            let r = crate::db::entities::Request {
+                uid: e.request_uid,
+                user_uid: e.user_uid.clone(),
+                ..crate::db::entities::request::tests::entity()
+            };
+            crate::db::entities::request::tests::prepare(tx, &r).await?;
+            r.create(tx.as_mut()).await
+        };
+    }
+}
@@ -0,0 +1,120 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::{Timestamp, UID};
+
+/// A comment on a request.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(RequestComments)]
+pub struct RequestComment {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The request being commented.
+    pub request_uid: i64,
+
+    /// The user that wrote the comment.
+    pub user_uid: UID,
+
+    /// The comment text.
+    pub text: String,
+
+    /// The timestamp of the comment.
+    pub time: Timestamp,
+}
+
+impl RequestComment {
+    /// The SQL statement used to load all comments on a request.
+    const READ_FOR_REQUEST: &'static str =
+        sql_from_file!("RequestComment.read-for-request");
+
+    /// Loads all comments on a request, oldest first.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `request_uid` - The request UID.
+    pub async fn read_for_request<'a>(
+        tx: &mut Tx<'a>,
+        request_uid: &i64,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_REQUEST)
+            .bind(request_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    RequestComment[UID = UID::new()] {
+        entity: |id| RequestComment {
+            uid: id,
+            request_uid: 1,
+            user_uid: UID::new(),
+            text: "text".into(),
+            time: Timestamp::now(),
+        };
+        modify: |e| RequestComment {
+            text: "another text".into(),
+            ..e
+        };
+        prepare: |tx, e| {
+            let r = crate::db::entities::Request {
+                uid: e.request_uid,
+                user_uid: e.user_uid.clone(),
+                ..crate::db::entities::request::tests::entity()
+            };
+            crate::db::entities::request::tests::prepare(tx, &r).await?;
+            r.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Role;
+
+    #[actix_rt::test]
+    async fn read_for_request() {
+        use super::*;
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User 1",
+            "test1@example.com",
+            &family.uid,
+        );
+        let request1 = create::request(
+            &mut conn,
+            &user.uid,
+            "name1",
+            "description1",
+            42,
+            "https://example.com/",
+        );
+        let request2 = create::request(
+            &mut conn,
+            &user.uid,
+            "name2",
+            "description2",
+            43,
+            "https://example.com/",
+        );
+        let comment1 =
+            create::request_comment(&mut conn, &request1.uid, &user.uid, "a");
+        let comment2 =
+            create::request_comment(&mut conn, &request1.uid, &user.uid, "b");
+        create::request_comment(&mut conn, &request2.uid, &user.uid, "c");
+        let mut tx = conn.begin().await.unwrap();
+
+        let comments = RequestComment::read_for_request(&mut tx, &request1.uid)
+            .await
+            .unwrap();
+        assert_eq!(comments, vec![comment1, comment2]);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::UID;
+
+/// A contribution towards a granted request.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(RequestContributions)]
+pub struct RequestContribution {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The request the contribution was made towards.
+    pub request_uid: i64,
+
+    /// The family member that contributed, or `None` for someone outside the
+    /// family.
+    pub user_uid: Option<UID>,
+
+    /// The name of the contributor.
+    pub name: String,
+
+    /// The amount contributed.
+    pub amount: i64,
+
+    /// The gift transaction booking the contribution.
+    pub transaction_uid: i64,
+}
+
+impl RequestContribution {
+    /// The SQL statement used to load all contributions towards a request.
+    const READ_FOR_REQUEST: &'static str =
+        sql_from_file!("RequestContribution.read-for-request");
+
+    /// Loads all contributions towards a request, largest first.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `request_uid` - The request UID.
+    pub async fn read_for_request<'a>(
+        tx: &mut Tx<'a>,
+        request_uid: &i64,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_REQUEST)
+            .bind(request_uid)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    RequestContribution[UID = UID::new()] {
+        entity: |id| RequestContribution {
+            uid: id,
+            request_uid: 1,
+            user_uid: None,
+            name: "Grandma".into(),
+            amount: 42,
+            transaction_uid: 1,
+        };
+        modify: |e| RequestContribution {
+            amount: 43,
+            ..e
+        };
+        prepare: |tx, e| {
+            let r = crate::db::entities::Request {
+                uid: e.request_uid,
+                ..crate::db::entities::request::tests::entity()
+            };
+            crate::db::entities::request::tests::prepare(tx, &r).await?;
+            r.create(tx.as_mut()).await?;
+            crate::db::entities::Transaction {
+                uid: e.transaction_uid,
+                user_uid: r.user_uid.clone(),
+                ..crate::db::entities::transaction::tests::entity()
+            }
+            .create(tx.as_mut())
+            .await
+        };
+    }
+}
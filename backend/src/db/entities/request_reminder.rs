@@ -0,0 +1,37 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::Timestamp;
+
+/// A reminder sent to parents about a pending request.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(RequestReminders)]
+pub struct RequestReminder {
+    /// The request the parents were reminded of.
+    pub request_uid: i64,
+
+    /// The timestamp of the reminder.
+    pub time: Timestamp,
+}
+
+entity_tests! {
+    RequestReminder[i64 = 1] {
+        entity: |id| RequestReminder {
+            request_uid: id,
+            time: Timestamp::now(),
+        };
+        modify: |e| RequestReminder {
+            time: Timestamp::now(),
+            ..e
+        };
+        prepare: |tx, e| {
+            let r = crate::db::entities::Request {
+                uid: e.request_uid,
+                ..crate::db::entities::request::tests::entity()
+            };
+            crate::db::entities::request::tests::prepare(tx, &r).await?;
+            r.create(tx.as_mut()).await
+        };
+    }
+}
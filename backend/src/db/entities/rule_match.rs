@@ -0,0 +1,71 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::{Timestamp, UID};
+
+/// A link between a matching transaction and the rule that generated it.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(Matches)]
+pub struct RuleMatch {
+    /// The matching transaction.
+    pub transaction_uid: i64,
+
+    /// The rule that generated the transaction.
+    pub rule_uid: UID,
+
+    /// The start of the period that was matched.
+    pub period: Timestamp,
+}
+
+impl RuleMatch {
+    /// The SQL statement used to load the match of a rule for a period.
+    const READ_FOR_RULE: &'static str =
+        sql_from_file!("RuleMatch.read-for-rule");
+
+    /// Loads the match of a rule for a specific period.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `rule_uid` - The rule UID.
+    /// *  `period` - The start of the period.
+    pub async fn read_for_rule<'a>(
+        tx: &mut Tx<'a>,
+        rule_uid: &UID,
+        period: Timestamp,
+    ) -> Result<Option<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_RULE)
+            .bind(rule_uid)
+            .bind(period)
+            .fetch_optional(tx.as_mut())
+            .await
+    }
+}
+
+entity_tests! {
+    RuleMatch[i64 = 1] {
+        entity: |id| RuleMatch {
+            transaction_uid: id,
+            rule_uid: UID::new(),
+            period: Timestamp::now(),
+        };
+        modify: |e| RuleMatch {
+            period: Timestamp::now(),
+            ..e
+        };
+        prepare: |tx, e| {
+            let r = crate::db::entities::match_rule::tests::entity_with_id(
+                e.rule_uid.clone(),
+            );
+            crate::db::entities::match_rule::tests::prepare(tx, &r).await?;
+            r.create(tx.as_mut()).await?;
+            crate::db::entities::Transaction {
+                uid: e.transaction_uid,
+                user_uid: r.user_uid.clone(),
+                ..crate::db::entities::transaction::tests::entity()
+            }
+            .create(tx.as_mut())
+            .await
+        };
+    }
+}
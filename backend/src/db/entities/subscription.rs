@@ -0,0 +1,186 @@
+use crate::prelude::*;
+
+use weru::database::entity;
+
+use crate::db::values::{Schedule, Timestamp, UID};
+
+/// A recurring deduction created from a granted recurring request.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[entity(Subscriptions)]
+pub struct Subscription {
+    /// The unique identifier.
+    pub uid: UID,
+
+    /// The child paying for the subscription.
+    pub user_uid: UID,
+
+    /// The granted request from which the subscription was created.
+    pub request_uid: i64,
+
+    /// The ledger of this subscription, or `None` for the monetary ledger.
+    pub ledger_uid: Option<UID>,
+
+    /// The jar from which deductions are drawn, or `None`.
+    pub jar_uid: Option<UID>,
+
+    /// A short name.
+    pub name: String,
+
+    /// The amount deducted every time.
+    pub amount: i64,
+
+    /// The schedule of the deductions.
+    pub schedule: Schedule,
+
+    /// The timestamp after which no more deductions are made.
+    pub ends: Option<Timestamp>,
+
+    /// Whether the subscription has been paused by a parent.
+    pub paused: bool,
+
+    /// The timestamp when the subscription was cancelled.
+    pub cancelled: Option<Timestamp>,
+
+    /// The timestamp of the latest deduction.
+    pub booked: Option<Timestamp>,
+}
+
+impl Subscription {
+    /// The SQL statement used to load all active subscriptions of a user.
+    const READ_FOR_USER: &'static str =
+        sql_from_file!("Subscription.read-for-user");
+
+    /// The SQL statement used to load all active subscriptions.
+    const READ_ACTIVE: &'static str =
+        sql_from_file!("Subscription.read-active");
+
+    /// Loads all subscriptions of a user that have neither been cancelled nor
+    /// ended.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    /// *  `now` - The current time.
+    pub async fn read_for_user<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+        now: Timestamp,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_FOR_USER)
+            .bind(user_uid)
+            .bind(now)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Loads all subscriptions that have neither been cancelled nor ended.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `now` - The current time.
+    pub async fn read_active<'a>(
+        tx: &mut Tx<'a>,
+        now: Timestamp,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        sqlx::query_as(Self::READ_ACTIVE)
+            .bind(now)
+            .fetch_all(tx.as_mut())
+            .await
+    }
+
+    /// Whether this subscription is active at a specific time.
+    ///
+    /// # Arguments
+    /// *  `now` - The time to check.
+    pub fn is_active(&self, now: Timestamp) -> bool {
+        self.cancelled.is_none()
+            && self.ends.map(|ends| ends.0 > now.0).unwrap_or(true)
+    }
+
+    /// Whether a deduction should be booked at a specific time.
+    ///
+    /// A deduction is booked at most once per day, and never for paused
+    /// subscriptions.
+    ///
+    /// # Arguments
+    /// *  `now` - The time to check.
+    pub fn is_due(&self, now: Timestamp) -> bool {
+        let date = now.0.date_naive();
+        self.is_active(now)
+            && !self.paused
+            && self.schedule.is_due(date)
+            && self
+                .booked
+                .map(|booked| booked.0.date_naive() != date)
+                .unwrap_or(true)
+    }
+}
+
+entity_tests! {
+    Subscription[UID = UID::new()] {
+        entity: |id| Subscription {
+            uid: id,
+            user_uid: UID::new(),
+            request_uid: 1,
+            ledger_uid: None,
+            jar_uid: None,
+            name: "name".into(),
+            amount: 42,
+            schedule: Schedule::Monthly(1),
+            ends: None,
+            paused: false,
+            cancelled: None,
+            booked: None,
+        };
+        modify: |e| Subscription {
+            paused: true,
+            ..e
+        };
+        prepare: |tx, e| {
+            let r = crate::db::entities::Request {
+                uid: e.request_uid,
+                user_uid: e.user_uid.clone(),
+                ..crate::db::entities::request::tests::entity()
+            };
+            crate::db::entities::request::tests::prepare(tx, &r).await?;
+            r.create(tx.as_mut()).await
+        };
+    }
+}
+
+#[cfg(test)]
+mod impl_tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    #[test]
+    fn is_due() {
+        let at = |s| -> Timestamp {
+            DateTime::parse_from_rfc3339(s).unwrap().into()
+        };
+        let subscription = tests::entity();
+        assert!(subscription.is_due(at("2024-03-01T12:00:00Z")));
+        assert!(!subscription.is_due(at("2024-03-02T12:00:00Z")));
+        assert!(!Subscription {
+            paused: true,
+            ..subscription.clone()
+        }
+        .is_due(at("2024-03-01T12:00:00Z")));
+        assert!(!Subscription {
+            cancelled: Some(at("2024-02-15T12:00:00Z")),
+            ..subscription.clone()
+        }
+        .is_due(at("2024-03-01T12:00:00Z")));
+        assert!(!Subscription {
+            ends: Some(at("2024-02-15T12:00:00Z")),
+            ..subscription.clone()
+        }
+        .is_due(at("2024-03-01T12:00:00Z")));
+        assert!(!Subscription {
+            booked: Some(at("2024-03-01T01:00:00Z")),
+            ..subscription.clone()
+        }
+        .is_due(at("2024-03-01T12:00:00Z")));
+    }
+}
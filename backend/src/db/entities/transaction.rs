@@ -1,10 +1,12 @@
 use crate::prelude::*;
 
+use std::collections::HashMap;
 use std::ops::Range;
 
 use weru::database::entity;
 use weru::futures::StreamExt;
 
+use crate::db::entities::Jar;
 use crate::db::values::{Timestamp, TransactionType, UID};
 
 /// A description of a transaction.
@@ -23,6 +25,13 @@ pub struct Transaction {
     /// The user involved in this transaction.
     pub user_uid: UID,
 
+    /// The ledger of this transaction, or `None` for the monetary ledger.
+    pub ledger_uid: Option<UID>,
+
+    /// The jar of this transaction, or `None` if it is not allocated to a
+    /// jar.
+    pub jar_uid: Option<UID>,
+
     /// A description.
     pub description: String,
 
@@ -45,19 +54,49 @@ impl Transaction {
     /// The SQL statement used to load the balace for a user.
     const BALANCE: &'static str = sql_from_file!("Transaction.balance");
 
+    /// The SQL statement used to load the balances of all non-monetary
+    /// ledgers for a user.
+    const LEDGER_BALANCES: &'static str =
+        sql_from_file!("Transaction.ledger-balances");
+
+    /// The SQL statement used to load the balances of all jars for a user.
+    const JAR_BALANCES: &'static str =
+        sql_from_file!("Transaction.jar-balances");
+
+    /// The SQL statement used to load the balance for a user at a point in
+    /// time.
+    const BALANCE_BEFORE: &'static str =
+        sql_from_file!("Transaction.balance-before");
+
+    /// The SQL statement used to load the total deposits for a user.
+    const DEPOSITS: &'static str = sql_from_file!("Transaction.deposits");
+
+    /// The SQL statement used to load the total spending for a user.
+    const SPENDING: &'static str = sql_from_file!("Transaction.spending");
+
+    /// The SQL statement used to load the total amount automatically approved
+    /// for a user.
+    const AUTO_APPROVED: &'static str =
+        sql_from_file!("Transaction.auto-approved");
+
     /// Creates a transaction in the database, delegating selection of UID.
     ///
     /// # Arguments
     /// *  `tx` - The database transaction.
     /// *  `transaction_type` - The transaction type.
     /// *  `user_uid` - The user UID.
+    /// *  `ledger_uid` - The ledger UID, or `None` for the monetary ledger.
+    /// *  `jar_uid` - The jar UID, or `None` for no jar.
     /// *  `description` - A description.
     /// *  `amount` - The amount. Negative amounts are withdrawals.
     /// *  `time` - The timestamp of the transaction.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_with_auto_uid<'a>(
         tx: &mut Tx<'a>,
         transaction_type: TransactionType,
         user_uid: UID,
+        ledger_uid: Option<UID>,
+        jar_uid: Option<UID>,
         description: String,
         amount: i64,
         time: Timestamp,
@@ -65,6 +104,8 @@ impl Transaction {
         let mut stream = sqlx::query(Self::CREATE_WITH_AUTO_UID)
             .bind(transaction_type)
             .bind(user_uid.clone())
+            .bind(ledger_uid.clone())
+            .bind(jar_uid.clone())
             .bind(description.clone())
             .bind(amount)
             .bind(time)
@@ -75,6 +116,8 @@ impl Transaction {
                 uid,
                 transaction_type,
                 user_uid,
+                ledger_uid,
+                jar_uid,
                 description,
                 amount,
                 time,
@@ -84,6 +127,46 @@ impl Transaction {
         Err(DatabaseError::RowNotFound)
     }
 
+    /// Creates monetary transactions for an amount split between the jars of
+    /// a user, delegating selection of UIDs.
+    ///
+    /// If the user has no jars, a single unallocated transaction is created.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `transaction_type` - The transaction type.
+    /// *  `user_uid` - The user UID.
+    /// *  `description` - A description.
+    /// *  `amount` - The amount to split.
+    /// *  `time` - The timestamp of the transactions.
+    pub async fn create_split_with_auto_uid<'a>(
+        tx: &mut Tx<'a>,
+        transaction_type: TransactionType,
+        user_uid: UID,
+        description: String,
+        amount: i64,
+        time: Timestamp,
+    ) -> Result<Vec<Self>, DatabaseError> {
+        let jars = Jar::read_for_user(tx, &user_uid).await?;
+        let mut result = Vec::new();
+        for (jar_uid, amount) in Jar::split(&jars, amount) {
+            result.push(
+                Self::create_with_auto_uid(
+                    tx,
+                    transaction_type,
+                    user_uid.clone(),
+                    None,
+                    jar_uid,
+                    description.clone(),
+                    amount,
+                    time,
+                )
+                .await?,
+            );
+        }
+        Ok(result)
+    }
+
     /// Loads transactions for a user.
     ///
     /// # Arguments
@@ -106,21 +189,157 @@ impl Transaction {
             .collect())
     }
 
-    /// Loads the balance for a user.
+    /// Loads the balance of a ledger for a user.
     ///
     /// # Arguments
     /// *  `tx` - The database transaction.
     /// *  `user_uid` - The user UID.
+    /// *  `ledger_uid` - The ledger UID, or `None` for the monetary ledger.
     pub async fn balance<'a>(
         tx: &mut Tx<'a>,
         user_uid: &UID,
+        ledger_uid: Option<&UID>,
     ) -> Result<Option<i64>, DatabaseError> {
         Ok(sqlx::query(Self::BALANCE)
             .bind(user_uid)
+            .bind(ledger_uid)
             .fetch_optional(tx.as_mut())
             .await?
             .map(|r| r.get(0)))
     }
+
+    /// Loads the balances of all non-monetary ledgers for a user.
+    ///
+    /// Ledgers without any transactions are not included.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    pub async fn ledger_balances<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+    ) -> Result<HashMap<UID, i64>, DatabaseError> {
+        Ok(sqlx::query(Self::LEDGER_BALANCES)
+            .bind(user_uid)
+            .fetch_all(tx.as_mut())
+            .await?
+            .into_iter()
+            .map(|r| (r.get(0), r.get(1)))
+            .collect())
+    }
+
+    /// Loads the balances of all jars for a user.
+    ///
+    /// These are the parts of the monetary balance allocated to jars. Jars
+    /// without any transactions are not included.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    pub async fn jar_balances<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+    ) -> Result<HashMap<UID, i64>, DatabaseError> {
+        Ok(sqlx::query(Self::JAR_BALANCES)
+            .bind(user_uid)
+            .fetch_all(tx.as_mut())
+            .await?
+            .into_iter()
+            .map(|r| (r.get(0), r.get(1)))
+            .collect())
+    }
+
+    /// Loads the monetary balance for a user at a point in time.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    /// *  `jar_uid` - A jar UID to only include money in that jar.
+    /// *  `before` - The point in time. Transactions at or after this are not
+    ///    included.
+    pub async fn balance_before<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+        jar_uid: Option<&UID>,
+        before: Timestamp,
+    ) -> Result<i64, DatabaseError> {
+        Ok(sqlx::query(Self::BALANCE_BEFORE)
+            .bind(user_uid)
+            .bind(jar_uid)
+            .bind(jar_uid)
+            .bind(before)
+            .fetch_one(tx.as_mut())
+            .await?
+            .get(0))
+    }
+
+    /// Loads the total monetary deposits for a user during a time span.
+    ///
+    /// Matching contributions are not considered deposits.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    /// *  `jar_uid` - A jar UID to only include deposits into that jar.
+    /// *  `range` - The time span.
+    pub async fn deposits<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+        jar_uid: Option<&UID>,
+        range: Range<Timestamp>,
+    ) -> Result<i64, DatabaseError> {
+        Ok(sqlx::query(Self::DEPOSITS)
+            .bind(user_uid)
+            .bind(jar_uid)
+            .bind(jar_uid)
+            .bind(TransactionType::Match)
+            .bind(range.start)
+            .bind(range.end)
+            .fetch_one(tx.as_mut())
+            .await?
+            .get(0))
+    }
+
+    /// Loads the total monetary spending for a user since a point in time.
+    ///
+    /// The spending is the sum of all withdrawals, as a positive value.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    /// *  `since` - The earliest timestamp to consider.
+    pub async fn spending<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+        since: Timestamp,
+    ) -> Result<i64, DatabaseError> {
+        Ok(sqlx::query(Self::SPENDING)
+            .bind(user_uid)
+            .bind(since)
+            .fetch_one(tx.as_mut())
+            .await?
+            .get(0))
+    }
+
+    /// Loads the total amount of requests automatically approved for a user
+    /// since a point in time, as a positive value.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `user_uid` - The user UID.
+    /// *  `since` - The earliest timestamp to consider.
+    pub async fn auto_approved<'a>(
+        tx: &mut Tx<'a>,
+        user_uid: &UID,
+        since: Timestamp,
+    ) -> Result<i64, DatabaseError> {
+        Ok(sqlx::query(Self::AUTO_APPROVED)
+            .bind(user_uid)
+            .bind(since)
+            .fetch_one(tx.as_mut())
+            .await?
+            .get(0))
+    }
 }
 
 entity_tests! {
@@ -129,6 +348,8 @@ entity_tests! {
             uid: id,
             transaction_type: TransactionType::Gift,
             user_uid: UID::new(),
+            ledger_uid: None,
+            jar_uid: None,
             description: "description".into(),
             amount: 42,
             time: Timestamp::now(),
@@ -177,6 +398,8 @@ mod impl_tests {
             &mut tx,
             TransactionType::Gift,
             user.uid.clone(),
+            None,
+            None,
             "description".into(),
             42,
             Timestamp::now(),
@@ -308,8 +531,184 @@ mod impl_tests {
         let mut tx = conn.begin().await.unwrap();
 
         assert_eq!(
-            Transaction::balance(&mut tx, &user1.uid).await.unwrap(),
+            Transaction::balance(&mut tx, &user1.uid, None)
+                .await
+                .unwrap(),
             Some((0..40).filter(|i| i & 1 != 0).map(|i| (i + 1) * 3).sum()),
         );
     }
+
+    #[actix_rt::test]
+    async fn ledger_balances() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let ledger1 =
+            create::ledger(&mut conn, &family.uid, "Screen time", "minutes");
+        let ledger2 = create::ledger(&mut conn, &family.uid, "Stars", "stars");
+        create::ledger(&mut conn, &family.uid, "Unused", "units");
+
+        create::transaction(
+            &mut conn,
+            TransactionType::Gift,
+            &user.uid,
+            "money",
+            100,
+            Timestamp::now(),
+        );
+        for (ledger, amount) in
+            [(&ledger1, 30), (&ledger1, -10), (&ledger2, 5)].iter()
+        {
+            create::ledger_transaction(
+                &mut conn,
+                TransactionType::Gift,
+                &user.uid,
+                &ledger.uid,
+                "points",
+                *amount,
+                Timestamp::now(),
+            );
+        }
+        let mut tx = conn.begin().await.unwrap();
+
+        assert_eq!(
+            Transaction::balance(&mut tx, &user.uid, None)
+                .await
+                .unwrap(),
+            Some(100),
+        );
+        assert_eq!(
+            Transaction::balance(&mut tx, &user.uid, Some(&ledger1.uid))
+                .await
+                .unwrap(),
+            Some(20),
+        );
+        assert_eq!(
+            Transaction::ledger_balances(&mut tx, &user.uid)
+                .await
+                .unwrap(),
+            [(ledger1.uid.clone(), 20), (ledger2.uid.clone(), 5)]
+                .iter()
+                .cloned()
+                .collect(),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn spending() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let ledger = create::ledger(&mut conn, &family.uid, "Stars", "stars");
+        let now = Timestamp::now();
+        let before: Timestamp = now
+            .0
+            .checked_sub_signed(chrono::Duration::days(10))
+            .unwrap()
+            .into();
+        let since: Timestamp = now
+            .0
+            .checked_sub_signed(chrono::Duration::days(5))
+            .unwrap()
+            .into();
+
+        create::transaction(
+            &mut conn,
+            TransactionType::Request,
+            &user.uid,
+            "old",
+            -100,
+            before,
+        );
+        create::transaction(
+            &mut conn,
+            TransactionType::Request,
+            &user.uid,
+            "new",
+            -30,
+            now,
+        );
+        create::transaction(
+            &mut conn,
+            TransactionType::Gift,
+            &user.uid,
+            "gift",
+            50,
+            now,
+        );
+        create::ledger_transaction(
+            &mut conn,
+            TransactionType::Request,
+            &user.uid,
+            &ledger.uid,
+            "stars",
+            -5,
+            now,
+        );
+        let mut tx = conn.begin().await.unwrap();
+
+        assert_eq!(
+            Transaction::spending(&mut tx, &user.uid, since)
+                .await
+                .unwrap(),
+            30,
+        );
+    }
+
+    #[actix_rt::test]
+    async fn create_split_with_auto_uid() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let spend = create::jar(&mut conn, &user.uid, "spend", 70);
+        let save = create::jar(&mut conn, &user.uid, "save", 30);
+        let mut tx = conn.begin().await.unwrap();
+
+        let transactions = Transaction::create_split_with_auto_uid(
+            &mut tx,
+            TransactionType::Gift,
+            user.uid.clone(),
+            "description".into(),
+            100,
+            Timestamp::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        assert_eq!(
+            Transaction::balance(&mut tx, &user.uid, None)
+                .await
+                .unwrap(),
+            Some(100),
+        );
+        assert_eq!(
+            Transaction::jar_balances(&mut tx, &user.uid).await.unwrap(),
+            [(spend.uid.clone(), 70), (save.uid.clone(), 30)]
+                .iter()
+                .cloned()
+                .collect(),
+        );
+    }
 }
@@ -2,7 +2,7 @@ use crate::prelude::*;
 
 use weru::database::{entity, sqlx};
 
-use crate::db::values::{EmailAddress, Role, UID};
+use crate::db::values::{Date, EmailAddress, Role, UID};
 
 /// A description of a user.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
@@ -22,6 +22,9 @@ pub struct User {
 
     /// The unique identifier of the family.
     pub family_uid: UID,
+
+    /// The birth date of this user, if known.
+    pub birth_date: Option<Date>,
 }
 
 impl User {
@@ -52,9 +55,11 @@ entity_tests! {
             name: "Test User".into(),
             email: None,
             family_uid: UID::new(),
+            birth_date: None,
         };
         modify: |e| User {
             name: "New Test User".into(),
+            birth_date: Some("2015-06-03".parse().unwrap()),
             ..e
         };
         prepare: |tx, e| {
@@ -0,0 +1,106 @@
+/**
+ * The number of digits of the minor unit of a currency.
+ *
+ * All amounts are stored in the minor unit.
+ */
+ALTER TABLE Currencies ADD COLUMN exponent INTEGER NOT NULL DEFAULT 0;
+
+/**
+ * The symbol separating the major and minor units.
+ */
+ALTER TABLE Currencies ADD COLUMN decimal_separator TEXT NOT NULL DEFAULT '.';
+
+/**
+ * The symbol separating groups of thousands, or empty for no grouping.
+ */
+ALTER TABLE Currencies ADD COLUMN grouping_separator TEXT NOT NULL DEFAULT '';
+
+/**
+ * The way negative values are presented.
+ *
+ * This is represented by the type db::values::NegativeStyle.
+ */
+ALTER TABLE Currencies ADD COLUMN negative_style TEXT NOT NULL
+    DEFAULT 'leading';
+
+/**
+ * The ISO 4217 currencies with a minor unit.
+ */
+UPDATE Currencies
+SET exponent = 2
+WHERE format = '{} ' || name AND name NOT IN (
+    'BHD', 'BIF', 'CLP', 'DJF', 'GNF', 'IQD', 'ISK', 'JOD', 'JPY', 'KMF',
+    'KRW', 'KWD', 'LYD', 'OMR', 'PYG', 'RWF', 'TND', 'UGX', 'VND', 'VUV',
+    'XAF', 'XOF', 'XPF'
+);
+UPDATE Currencies
+SET exponent = 3
+WHERE format = '{} ' || name AND name IN (
+    'BHD', 'IQD', 'JOD', 'KWD', 'LYD', 'OMR', 'TND'
+);
+
+/**
+ * Amounts used to be stored in the major unit, so the stored amounts of
+ * families using a currency with a minor unit are rescaled.
+ *
+ * Families without a configuration use the default currency of the server,
+ * whose exponent defaults to 0, so their amounts are unchanged.
+ */
+UPDATE Transactions
+SET amount = amount * (
+    SELECT CASE Currencies.exponent WHEN 2 THEN 100 WHEN 3 THEN 1000 END
+    FROM Users
+    JOIN Configurations ON Configurations.family_uid = Users.family_uid
+    JOIN Currencies ON Currencies.name = Configurations.currency
+    WHERE Users.uid = Transactions.user_uid
+)
+WHERE user_uid IN (
+    SELECT Users.uid
+    FROM Users
+    JOIN Configurations ON Configurations.family_uid = Users.family_uid
+    JOIN Currencies ON Currencies.name = Configurations.currency
+    WHERE Currencies.exponent > 0
+);
+UPDATE Requests
+SET amount = amount * (
+    SELECT CASE Currencies.exponent WHEN 2 THEN 100 WHEN 3 THEN 1000 END
+    FROM Users
+    JOIN Configurations ON Configurations.family_uid = Users.family_uid
+    JOIN Currencies ON Currencies.name = Configurations.currency
+    WHERE Users.uid = Requests.user_uid
+)
+WHERE user_uid IN (
+    SELECT Users.uid
+    FROM Users
+    JOIN Configurations ON Configurations.family_uid = Users.family_uid
+    JOIN Currencies ON Currencies.name = Configurations.currency
+    WHERE Currencies.exponent > 0
+);
+UPDATE Allowances
+SET amount = amount * (
+    SELECT CASE Currencies.exponent WHEN 2 THEN 100 WHEN 3 THEN 1000 END
+    FROM Users
+    JOIN Configurations ON Configurations.family_uid = Users.family_uid
+    JOIN Currencies ON Currencies.name = Configurations.currency
+    WHERE Users.uid = Allowances.user_uid
+)
+WHERE user_uid IN (
+    SELECT Users.uid
+    FROM Users
+    JOIN Configurations ON Configurations.family_uid = Users.family_uid
+    JOIN Currencies ON Currencies.name = Configurations.currency
+    WHERE Currencies.exponent > 0
+);
+UPDATE Invitations
+SET allowance_amount = allowance_amount * (
+    SELECT CASE Currencies.exponent WHEN 2 THEN 100 WHEN 3 THEN 1000 END
+    FROM Configurations
+    JOIN Currencies ON Currencies.name = Configurations.currency
+    WHERE Configurations.family_uid = Invitations.family_uid
+)
+WHERE allowance_amount IS NOT NULL AND family_uid IN (
+    SELECT Configurations.family_uid
+    FROM Configurations
+    JOIN Currencies ON Currencies.name = Configurations.currency
+    WHERE Currencies.exponent > 0
+);
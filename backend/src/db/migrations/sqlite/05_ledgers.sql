@@ -0,0 +1,51 @@
+/**
+ * Additional non-monetary ledgers, such as screen time minutes or reward
+ * points.
+ *
+ * The monetary ledger is implicit, and is referenced by a NULL ledger.
+ */
+CREATE TABLE Ledgers (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The unique ID of the family.
+     */
+    family_uid TEXT NOT NULL,
+
+    /**
+     * The display name.
+     */
+    name TEXT NOT NULL,
+
+    /**
+     * The unit of values in this ledger.
+     */
+    unit TEXT NOT NULL,
+
+    FOREIGN KEY (family_uid)
+        REFERENCES Families (uid)
+        ON DELETE CASCADE
+);
+
+/**
+ * The ledger of a transaction, or NULL for the monetary ledger.
+ */
+ALTER TABLE Transactions ADD COLUMN ledger_uid TEXT
+    REFERENCES Ledgers (uid) ON DELETE CASCADE;
+
+/**
+ * The ledger of a request, or NULL for the monetary ledger.
+ */
+ALTER TABLE Requests ADD COLUMN ledger_uid TEXT
+    REFERENCES Ledgers (uid) ON DELETE CASCADE;
+
+/**
+ * The ledger of an allowance, or NULL for the monetary ledger.
+ */
+ALTER TABLE Allowances ADD COLUMN ledger_uid TEXT
+    REFERENCES Ledgers (uid) ON DELETE CASCADE;
@@ -0,0 +1,50 @@
+/**
+ * Savings goals for children.
+ */
+CREATE TABLE Goals (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The user saving towards this goal.
+     */
+    user_uid TEXT NOT NULL,
+
+    /**
+     * A short name.
+     */
+    name TEXT NOT NULL,
+
+    /**
+     * The target amount.
+     */
+    amount INTEGER NOT NULL,
+
+    /**
+     * An optional associated URL.
+     */
+    url TEXT,
+
+    /**
+     * An optional deadline.
+     */
+    deadline DATETIME,
+
+    /**
+     * The part of the balance reserved for this goal.
+     */
+    earmarked INTEGER NOT NULL DEFAULT 0,
+
+    /**
+     * The timestamp when this goal was completed, or NULL if it is active.
+     */
+    completed DATETIME,
+
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE
+);
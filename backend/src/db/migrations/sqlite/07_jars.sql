@@ -0,0 +1,37 @@
+/**
+ * Named sub-accounts, such as "spend", "save" and "give", between which
+ * incoming money is split.
+ */
+CREATE TABLE Jars (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The user owning this jar.
+     */
+    user_uid TEXT NOT NULL,
+
+    /**
+     * The display name.
+     */
+    name TEXT NOT NULL,
+
+    /**
+     * The percentage of incoming money put in this jar.
+     */
+    percentage INTEGER NOT NULL,
+
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE
+);
+
+/**
+ * The jar of a transaction, or NULL if it is not allocated to a jar.
+ */
+ALTER TABLE Transactions ADD COLUMN jar_uid TEXT
+    REFERENCES Jars (uid) ON DELETE SET NULL;
@@ -0,0 +1,103 @@
+/**
+ * Rules for parent contributions matching the savings of a child.
+ */
+CREATE TABLE MatchRules (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The user whose savings are matched.
+     */
+    user_uid TEXT NOT NULL,
+
+    /**
+     * A short name, used as description of matching transactions.
+     */
+    name TEXT NOT NULL,
+
+    /**
+     * What is matched.
+     *
+     * This is represented by the type db::values::MatchKind.
+     */
+    kind TEXT NOT NULL,
+
+    /**
+     * The amount required for a contribution.
+     */
+    per INTEGER NOT NULL,
+
+    /**
+     * The amount contributed for every whole multiple of per.
+     */
+    matched INTEGER NOT NULL,
+
+    /**
+     * The maximum contribution per period, or NULL for no limit.
+     */
+    cap INTEGER,
+
+    /**
+     * The period over which the rule is evaluated.
+     *
+     * This is represented by the type db::values::Period.
+     */
+    period TEXT NOT NULL,
+
+    /**
+     * The jar whose money is matched and which receives contributions, or
+     * NULL for the entire balance.
+     */
+    jar_uid TEXT,
+
+    /**
+     * A goal for which contributions are earmarked, or NULL.
+     */
+    goal_uid TEXT,
+
+    /**
+     * The timestamp when this rule was created.
+     */
+    created DATETIME NOT NULL,
+
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (jar_uid)
+        REFERENCES Jars (uid)
+        ON DELETE SET NULL,
+    FOREIGN KEY (goal_uid)
+        REFERENCES Goals (uid)
+        ON DELETE SET NULL
+);
+
+/**
+ * Links between matching transactions and the rules that generated them.
+ */
+CREATE TABLE Matches (
+    /**
+     * The matching transaction.
+     */
+    transaction_uid INTEGER PRIMARY KEY UNIQUE,
+
+    /**
+     * The rule that generated the transaction.
+     */
+    rule_uid TEXT NOT NULL,
+
+    /**
+     * The start of the period that was matched.
+     */
+    period DATETIME NOT NULL,
+
+    FOREIGN KEY (transaction_uid)
+        REFERENCES Transactions (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (rule_uid)
+        REFERENCES MatchRules (uid)
+        ON DELETE CASCADE
+);
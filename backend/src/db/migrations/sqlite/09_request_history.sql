@@ -0,0 +1,23 @@
+/**
+ * The status of a request.
+ *
+ * This is represented by the type db::values::RequestStatus.
+ */
+ALTER TABLE Requests ADD COLUMN status TEXT NOT NULL DEFAULT 'pending';
+
+/**
+ * The timestamp when the request was decided, or NULL if it is pending.
+ */
+ALTER TABLE Requests ADD COLUMN decided DATETIME;
+
+/**
+ * The user that decided the request, or NULL if it is pending.
+ */
+ALTER TABLE Requests ADD COLUMN decided_by TEXT
+    REFERENCES Users (uid) ON DELETE SET NULL;
+
+/**
+ * The transaction generated when the request was granted, or NULL.
+ */
+ALTER TABLE Requests ADD COLUMN transaction_uid INTEGER
+    REFERENCES Transactions (uid) ON DELETE SET NULL;
@@ -0,0 +1,43 @@
+/**
+ * The reason given when a request was declined, or NULL.
+ */
+ALTER TABLE Requests ADD COLUMN reason TEXT;
+
+/**
+ * Comments on requests, used to discuss them before they are decided.
+ */
+CREATE TABLE RequestComments (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The request being commented.
+     */
+    request_uid INTEGER NOT NULL,
+
+    /**
+     * The user that wrote the comment.
+     */
+    user_uid TEXT NOT NULL,
+
+    /**
+     * The comment text.
+     */
+    text TEXT NOT NULL,
+
+    /**
+     * The timestamp of the comment.
+     */
+    time DATETIME NOT NULL,
+
+    FOREIGN KEY (request_uid)
+        REFERENCES Requests (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,47 @@
+/**
+ * Contributions towards granted requests, made by parents or relatives.
+ */
+CREATE TABLE RequestContributions (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The request the contribution was made towards.
+     */
+    request_uid INTEGER NOT NULL,
+
+    /**
+     * The family member that contributed, or NULL for someone outside the
+     * family.
+     */
+    user_uid TEXT,
+
+    /**
+     * The name of the contributor.
+     */
+    name TEXT NOT NULL,
+
+    /**
+     * The amount contributed.
+     */
+    amount INTEGER NOT NULL,
+
+    /**
+     * The gift transaction booking the contribution.
+     */
+    transaction_uid INTEGER NOT NULL,
+
+    FOREIGN KEY (request_uid)
+        REFERENCES Requests (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE SET NULL,
+    FOREIGN KEY (transaction_uid)
+        REFERENCES Transactions (uid)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,115 @@
+/**
+ * Per-family settings.
+ *
+ * Families without a row use the default settings.
+ */
+CREATE TABLE FamilySettings (
+    /**
+     * The unique ID of the family.
+     */
+    family_uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The amount from which requests require approval by several parents,
+     * or NULL if a single approval always suffices.
+     */
+    approval_threshold INTEGER,
+
+    /**
+     * The number of distinct parents required to approve requests above the
+     * threshold.
+     */
+    approvals_required INTEGER NOT NULL DEFAULT 1,
+
+    FOREIGN KEY (family_uid)
+        REFERENCES Families (uid)
+        ON DELETE CASCADE
+);
+
+/**
+ * Approvals of requests by parents.
+ */
+CREATE TABLE RequestApprovals (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The request being approved.
+     */
+    request_uid INTEGER NOT NULL,
+
+    /**
+     * The parent approving the request.
+     */
+    user_uid TEXT NOT NULL,
+
+    /**
+     * The timestamp of the approval.
+     */
+    time DATETIME NOT NULL,
+
+    /**
+     * The approved cost.
+     */
+    cost INTEGER NOT NULL,
+
+    /**
+     * The approved jar from which to draw the cost, or NULL.
+     */
+    jar_uid TEXT,
+
+    UNIQUE (request_uid, user_uid),
+    FOREIGN KEY (request_uid)
+        REFERENCES Requests (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (jar_uid)
+        REFERENCES Jars (uid)
+        ON DELETE SET NULL
+);
+
+/**
+ * The contributions towards the cost of requests approved by parents.
+ */
+CREATE TABLE ApprovedContributions (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The approval including the contribution.
+     */
+    approval_uid TEXT NOT NULL,
+
+    /**
+     * The family member contributing, or NULL for someone outside the
+     * family.
+     */
+    user_uid TEXT,
+
+    /**
+     * The name of the contributor.
+     */
+    name TEXT NOT NULL,
+
+    /**
+     * The amount contributed.
+     */
+    amount INTEGER NOT NULL,
+
+    FOREIGN KEY (approval_uid)
+        REFERENCES RequestApprovals (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE SET NULL
+);
@@ -0,0 +1,44 @@
+/**
+ * The category of a request, or NULL.
+ */
+ALTER TABLE Requests ADD COLUMN category TEXT;
+
+/**
+ * Rules for automatically granting small requests made by a child.
+ */
+CREATE TABLE AutoApprovals (
+    /**
+     * The unique ID of the child whose requests are granted.
+     */
+    user_uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The maximum amount of a request granted automatically.
+     */
+    max_amount INTEGER NOT NULL,
+
+    /**
+     * A comma separated list of categories that may be granted
+     * automatically, or the empty string to allow all categories.
+     *
+     * This is represented by the type db::values::Categories.
+     */
+    categories TEXT NOT NULL,
+
+    /**
+     * The maximum total amount granted automatically per period, or NULL for
+     * no limit.
+     */
+    budget INTEGER,
+
+    /**
+     * The period over which the budget applies.
+     *
+     * This is represented by the type db::values::Period.
+     */
+    period TEXT NOT NULL,
+
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,32 @@
+/**
+ * The number of days after which parents are reminded of a pending request,
+ * or NULL if no reminders are sent.
+ */
+ALTER TABLE FamilySettings ADD COLUMN request_reminder INTEGER;
+
+/**
+ * The number of days after which a pending request expires, or NULL if
+ * requests never expire.
+ */
+ALTER TABLE FamilySettings ADD COLUMN request_expiry INTEGER;
+
+/**
+ * Reminders sent to parents about pending requests.
+ *
+ * A reminder is sent at most once per request.
+ */
+CREATE TABLE RequestReminders (
+    /**
+     * The request the parents were reminded of.
+     */
+    request_uid INTEGER PRIMARY KEY UNIQUE,
+
+    /**
+     * The timestamp of the reminder.
+     */
+    time DATETIME NOT NULL,
+
+    FOREIGN KEY (request_uid)
+        REFERENCES Requests (uid)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,93 @@
+/**
+ * The schedule of a recurring request, or NULL for one-off requests.
+ *
+ * This is represented by the type db::values::Schedule.
+ */
+ALTER TABLE Requests ADD COLUMN schedule TEXT;
+
+/**
+ * The timestamp after which a recurring request no longer recurs, or NULL.
+ */
+ALTER TABLE Requests ADD COLUMN ends DATETIME;
+
+/**
+ * Recurring deductions created from granted recurring requests.
+ */
+CREATE TABLE Subscriptions (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The child paying for the subscription.
+     */
+    user_uid TEXT NOT NULL,
+
+    /**
+     * The granted request from which the subscription was created.
+     */
+    request_uid INTEGER NOT NULL,
+
+    /**
+     * The ledger of the subscription, or NULL for the monetary ledger.
+     */
+    ledger_uid TEXT,
+
+    /**
+     * The jar from which deductions are drawn, or NULL.
+     */
+    jar_uid TEXT,
+
+    /**
+     * A short name.
+     */
+    name TEXT NOT NULL,
+
+    /**
+     * The amount deducted every time.
+     */
+    amount INTEGER NOT NULL,
+
+    /**
+     * The schedule of the deductions.
+     *
+     * This is represented by the type db::values::Schedule.
+     */
+    schedule TEXT NOT NULL,
+
+    /**
+     * The timestamp after which no more deductions are made, or NULL.
+     */
+    ends DATETIME,
+
+    /**
+     * Whether the subscription has been paused by a parent.
+     */
+    paused BOOLEAN NOT NULL DEFAULT 0,
+
+    /**
+     * The timestamp when the subscription was cancelled, or NULL.
+     */
+    cancelled DATETIME,
+
+    /**
+     * The timestamp of the latest deduction, or NULL.
+     */
+    booked DATETIME,
+
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (request_uid)
+        REFERENCES Requests (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (ledger_uid)
+        REFERENCES Ledgers (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (jar_uid)
+        REFERENCES Jars (uid)
+        ON DELETE SET NULL
+);
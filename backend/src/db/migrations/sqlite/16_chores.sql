@@ -0,0 +1,77 @@
+/**
+ * Chores that children can do for a reward.
+ */
+CREATE TABLE Chores (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The family of the chore.
+     */
+    family_uid TEXT NOT NULL,
+
+    /**
+     * A short title.
+     */
+    title TEXT NOT NULL,
+
+    /**
+     * The reward paid when the chore is approved.
+     */
+    reward INTEGER NOT NULL,
+
+    /**
+     * The timestamp when the chore is due, or NULL.
+     */
+    due DATETIME,
+
+    /**
+     * The child the chore is assigned to, or NULL if it is open to all
+     * children.
+     */
+    assignee_uid TEXT,
+
+    /**
+     * The child that claimed the chore, or NULL.
+     */
+    claimed_by TEXT,
+
+    /**
+     * The status of the chore.
+     *
+     * This is represented by the type db::values::ChoreStatus.
+     */
+    status TEXT NOT NULL,
+
+    /**
+     * The timestamp when the chore was created.
+     */
+    created DATETIME NOT NULL,
+
+    /**
+     * The timestamp when the chore was approved, or NULL.
+     */
+    approved DATETIME,
+
+    /**
+     * The parent that approved the chore, or NULL.
+     */
+    approved_by TEXT,
+
+    FOREIGN KEY (family_uid)
+        REFERENCES Families (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (assignee_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (claimed_by)
+        REFERENCES Users (uid)
+        ON DELETE SET NULL,
+    FOREIGN KEY (approved_by)
+        REFERENCES Users (uid)
+        ON DELETE SET NULL
+);
@@ -0,0 +1,67 @@
+/**
+ * Templates from which chores are generated on a schedule.
+ */
+CREATE TABLE ChoreTemplates (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The family of the template.
+     */
+    family_uid TEXT NOT NULL,
+
+    /**
+     * The title of the generated chores.
+     */
+    title TEXT NOT NULL,
+
+    /**
+     * The reward of the generated chores.
+     */
+    reward INTEGER NOT NULL,
+
+    /**
+     * The child the generated chores are assigned to, or NULL if they are
+     * open to all children.
+     */
+    assignee_uid TEXT,
+
+    /**
+     * The schedule on which chores are generated.
+     *
+     * This is represented by the type db::values::Schedule.
+     */
+    schedule TEXT NOT NULL,
+
+    /**
+     * The amount deducted from the assignee when a generated chore is missed.
+     */
+    penalty INTEGER NOT NULL DEFAULT 0,
+
+    /**
+     * The timestamp when the template was created.
+     */
+    created DATETIME NOT NULL,
+
+    /**
+     * The timestamp when a chore was last generated, or NULL.
+     */
+    generated DATETIME,
+
+    FOREIGN KEY (family_uid)
+        REFERENCES Families (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (assignee_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE
+);
+
+/**
+ * The template from which a chore was generated, or NULL.
+ */
+ALTER TABLE Chores ADD COLUMN template_uid TEXT
+    REFERENCES ChoreTemplates (uid) ON DELETE SET NULL;
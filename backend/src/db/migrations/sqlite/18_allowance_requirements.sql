@@ -0,0 +1,17 @@
+/**
+ * The percentage of required chores a child must complete for the allowance
+ * to be paid in full, or NULL if the allowance is unconditional.
+ */
+ALTER TABLE Allowances ADD COLUMN required_share INTEGER;
+
+/**
+ * Whether a prorated amount is paid when the requirement is not met, rather
+ * than nothing.
+ */
+ALTER TABLE Allowances ADD COLUMN prorate BOOLEAN NOT NULL DEFAULT 0;
+
+/**
+ * Whether chores generated from the template count towards allowance
+ * requirements.
+ */
+ALTER TABLE ChoreTemplates ADD COLUMN required BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,64 @@
+/**
+ * Rules rewarding children with a bonus for streaks of consistent behaviour.
+ */
+CREATE TABLE BonusRules (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The child receiving the bonus.
+     */
+    user_uid TEXT NOT NULL,
+
+    /**
+     * A short name, used as description of bonus transactions.
+     */
+    name TEXT NOT NULL,
+
+    /**
+     * What is rewarded.
+     *
+     * This is represented by the type db::values::BonusKind.
+     */
+    kind TEXT NOT NULL,
+
+    /**
+     * The period over which the rule is evaluated.
+     *
+     * This is represented by the type db::values::Period.
+     */
+    period TEXT NOT NULL,
+
+    /**
+     * The number of successful periods in a row required for a bonus.
+     */
+    streak INTEGER NOT NULL,
+
+    /**
+     * The amount of the bonus.
+     */
+    amount INTEGER NOT NULL,
+
+    /**
+     * The number of successful periods in a row so far.
+     */
+    current INTEGER NOT NULL DEFAULT 0,
+
+    /**
+     * The start of the latest evaluated period, or NULL.
+     */
+    evaluated DATETIME,
+
+    /**
+     * The timestamp when the rule was created.
+     */
+    created DATETIME NOT NULL,
+
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,41 @@
+/**
+ * How far below zero the balance of a child may fall because of fines.
+ */
+ALTER TABLE FamilySettings ADD COLUMN overdraft_limit INTEGER NOT NULL
+    DEFAULT 0;
+
+/**
+ * The reasons for which parents of a family may fine their children.
+ */
+CREATE TABLE FineReasons (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The family of the reason.
+     */
+    family_uid TEXT NOT NULL,
+
+    /**
+     * A short description, used as description of fines.
+     */
+    name TEXT NOT NULL,
+
+    /**
+     * The default amount of fines for this reason.
+     */
+    amount INTEGER NOT NULL,
+
+    /**
+     * The timestamp when the reason was created.
+     */
+    created DATETIME NOT NULL,
+
+    FOREIGN KEY (family_uid)
+        REFERENCES Families (uid)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,55 @@
+/**
+ * Transactions scheduled by parents to be booked at a later time.
+ */
+CREATE TABLE PendingTransactions (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The user involved in the transaction.
+     */
+    user_uid TEXT NOT NULL,
+
+    /**
+     * The type of the transaction.
+     *
+     * This is represented by the type db::values::TransactionType.
+     */
+    transaction_type TEXT NOT NULL,
+
+    /**
+     * The ledger of the transaction, or NULL for the monetary ledger.
+     */
+    ledger_uid TEXT,
+
+    /**
+     * A description of the transaction.
+     */
+    description TEXT NOT NULL,
+
+    /**
+     * The amount of the transaction.
+     */
+    amount INTEGER NOT NULL,
+
+    /**
+     * The timestamp when the transaction is booked.
+     */
+    due DATETIME NOT NULL,
+
+    /**
+     * The timestamp when the transaction was scheduled.
+     */
+    created DATETIME NOT NULL,
+
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (ledger_uid)
+        REFERENCES Ledgers (uid)
+        ON DELETE CASCADE
+);
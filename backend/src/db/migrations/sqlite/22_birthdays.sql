@@ -0,0 +1,39 @@
+/**
+ * The birth date of a user, or NULL if unknown.
+ *
+ * This is represented by the type db::values::Date.
+ */
+ALTER TABLE Users ADD COLUMN birth_date TEXT;
+
+/**
+ * The time zone of a family, as the offset from UTC in minutes.
+ */
+ALTER TABLE FamilySettings ADD COLUMN utc_offset INTEGER NOT NULL DEFAULT 0;
+
+/**
+ * The gifts booked automatically on the birthdays of children.
+ */
+CREATE TABLE BirthdayGifts (
+    /**
+     * The child receiving the gift.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    user_uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The amount of the gift.
+     */
+    amount INTEGER NOT NULL,
+
+    /**
+     * The latest birthday for which a gift was booked, or NULL.
+     *
+     * This is represented by the type db::values::Date.
+     */
+    booked TEXT,
+
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE
+);
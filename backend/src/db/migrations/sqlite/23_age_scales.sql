@@ -0,0 +1,7 @@
+/**
+ * The scale from which the amount of an allowance is derived using the age of
+ * the child, or NULL if the amount is fixed.
+ *
+ * This is represented by the type db::values::AgeScale.
+ */
+ALTER TABLE Allowances ADD COLUMN age_scale TEXT;
@@ -0,0 +1,76 @@
+/**
+ * The history of changes to the amounts and schedules of allowances.
+ *
+ * Changes may take effect in the future, in which case they are applied to
+ * the allowance once they are due.
+ */
+CREATE TABLE AllowanceChanges (
+    /**
+     * The unique ID.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The allowance that was changed.
+     */
+    allowance_uid TEXT NOT NULL,
+
+    /**
+     * The amount before the change.
+     */
+    previous_amount INTEGER NOT NULL,
+
+    /**
+     * The schedule before the change.
+     *
+     * This is represented by the type db::values::Schedule.
+     */
+    previous_schedule TEXT NOT NULL,
+
+    /**
+     * The amount after the change.
+     */
+    amount INTEGER NOT NULL,
+
+    /**
+     * The schedule after the change.
+     *
+     * This is represented by the type db::values::Schedule.
+     */
+    schedule TEXT NOT NULL,
+
+    /**
+     * The timestamp from which the change applies.
+     */
+    effective DATETIME NOT NULL,
+
+    /**
+     * Whether the next payment is prorated between the previous and the new
+     * amount.
+     */
+    prorate BOOLEAN NOT NULL,
+
+    /**
+     * Whether the change has been applied to the allowance.
+     */
+    applied BOOLEAN NOT NULL,
+
+    /**
+     * The timestamp when the change was made.
+     */
+    created DATETIME NOT NULL,
+
+    /**
+     * The parent who made the change, or NULL if it was made automatically.
+     */
+    created_by TEXT,
+
+    FOREIGN KEY (allowance_uid)
+        REFERENCES Allowances (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (created_by)
+        REFERENCES Users (uid)
+        ON DELETE SET NULL
+);
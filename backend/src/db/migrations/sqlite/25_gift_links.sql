@@ -0,0 +1,63 @@
+/**
+ * Links through which relatives without accounts can announce gifts.
+ *
+ * The unique ID of a link is the token shared with the relative. Each link
+ * can be used once, and is removed when a parent confirms receipt of the
+ * gift.
+ */
+CREATE TABLE GiftLinks (
+    /**
+     * The unique ID, which is also the token of the link.
+     *
+     * This is represented by the type db::values::UID.
+     */
+    uid TEXT PRIMARY KEY UNIQUE,
+
+    /**
+     * The child receiving the gift.
+     */
+    user_uid TEXT NOT NULL,
+
+    /**
+     * The timestamp after which the link can no longer be used.
+     */
+    expires DATETIME NOT NULL,
+
+    /**
+     * The name of the giver, or NULL if no gift has been recorded.
+     */
+    giver TEXT,
+
+    /**
+     * The message from the giver, or NULL if no gift has been recorded.
+     */
+    message TEXT,
+
+    /**
+     * The amount of the gift, or NULL if no gift has been recorded.
+     */
+    amount INTEGER,
+
+    /**
+     * The timestamp when the gift was recorded, or NULL if no gift has been
+     * recorded.
+     */
+    recorded DATETIME,
+
+    /**
+     * The timestamp when the link was created.
+     */
+    created DATETIME NOT NULL,
+
+    /**
+     * The parent who created the link.
+     */
+    created_by TEXT,
+
+    FOREIGN KEY (user_uid)
+        REFERENCES Users (uid)
+        ON DELETE CASCADE,
+    FOREIGN KEY (created_by)
+        REFERENCES Users (uid)
+        ON DELETE SET NULL
+);
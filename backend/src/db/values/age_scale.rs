@@ -0,0 +1,175 @@
+use std::error;
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+/// A scale deriving an amount from an age in years.
+///
+/// Linear scales are represented by the base amount followed by `+` and the
+/// amount added per year of age, such as `100+25`. Tables are represented by
+/// comma separated pairs of the age from which an amount applies and the
+/// amount, such as `6:100,8:150,10:200`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AgeScale {
+    /// A base amount, and an amount added per year of age.
+    Linear { base: u32, step: u32 },
+
+    /// Amounts applying from specific ages, ordered by age.
+    Table(Vec<(u32, u32)>),
+}
+
+impl AgeScale {
+    /// The amount for a specific age.
+    ///
+    /// For tables, this is `None` if the age is lower than the first age of
+    /// the table.
+    ///
+    /// # Arguments
+    /// *  `age` - The age in years.
+    pub fn amount(&self, age: u32) -> Option<u32> {
+        use AgeScale::*;
+        match self {
+            Linear { base, step } => Some(base + step * age),
+            Table(entries) => entries
+                .iter()
+                .take_while(|(from, _)| *from <= age)
+                .last()
+                .map(|(_, amount)| *amount),
+        }
+    }
+}
+
+impl str::FromStr for AgeScale {
+    type Err = AgeScaleParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let error = || AgeScaleParseError(source.into());
+        let number = |s: &str| s.trim().parse::<u32>().map_err(|_| error());
+        if source.contains(':') {
+            let entries = source
+                .split(',')
+                .map(|entry| {
+                    let (age, amount) =
+                        entry.split_once(':').ok_or_else(error)?;
+                    Ok((number(age)?, number(amount)?))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if entries.windows(2).all(|pair| pair[0].0 < pair[1].0) {
+                Ok(AgeScale::Table(entries))
+            } else {
+                Err(error())
+            }
+        } else {
+            let (base, step) = source.split_once('+').ok_or_else(error)?;
+            Ok(AgeScale::Linear {
+                base: number(base)?,
+                step: number(step)?,
+            })
+        }
+    }
+}
+
+impl fmt::Display for AgeScale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use AgeScale::*;
+        match self {
+            Linear { base, step } => write!(f, "{}+{}", base, step),
+            Table(entries) => write!(
+                f,
+                "{}",
+                entries
+                    .iter()
+                    .map(|(age, amount)| format!("{}:{}", age, amount))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+        }
+    }
+}
+
+impl<'a> Deserialize<'a> for AgeScale {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for AgeScale {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AgeScaleParseError(String);
+
+impl fmt::Display for AgeScaleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid age scale: {}", self.0)
+    }
+}
+
+impl error::Error for AgeScaleParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            "100+25".parse::<AgeScale>().unwrap(),
+            AgeScale::Linear {
+                base: 100,
+                step: 25,
+            },
+        );
+        assert_eq!(
+            "6:100,8:150".parse::<AgeScale>().unwrap(),
+            AgeScale::Table(vec![(6, 100), (8, 150)]),
+        );
+        assert_eq!(
+            "8:150,6:100".parse::<AgeScale>(),
+            Err(AgeScaleParseError("8:150,6:100".into())),
+        );
+        assert_eq!(
+            "100".parse::<AgeScale>(),
+            Err(AgeScaleParseError("100".into())),
+        );
+        assert_eq!(
+            "6:100,8".parse::<AgeScale>(),
+            Err(AgeScaleParseError("6:100,8".into())),
+        );
+    }
+
+    #[test]
+    fn to_str() {
+        for source in ["100+25", "6:100", "6:100,8:150,10:200"].iter() {
+            let a = source.parse::<AgeScale>().unwrap();
+            assert_eq!(&a.to_string(), source);
+        }
+    }
+
+    #[test]
+    fn amount() {
+        let linear = "100+25".parse::<AgeScale>().unwrap();
+        assert_eq!(linear.amount(0), Some(100));
+        assert_eq!(linear.amount(8), Some(300));
+        let table = "6:100,8:150".parse::<AgeScale>().unwrap();
+        assert_eq!(table.amount(5), None);
+        assert_eq!(table.amount(6), Some(100));
+        assert_eq!(table.amount(7), Some(100));
+        assert_eq!(table.amount(12), Some(150));
+    }
+}
@@ -0,0 +1,96 @@
+use std::error;
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+/// What a bonus rule rewards.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BonusKind {
+    /// All chores of a period were completed.
+    Chores,
+
+    /// Nothing was spent during a period.
+    NoSpending,
+}
+
+impl str::FromStr for BonusKind {
+    type Err = BonusKindParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        use BonusKind::*;
+        match source {
+            "chores" => Ok(Chores),
+            "no-spending" => Ok(NoSpending),
+            s => Err(BonusKindParseError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for BonusKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use BonusKind::*;
+        match self {
+            Chores => write!(f, "chores"),
+            NoSpending => write!(f, "no-spending"),
+        }
+    }
+}
+
+impl<'a> Deserialize<'a> for BonusKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for BonusKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BonusKindParseError(String);
+
+impl fmt::Display for BonusKindParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid bonus kind: {}", self.0)
+    }
+}
+
+impl error::Error for BonusKindParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        use BonusKind::*;
+        assert_eq!("chores".parse::<BonusKind>().unwrap(), Chores);
+        assert_eq!("no-spending".parse::<BonusKind>().unwrap(), NoSpending);
+        assert_eq!(
+            "unknown".parse::<BonusKind>(),
+            Err(BonusKindParseError("unknown".into())),
+        );
+    }
+
+    #[test]
+    fn to_str() {
+        for source in ["chores", "no-spending"].iter() {
+            let a = source.parse::<BonusKind>().unwrap();
+            assert_eq!(&a.to_string(), source);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::error;
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+/// The separator used between categories when stored as a string.
+const SEPARATOR: char = ',';
+
+/// A list of request categories.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Categories(pub Vec<String>);
+
+impl Categories {
+    /// Whether a category is part of this list.
+    ///
+    /// # Arguments
+    /// *  `category` - The category to check.
+    pub fn contains(&self, category: &str) -> bool {
+        self.0.iter().any(|c| c == category)
+    }
+
+    /// Whether this list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl str::FromStr for Categories {
+    type Err = CategoriesParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Ok(Self(
+            source
+                .split(SEPARATOR)
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect(),
+        ))
+    }
+}
+
+impl fmt::Display for Categories {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.join(&SEPARATOR.to_string()))
+    }
+}
+
+impl<'a> Deserialize<'a> for Categories {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'a>,
+    {
+        let categories = Vec::<String>::deserialize(deserializer)?;
+        if let Some(c) = categories
+            .iter()
+            .find(|c| c.trim().is_empty() || c.contains(SEPARATOR))
+        {
+            Err(serde::de::Error::custom(CategoriesParseError(c.clone())))
+        } else {
+            Ok(Self(categories.iter().map(|c| c.trim().into()).collect()))
+        }
+    }
+}
+
+impl Serialize for Categories {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CategoriesParseError(String);
+
+impl fmt::Display for CategoriesParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid category: {}", self.0)
+    }
+}
+
+impl error::Error for CategoriesParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        assert_eq!("".parse::<Categories>().unwrap(), Categories::default());
+        assert_eq!(
+            "sweets, toys,".parse::<Categories>().unwrap(),
+            Categories(vec!["sweets".into(), "toys".into()]),
+        );
+    }
+
+    #[test]
+    fn to_str() {
+        for source in ["", "sweets", "sweets,toys"].iter() {
+            let a = source.parse::<Categories>().unwrap();
+            assert_eq!(&a.to_string(), source);
+        }
+    }
+
+    #[test]
+    fn contains() {
+        let categories = Categories(vec!["sweets".into(), "toys".into()]);
+        assert!(categories.contains("toys"));
+        assert!(!categories.contains("games"));
+    }
+}
@@ -0,0 +1,114 @@
+use std::error;
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+/// The status of a chore.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChoreStatus {
+    /// The chore has not yet been claimed by a child.
+    Open,
+
+    /// The chore has been claimed by a child.
+    Claimed,
+
+    /// The child has marked the chore as done.
+    Done,
+
+    /// A parent has approved the chore, and the reward has been paid.
+    Approved,
+
+    /// The chore was not claimed before it was due.
+    Missed,
+}
+
+impl str::FromStr for ChoreStatus {
+    type Err = ChoreStatusParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        use ChoreStatus::*;
+        match source {
+            "open" => Ok(Open),
+            "claimed" => Ok(Claimed),
+            "done" => Ok(Done),
+            "approved" => Ok(Approved),
+            "missed" => Ok(Missed),
+            s => Err(ChoreStatusParseError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for ChoreStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ChoreStatus::*;
+        match self {
+            Open => write!(f, "open"),
+            Claimed => write!(f, "claimed"),
+            Done => write!(f, "done"),
+            Approved => write!(f, "approved"),
+            Missed => write!(f, "missed"),
+        }
+    }
+}
+
+impl<'a> Deserialize<'a> for ChoreStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for ChoreStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ChoreStatusParseError(String);
+
+impl fmt::Display for ChoreStatusParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid chore status: {}", self.0)
+    }
+}
+
+impl error::Error for ChoreStatusParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        use ChoreStatus::*;
+        assert_eq!("open".parse::<ChoreStatus>().unwrap(), Open);
+        assert_eq!("claimed".parse::<ChoreStatus>().unwrap(), Claimed);
+        assert_eq!("done".parse::<ChoreStatus>().unwrap(), Done);
+        assert_eq!("approved".parse::<ChoreStatus>().unwrap(), Approved);
+        assert_eq!("missed".parse::<ChoreStatus>().unwrap(), Missed);
+        assert_eq!(
+            "unknown".parse::<ChoreStatus>(),
+            Err(ChoreStatusParseError("unknown".into())),
+        );
+    }
+
+    #[test]
+    fn to_str() {
+        for source in ["open", "claimed", "done", "approved", "missed"].iter() {
+            let a = source.parse::<ChoreStatus>().unwrap();
+            assert_eq!(&a.to_string(), source);
+        }
+    }
+}
@@ -28,6 +28,16 @@ impl CurrencyFormat {
             Self(s.into(), "".into())
         }
     }
+
+    /// The text preceding the value.
+    pub fn prefix(&self) -> &str {
+        &self.0
+    }
+
+    /// The text following the value.
+    pub fn suffix(&self) -> &str {
+        &self.1
+    }
 }
 
 impl str::FromStr for CurrencyFormat {
@@ -0,0 +1,151 @@
+use std::error;
+use std::fmt;
+use std::str;
+
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The format used to represent dates.
+const FORMAT: &'static str = "%Y-%m-%d";
+
+/// A calendar date without a time of day, such as a birth date.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Date(pub NaiveDate);
+
+impl Date {
+    /// The anniversary of this date in a specific year.
+    ///
+    /// In years without a leap day, the anniversary of a leap day is the day
+    /// before.
+    ///
+    /// # Arguments
+    /// *  `year` - The year of the anniversary.
+    pub fn anniversary(&self, year: i32) -> NaiveDate {
+        self.0
+            .with_year(year)
+            .or_else(|| NaiveDate::from_ymd_opt(year, 2, 28))
+            .unwrap_or(self.0)
+    }
+
+    /// The number of full years between this date and another date.
+    ///
+    /// # Arguments
+    /// *  `date` - The later date.
+    pub fn years_until(&self, date: NaiveDate) -> u32 {
+        let years = date.year() - self.0.year();
+        if date < self.anniversary(date.year()) {
+            (years - 1).max(0) as u32
+        } else {
+            years.max(0) as u32
+        }
+    }
+}
+
+impl From<NaiveDate> for Date {
+    fn from(source: NaiveDate) -> Self {
+        Self(source)
+    }
+}
+
+impl str::FromStr for Date {
+    type Err = DateParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Ok(Self(NaiveDate::parse_from_str(source, FORMAT)?))
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.format(FORMAT))
+    }
+}
+
+impl<'a> Deserialize<'a> for Date {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct DateParseError(chrono::format::ParseError);
+
+impl fmt::Display for DateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl error::Error for DateParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+impl From<chrono::format::ParseError> for DateParseError {
+    fn from(source: chrono::format::ParseError) -> Self {
+        Self(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            "2015-06-03".parse::<Date>().unwrap(),
+            Date(NaiveDate::from_ymd_opt(2015, 6, 3).unwrap()),
+        );
+        assert!("2015-06-31".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn to_str() {
+        let source = "2015-06-03";
+
+        let a = source.parse::<Date>().unwrap();
+        assert_eq!(a.to_string(), source);
+    }
+
+    #[test]
+    fn anniversary() {
+        let date = |s: &str| s.parse::<Date>().unwrap().0;
+        assert_eq!(
+            "2015-06-03".parse::<Date>().unwrap().anniversary(2024),
+            date("2024-06-03"),
+        );
+        assert_eq!(
+            "2016-02-29".parse::<Date>().unwrap().anniversary(2024),
+            date("2024-02-29"),
+        );
+        assert_eq!(
+            "2016-02-29".parse::<Date>().unwrap().anniversary(2025),
+            date("2025-02-28"),
+        );
+    }
+
+    #[test]
+    fn years_until() {
+        let date = |s: &str| s.parse::<Date>().unwrap().0;
+        let birth = "2015-06-03".parse::<Date>().unwrap();
+        assert_eq!(birth.years_until(date("2015-06-03")), 0);
+        assert_eq!(birth.years_until(date("2024-06-02")), 8);
+        assert_eq!(birth.years_until(date("2024-06-03")), 9);
+        assert_eq!(birth.years_until(date("2014-06-03")), 0);
+    }
+}
@@ -0,0 +1,96 @@
+use std::error;
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+/// What a matching rule matches.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MatchKind {
+    /// The balance held at the end of a period is matched.
+    Savings,
+
+    /// The deposits made during a period are matched.
+    Deposits,
+}
+
+impl str::FromStr for MatchKind {
+    type Err = MatchKindParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        use MatchKind::*;
+        match source {
+            "savings" => Ok(Savings),
+            "deposits" => Ok(Deposits),
+            s => Err(MatchKindParseError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for MatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use MatchKind::*;
+        match self {
+            Savings => write!(f, "savings"),
+            Deposits => write!(f, "deposits"),
+        }
+    }
+}
+
+impl<'a> Deserialize<'a> for MatchKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for MatchKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MatchKindParseError(String);
+
+impl fmt::Display for MatchKindParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid match kind: {}", self.0)
+    }
+}
+
+impl error::Error for MatchKindParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        use MatchKind::*;
+        assert_eq!("savings".parse::<MatchKind>().unwrap(), Savings);
+        assert_eq!("deposits".parse::<MatchKind>().unwrap(), Deposits);
+        assert_eq!(
+            "unknown".parse::<MatchKind>(),
+            Err(MatchKindParseError("unknown".into())),
+        );
+    }
+
+    #[test]
+    fn to_str() {
+        for source in ["savings", "deposits"].iter() {
+            let a = source.parse::<MatchKind>().unwrap();
+            assert_eq!(&a.to_string(), source);
+        }
+    }
+}
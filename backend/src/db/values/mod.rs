@@ -1,9 +1,27 @@
+mod age_scale;
+pub use self::age_scale::*;
+mod bonus_kind;
+pub use self::bonus_kind::*;
+mod categories;
+pub use self::categories::*;
+mod chore_status;
+pub use self::chore_status::*;
 mod currency_format;
 pub use self::currency_format::*;
+mod date;
+pub use self::date::*;
 mod email_address;
 pub use self::email_address::*;
+mod match_kind;
+pub use self::match_kind::*;
+mod negative_style;
+pub use self::negative_style::*;
 mod password_hash;
 pub use self::password_hash::*;
+mod period;
+pub use self::period::*;
+mod request_status;
+pub use self::request_status::*;
 mod role;
 pub use self::role::*;
 mod schedule;
@@ -99,9 +117,18 @@ mod values {
     use weru::database::sqlx::{error::BoxDynError, Decode, Encode, Type};
     use weru::database::Database;
 
+    value!(AgeScale => String);
+    value!(BonusKind => String);
+    value!(Categories => String);
+    value!(ChoreStatus => String);
     value!(CurrencyFormat => String);
+    value!(Date => String);
     value!(EmailAddress => String);
+    value!(MatchKind => String);
+    value!(NegativeStyle => String);
     value!(PasswordHash => String);
+    value!(Period => String);
+    value!(RequestStatus => String);
     value!(Role => String);
     value!(Schedule => String);
     value!(Timestamp[chrono::DateTime::<chrono::FixedOffset>]);
@@ -0,0 +1,106 @@
+use std::error;
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+/// The way negative monetary values are presented.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NegativeStyle {
+    /// The minus sign precedes the currency prefix, as in `-$1.00`.
+    #[default]
+    Leading,
+
+    /// The minus sign follows the currency prefix, as in `$-1.00`.
+    Inner,
+
+    /// The value is wrapped in parentheses, as in `($1.00)`.
+    Parentheses,
+}
+
+impl str::FromStr for NegativeStyle {
+    type Err = NegativeStyleParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        use NegativeStyle::*;
+        match source {
+            "leading" => Ok(Leading),
+            "inner" => Ok(Inner),
+            "parentheses" => Ok(Parentheses),
+            s => Err(NegativeStyleParseError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for NegativeStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use NegativeStyle::*;
+        match self {
+            Leading => write!(f, "leading"),
+            Inner => write!(f, "inner"),
+            Parentheses => write!(f, "parentheses"),
+        }
+    }
+}
+
+impl<'a> Deserialize<'a> for NegativeStyle {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for NegativeStyle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NegativeStyleParseError(String);
+
+impl fmt::Display for NegativeStyleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid negative style: {}", self.0)
+    }
+}
+
+impl error::Error for NegativeStyleParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        use NegativeStyle::*;
+        assert_eq!("leading".parse::<NegativeStyle>().unwrap(), Leading);
+        assert_eq!("inner".parse::<NegativeStyle>().unwrap(), Inner);
+        assert_eq!(
+            "parentheses".parse::<NegativeStyle>().unwrap(),
+            Parentheses,
+        );
+        assert_eq!(
+            "unknown".parse::<NegativeStyle>(),
+            Err(NegativeStyleParseError("unknown".into())),
+        );
+    }
+
+    #[test]
+    fn to_str() {
+        for source in ["leading", "inner", "parentheses"].iter() {
+            let a = source.parse::<NegativeStyle>().unwrap();
+            assert_eq!(&a.to_string(), source);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::error;
+use std::fmt;
+use std::str;
+
+use chrono::prelude::*;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::db::values::Timestamp;
+
+/// A recurring period of time.
+///
+/// Periods start at midnight UTC; weeks start on Mondays and months on the
+/// first day of the month.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Period {
+    /// A calendar week.
+    Weekly,
+
+    /// A calendar month.
+    Monthly,
+}
+
+impl Period {
+    /// Calculates the start of the period containing a timestamp.
+    ///
+    /// # Arguments
+    /// *  `timestamp` - The timestamp.
+    pub fn start(&self, timestamp: Timestamp) -> Timestamp {
+        use Period::*;
+        let date = timestamp.0.with_timezone(&Utc).date_naive();
+        let date = match self {
+            Weekly => {
+                date - Duration::days(
+                    date.weekday().num_days_from_monday() as i64
+                )
+            }
+            Monthly => date.with_day(1).unwrap_or(date),
+        };
+        Timestamp(Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN)).into())
+    }
+
+    /// Calculates the start of the period preceding the one containing a
+    /// timestamp.
+    ///
+    /// # Arguments
+    /// *  `timestamp` - The timestamp.
+    pub fn previous(&self, timestamp: Timestamp) -> Timestamp {
+        let start = self.start(timestamp);
+        self.start(Timestamp(start.0 - Duration::days(1)))
+    }
+}
+
+impl str::FromStr for Period {
+    type Err = PeriodParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        use Period::*;
+        match source {
+            "weekly" => Ok(Weekly),
+            "monthly" => Ok(Monthly),
+            s => Err(PeriodParseError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Period::*;
+        match self {
+            Weekly => write!(f, "weekly"),
+            Monthly => write!(f, "monthly"),
+        }
+    }
+}
+
+impl<'a> Deserialize<'a> for Period {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for Period {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PeriodParseError(String);
+
+impl fmt::Display for PeriodParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid period: {}", self.0)
+    }
+}
+
+impl error::Error for PeriodParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        use Period::*;
+        assert_eq!("weekly".parse::<Period>().unwrap(), Weekly);
+        assert_eq!("monthly".parse::<Period>().unwrap(), Monthly);
+        assert_eq!(
+            "unknown".parse::<Period>(),
+            Err(PeriodParseError("unknown".into())),
+        );
+    }
+
+    #[test]
+    fn to_str() {
+        for source in ["weekly", "monthly"].iter() {
+            let a = source.parse::<Period>().unwrap();
+            assert_eq!(&a.to_string(), source);
+        }
+    }
+
+    #[test]
+    fn start() {
+        let timestamp = "2024-03-14T15:16:17+02:00".parse().unwrap();
+        assert_eq!(
+            Period::Weekly.start(timestamp),
+            "2024-03-11T00:00:00Z".parse().unwrap(),
+        );
+        assert_eq!(
+            Period::Monthly.start(timestamp),
+            "2024-03-01T00:00:00Z".parse().unwrap(),
+        );
+    }
+
+    #[test]
+    fn previous() {
+        let timestamp = "2024-03-14T15:16:17+02:00".parse().unwrap();
+        assert_eq!(
+            Period::Weekly.previous(timestamp),
+            "2024-03-04T00:00:00Z".parse().unwrap(),
+        );
+        assert_eq!(
+            Period::Monthly.previous(timestamp),
+            "2024-02-01T00:00:00Z".parse().unwrap(),
+        );
+    }
+}
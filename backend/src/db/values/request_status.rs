@@ -0,0 +1,116 @@
+use std::error;
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+/// The status of a request.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RequestStatus {
+    /// The request has not yet been decided.
+    Pending,
+
+    /// The request was granted by a parent.
+    Granted,
+
+    /// The request was declined by a parent.
+    Declined,
+
+    /// The request was withdrawn by the child making it.
+    Withdrawn,
+
+    /// The request was not decided before the expiry set by the family.
+    Expired,
+}
+
+impl str::FromStr for RequestStatus {
+    type Err = RequestStatusParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        use RequestStatus::*;
+        match source {
+            "pending" => Ok(Pending),
+            "granted" => Ok(Granted),
+            "declined" => Ok(Declined),
+            "withdrawn" => Ok(Withdrawn),
+            "expired" => Ok(Expired),
+            s => Err(RequestStatusParseError(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for RequestStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use RequestStatus::*;
+        match self {
+            Pending => write!(f, "pending"),
+            Granted => write!(f, "granted"),
+            Declined => write!(f, "declined"),
+            Withdrawn => write!(f, "withdrawn"),
+            Expired => write!(f, "expired"),
+        }
+    }
+}
+
+impl<'a> Deserialize<'a> for RequestStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for RequestStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RequestStatusParseError(String);
+
+impl fmt::Display for RequestStatusParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid request status: {}", self.0)
+    }
+}
+
+impl error::Error for RequestStatusParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        use RequestStatus::*;
+        assert_eq!("pending".parse::<RequestStatus>().unwrap(), Pending);
+        assert_eq!("granted".parse::<RequestStatus>().unwrap(), Granted);
+        assert_eq!("declined".parse::<RequestStatus>().unwrap(), Declined);
+        assert_eq!("withdrawn".parse::<RequestStatus>().unwrap(), Withdrawn);
+        assert_eq!("expired".parse::<RequestStatus>().unwrap(), Expired);
+        assert_eq!(
+            "unknown".parse::<RequestStatus>(),
+            Err(RequestStatusParseError("unknown".into())),
+        );
+    }
+
+    #[test]
+    fn to_str() {
+        for source in
+            ["pending", "granted", "declined", "withdrawn", "expired"].iter()
+        {
+            let a = source.parse::<RequestStatus>().unwrap();
+            assert_eq!(&a.to_string(), source);
+        }
+    }
+}
@@ -3,15 +3,80 @@ use std::fmt;
 use std::str;
 
 use chrono::prelude::*;
+use chrono::Duration;
 use serde::{Deserialize, Serialize};
 
 /// A Schedule.
+///
+/// Daily schedules are represented by `daily`, weekly schedules by the name
+/// of the weekday, and monthly schedules by `monthly-` followed by the day of
+/// the month.
 #[derive(Clone, Debug, PartialEq)]
-pub struct Schedule(pub Weekday);
+pub enum Schedule {
+    /// Every day.
+    Daily,
+
+    /// The same weekday every week.
+    Weekly(Weekday),
+
+    /// The same day every month.
+    ///
+    /// In months with fewer days, the last day of the month is used.
+    Monthly(u32),
+}
+
+impl Schedule {
+    /// The string representation of daily schedules.
+    const DAILY: &'static str = "daily";
+
+    /// The prefix of monthly schedules.
+    const MONTHLY: &'static str = "monthly-";
+
+    /// Determines whether this schedule is due on a specific date.
+    ///
+    /// # Arguments
+    /// *  `date` - The date to check.
+    pub fn is_due(&self, date: NaiveDate) -> bool {
+        use Schedule::*;
+        match self {
+            Daily => true,
+            Weekly(weekday) => date.weekday() == *weekday,
+            Monthly(day) => {
+                let last = (28..=31)
+                    .rev()
+                    .find(|&d| date.with_day(d).is_some())
+                    .unwrap_or(28);
+                date.day() == (*day).min(last)
+            }
+        }
+    }
+
+    /// The first date after a specific date on which this schedule is due.
+    ///
+    /// # Arguments
+    /// *  `date` - The date after which to look.
+    pub fn next_after(&self, date: NaiveDate) -> NaiveDate {
+        date.iter_days()
+            .skip(1)
+            .find(|&d| self.is_due(d))
+            .unwrap_or(date)
+    }
+
+    /// The last date before a specific date on which this schedule was due.
+    ///
+    /// # Arguments
+    /// *  `date` - The date before which to look.
+    pub fn previous_before(&self, date: NaiveDate) -> NaiveDate {
+        (1..=31)
+            .map(|days| date - Duration::days(days))
+            .find(|&d| self.is_due(d))
+            .unwrap_or(date)
+    }
+}
 
 impl From<Weekday> for Schedule {
     fn from(source: Weekday) -> Self {
-        Self(source)
+        Self::Weekly(source)
     }
 }
 
@@ -19,17 +84,32 @@ impl str::FromStr for Schedule {
     type Err = ScheduleParseError;
 
     fn from_str(source: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            source
-                .parse()
-                .map_err(|_| ScheduleParseError(source.into()))?,
-        ))
+        if source == Self::DAILY {
+            Ok(Self::Daily)
+        } else if let Some(day) = source.strip_prefix(Self::MONTHLY) {
+            day.parse()
+                .ok()
+                .filter(|day| (1..=31).contains(day))
+                .map(Self::Monthly)
+                .ok_or_else(|| ScheduleParseError(source.into()))
+        } else {
+            Ok(Self::Weekly(
+                source
+                    .parse()
+                    .map_err(|_| ScheduleParseError(source.into()))?,
+            ))
+        }
     }
 }
 
 impl fmt::Display for Schedule {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(&self.0, f)
+        use Schedule::*;
+        match self {
+            Daily => write!(f, "{}", Self::DAILY),
+            Weekly(weekday) => fmt::Display::fmt(weekday, f),
+            Monthly(day) => write!(f, "{}{}", Self::MONTHLY, day),
+        }
     }
 }
 
@@ -73,40 +153,74 @@ mod tests {
 
     #[test]
     fn from_str() {
-        assert_eq!("Mon".parse::<Schedule>().unwrap(), Schedule(Weekday::Mon));
+        assert_eq!("daily".parse::<Schedule>().unwrap(), Schedule::Daily);
+        assert_eq!(
+            "Mon".parse::<Schedule>().unwrap(),
+            Schedule::Weekly(Weekday::Mon)
+        );
         assert_eq!(
             "Monday".parse::<Schedule>().unwrap(),
-            Schedule(Weekday::Mon)
+            Schedule::Weekly(Weekday::Mon)
+        );
+        assert_eq!(
+            "Tue".parse::<Schedule>().unwrap(),
+            Schedule::Weekly(Weekday::Tue)
         );
-        assert_eq!("Tue".parse::<Schedule>().unwrap(), Schedule(Weekday::Tue));
         assert_eq!(
             "Tuesday".parse::<Schedule>().unwrap(),
-            Schedule(Weekday::Tue)
+            Schedule::Weekly(Weekday::Tue)
+        );
+        assert_eq!(
+            "Wed".parse::<Schedule>().unwrap(),
+            Schedule::Weekly(Weekday::Wed)
         );
-        assert_eq!("Wed".parse::<Schedule>().unwrap(), Schedule(Weekday::Wed));
         assert_eq!(
             "Wednesday".parse::<Schedule>().unwrap(),
-            Schedule(Weekday::Wed)
+            Schedule::Weekly(Weekday::Wed)
+        );
+        assert_eq!(
+            "Thu".parse::<Schedule>().unwrap(),
+            Schedule::Weekly(Weekday::Thu)
         );
-        assert_eq!("Thu".parse::<Schedule>().unwrap(), Schedule(Weekday::Thu));
         assert_eq!(
             "Thursday".parse::<Schedule>().unwrap(),
-            Schedule(Weekday::Thu)
+            Schedule::Weekly(Weekday::Thu)
+        );
+        assert_eq!(
+            "Fri".parse::<Schedule>().unwrap(),
+            Schedule::Weekly(Weekday::Fri)
         );
-        assert_eq!("Fri".parse::<Schedule>().unwrap(), Schedule(Weekday::Fri));
         assert_eq!(
             "Friday".parse::<Schedule>().unwrap(),
-            Schedule(Weekday::Fri)
+            Schedule::Weekly(Weekday::Fri)
+        );
+        assert_eq!(
+            "Sat".parse::<Schedule>().unwrap(),
+            Schedule::Weekly(Weekday::Sat)
         );
-        assert_eq!("Sat".parse::<Schedule>().unwrap(), Schedule(Weekday::Sat));
         assert_eq!(
             "Saturday".parse::<Schedule>().unwrap(),
-            Schedule(Weekday::Sat)
+            Schedule::Weekly(Weekday::Sat)
+        );
+        assert_eq!(
+            "Sun".parse::<Schedule>().unwrap(),
+            Schedule::Weekly(Weekday::Sun)
         );
-        assert_eq!("Sun".parse::<Schedule>().unwrap(), Schedule(Weekday::Sun));
         assert_eq!(
             "Sunday".parse::<Schedule>().unwrap(),
-            Schedule(Weekday::Sun)
+            Schedule::Weekly(Weekday::Sun)
+        );
+        assert_eq!(
+            "monthly-1".parse::<Schedule>().unwrap(),
+            Schedule::Monthly(1)
+        );
+        assert_eq!(
+            "monthly-31".parse::<Schedule>().unwrap(),
+            Schedule::Monthly(31)
+        );
+        assert_eq!(
+            "monthly-32".parse::<Schedule>(),
+            Err(ScheduleParseError("monthly-32".into())),
         );
         assert_eq!(
             "unknown".parse::<Schedule>(),
@@ -116,9 +230,69 @@ mod tests {
 
     #[test]
     fn to_str() {
-        for source in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"].iter() {
+        for source in [
+            "daily",
+            "Mon",
+            "Tue",
+            "Wed",
+            "Thu",
+            "Fri",
+            "Sat",
+            "Sun",
+            "monthly-1",
+            "monthly-15",
+        ]
+        .iter()
+        {
             let a = source.parse::<Schedule>().unwrap();
             assert_eq!(&a.to_string(), source);
         }
     }
+
+    #[test]
+    fn is_due() {
+        let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert!(Schedule::Weekly(Weekday::Fri).is_due(date("2024-03-01")));
+        assert!(!Schedule::Weekly(Weekday::Fri).is_due(date("2024-03-02")));
+        assert!(Schedule::Monthly(1).is_due(date("2024-03-01")));
+        assert!(!Schedule::Monthly(1).is_due(date("2024-03-02")));
+        assert!(Schedule::Monthly(31).is_due(date("2024-02-29")));
+        assert!(!Schedule::Monthly(31).is_due(date("2024-02-28")));
+        assert!(Schedule::Monthly(31).is_due(date("2024-03-31")));
+        assert!(Schedule::Daily.is_due(date("2024-03-02")));
+    }
+
+    #[test]
+    fn next_after() {
+        let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(
+            Schedule::Daily.next_after(date("2024-02-29")),
+            date("2024-03-01"),
+        );
+        assert_eq!(
+            Schedule::Weekly(Weekday::Fri).next_after(date("2024-03-01")),
+            date("2024-03-08"),
+        );
+        assert_eq!(
+            Schedule::Monthly(31).next_after(date("2024-01-31")),
+            date("2024-02-29"),
+        );
+    }
+
+    #[test]
+    fn previous_before() {
+        let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(
+            Schedule::Daily.previous_before(date("2024-03-01")),
+            date("2024-02-29"),
+        );
+        assert_eq!(
+            Schedule::Weekly(Weekday::Fri).previous_before(date("2024-03-08")),
+            date("2024-03-01"),
+        );
+        assert_eq!(
+            Schedule::Monthly(31).previous_before(date("2024-03-31")),
+            date("2024-02-29"),
+        );
+    }
 }
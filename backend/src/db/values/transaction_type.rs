@@ -10,11 +10,32 @@ pub enum TransactionType {
     /// The transaction is an allowance payment.
     Allowance,
 
+    /// The transaction is a request that was approved automatically.
+    AutoApproved,
+
+    /// The transaction is a bonus for a streak.
+    Bonus,
+
+    /// The transaction is a reward for a chore.
+    Chore,
+
+    /// The transaction is a fine imposed by a parent.
+    Fine,
+
     /// The transaction is a gift from a parent.
     Gift,
 
+    /// The transaction is a parent contribution matching savings.
+    Match,
+
+    /// The transaction is a penalty for a missed chore.
+    Penalty,
+
     /// The transaction is a request that has been granted.
     Request,
+
+    /// The transaction is a recurring deduction for a subscription.
+    Subscription,
 }
 
 impl str::FromStr for TransactionType {
@@ -24,8 +45,15 @@ impl str::FromStr for TransactionType {
         use TransactionType::*;
         match source {
             "allowance" => Ok(Allowance),
+            "auto-approved" => Ok(AutoApproved),
+            "bonus" => Ok(Bonus),
+            "chore" => Ok(Chore),
+            "fine" => Ok(Fine),
             "gift" => Ok(Gift),
+            "match" => Ok(Match),
+            "penalty" => Ok(Penalty),
             "request" => Ok(Request),
+            "subscription" => Ok(Subscription),
             s => Err(TransactionTypeParseError(s.into())),
         }
     }
@@ -36,8 +64,15 @@ impl fmt::Display for TransactionType {
         use TransactionType::*;
         match self {
             Allowance => write!(f, "allowance"),
+            AutoApproved => write!(f, "auto-approved"),
+            Bonus => write!(f, "bonus"),
+            Chore => write!(f, "chore"),
+            Fine => write!(f, "fine"),
             Gift => write!(f, "gift"),
+            Match => write!(f, "match"),
+            Penalty => write!(f, "penalty"),
             Request => write!(f, "request"),
+            Subscription => write!(f, "subscription"),
         }
     }
 }
@@ -84,8 +119,21 @@ mod tests {
     fn from_str() {
         use TransactionType::*;
         assert_eq!("allowance".parse::<TransactionType>().unwrap(), Allowance);
+        assert_eq!(
+            "auto-approved".parse::<TransactionType>().unwrap(),
+            AutoApproved,
+        );
+        assert_eq!("bonus".parse::<TransactionType>().unwrap(), Bonus);
+        assert_eq!("chore".parse::<TransactionType>().unwrap(), Chore);
+        assert_eq!("fine".parse::<TransactionType>().unwrap(), Fine);
         assert_eq!("gift".parse::<TransactionType>().unwrap(), Gift);
+        assert_eq!("match".parse::<TransactionType>().unwrap(), Match);
+        assert_eq!("penalty".parse::<TransactionType>().unwrap(), Penalty);
         assert_eq!("request".parse::<TransactionType>().unwrap(), Request);
+        assert_eq!(
+            "subscription".parse::<TransactionType>().unwrap(),
+            Subscription,
+        );
         assert_eq!(
             "unknown".parse::<TransactionType>(),
             Err(TransactionTypeParseError("unknown".into())),
@@ -94,7 +142,20 @@ mod tests {
 
     #[test]
     fn to_str() {
-        for source in ["allowance", "gift", "request"].iter() {
+        for source in [
+            "allowance",
+            "auto-approved",
+            "bonus",
+            "chore",
+            "fine",
+            "gift",
+            "match",
+            "penalty",
+            "request",
+            "subscription",
+        ]
+        .iter()
+        {
             let a = source.parse::<TransactionType>().unwrap();
             assert_eq!(&a.to_string(), source);
         }
@@ -33,20 +33,46 @@ async fn run() -> Result<(), Box<dyn Error>> {
     let database = Data::new(configuration.database.engine().await?);
     db::MIGRATOR.run(&mut database.connection().await?).await?;
 
-    let tasks_connection_pool = configuration.database.engine().await?;
-    let _scheduler = Supervisor::start(|_| {
-        tasks::Scheduled::new(tasks_connection_pool).with(
-            tasks::ScheduledTask::Daily(Box::new(
-                tasks::allowance::AllowancePayer,
-            )),
-        )
-    });
-
     let channel = Data::new(configuration.channel.engine().await?);
 
     let email = configuration.email.engine().await?;
     let sender = Data::new(email.sender().await);
 
+    let tasks_connection_pool = configuration.database.engine().await?;
+    let allowance_adjuster =
+        tasks::allowance::AllowanceAdjuster::new(channel.clone());
+    let birthday_giver = tasks::birthdays::BirthdayGiver::new(channel.clone());
+    let bonus_payer = tasks::bonuses::BonusPayer::new(channel.clone());
+    let chore_scheduler = tasks::chores::ChoreScheduler::new(channel.clone());
+    let pending_transaction_booker =
+        tasks::pending::PendingTransactionBooker::new(channel.clone());
+    let request_expirer = tasks::requests::RequestExpirer::new(
+        channel.clone(),
+        sender.clone(),
+        configuration.server.url.clone(),
+        configuration.defaults(),
+    );
+    let _scheduler = Supervisor::start(|_| {
+        tasks::Scheduled::new(tasks_connection_pool)
+            .with(tasks::ScheduledTask::Daily(Box::new(allowance_adjuster)))
+            .with(tasks::ScheduledTask::Daily(Box::new(
+                tasks::allowance::AllowancePayer,
+            )))
+            .with(tasks::ScheduledTask::Daily(Box::new(birthday_giver)))
+            .with(tasks::ScheduledTask::Daily(Box::new(bonus_payer)))
+            .with(tasks::ScheduledTask::Daily(Box::new(chore_scheduler)))
+            .with(tasks::ScheduledTask::Daily(Box::new(
+                tasks::matching::MatchPayer,
+            )))
+            .with(tasks::ScheduledTask::Daily(Box::new(
+                pending_transaction_booker,
+            )))
+            .with(tasks::ScheduledTask::Daily(Box::new(request_expirer)))
+            .with(tasks::ScheduledTask::Daily(Box::new(
+                tasks::subscriptions::SubscriptionPayer,
+            )))
+    });
+
     let defaults = Data::new(configuration.defaults());
     let configuration = Data::new(configuration);
 
@@ -59,25 +85,67 @@ async fn run() -> Result<(), Box<dyn Error>> {
             .app_data(sender.clone())
             .wrap(session_store.clone().middleware(&configuration.session))
             .service(api::server::handle)
+            .service(api::bonus::create::handle)
+            .service(api::bonus::remove::handle)
+            .service(api::chore::approve::handle)
+            .service(api::chore::claim::handle)
+            .service(api::chore::create::handle)
+            .service(api::chore::done::handle)
+            .service(api::chore::template::create::handle)
+            .service(api::chore::template::remove::handle)
+            .service(api::currency::list::handle)
             .service(api::family::add::handle)
+            .service(api::family::currency::handle)
             .service(api::family::register::handle)
             .service(api::family::remove::handle)
+            .service(api::family::settings::handle)
+            .service(api::fine::apply::handle)
+            .service(api::fine::create::handle)
+            .service(api::fine::remove::handle)
+            .service(api::gift_link::confirm::handle)
+            .service(api::gift_link::create::handle)
+            .service(api::gift_link::get::handle)
+            .service(api::gift_link::record::handle)
+            .service(api::gift_link::remove::handle)
+            .service(api::goal::complete::handle)
+            .service(api::goal::create::handle)
+            .service(api::goal::update::handle)
             .service(api::invitation::accept::handle)
             .service(api::invitation::create::handle)
             .service(api::invitation::get::handle)
+            .service(api::ledger::create::handle)
+            .service(api::ledger::remove::handle)
+            .service(api::matching::create::handle)
+            .service(api::matching::preview::handle)
+            .service(api::matching::remove::handle)
             .service(api::overview::handle)
+            .service(api::request::comment::handle)
             .service(api::request::decline::handle)
             .service(api::request::get::handle)
             .service(api::request::grant::handle)
+            .service(api::request::history::handle)
             .service(api::request::make::handle)
+            .service(api::request::update::handle)
+            .service(api::request::withdraw::handle)
             .service(api::session::introspect::handle)
             .service(api::session::login::handle)
             .service(api::session::logout::handle)
             .service(api::session::password::handle)
+            .service(api::subscription::cancel::handle)
+            .service(api::subscription::pause::handle)
+            .service(api::transaction::bulk::handle)
             .service(api::transaction::create::handle)
             .service(api::transaction::list::handle)
+            .service(api::transaction::pending::cancel::handle)
+            .service(api::transaction::pending::create::handle)
+            .service(api::transaction::pending::list::handle)
+            .service(api::transaction::pending::update::handle)
             .service(api::user::allowance::handle)
+            .service(api::user::allowance_history::handle)
+            .service(api::user::auto_approval::handle)
+            .service(api::user::birthday::handle)
             .service(api::user::get::handle)
+            .service(api::user::jars::handle)
             .route(
                 "/notify",
                 weru::actix::web::web::get().to(api::notify::handle),
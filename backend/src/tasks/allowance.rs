@@ -1,14 +1,258 @@
 use crate::prelude::*;
 
-use chrono::Datelike;
+use chrono::{Datelike, NaiveTime, TimeZone, Utc};
 use weru::async_trait::async_trait;
 
+use crate::api::notify::{Event, Notify};
 use crate::db;
+use crate::db::entities::{
+    Allowance, AllowanceChange, Chore, FamilySettings, Transaction, User,
+};
+use crate::db::values::{Schedule, Timestamp, TransactionType, UID};
 
 pub struct AllowancePayer;
 
+/// Applies scheduled allowance changes that are due, and adjusts the amounts
+/// of allowances derived from the ages of children.
+///
+/// This task must run before the allowance payer, so that the current
+/// amounts are paid. Ages are determined in the time zone of the family.
+pub struct AllowanceAdjuster {
+    /// The channel used to notify family members.
+    channel: web::Data<ChannelEngine>,
+}
+
+impl AllowanceAdjuster {
+    /// Creates a new task.
+    ///
+    /// # Arguments
+    /// *  `channel` - The channel used to notify family members.
+    pub fn new(channel: web::Data<ChannelEngine>) -> Self {
+        Self { channel }
+    }
+
+    /// Applies due changes, and updates the amounts of all allowances with an
+    /// age scale.
+    ///
+    /// Scheduled changes do not alter the amounts of allowances with an age
+    /// scale, so on a birthday the amount for the new age applies.
+    ///
+    /// Amounts derived from ages are recorded in the history of the
+    /// allowance. The allowances that changed are returned along with the
+    /// families of the children.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `timestamp` - The current timestamp.
+    pub async fn process<'a>(
+        tx: &mut Tx<'a>,
+        timestamp: Timestamp,
+    ) -> Result<Vec<(UID, Allowance)>, DatabaseError> {
+        let mut adjusted = Vec::new();
+        for change in AllowanceChange::read_due(tx, timestamp).await? {
+            let current =
+                match Allowance::read(tx.as_mut(), &change.allowance_uid)
+                    .await?
+                {
+                    Some(allowance) => allowance,
+                    None => continue,
+                };
+
+            // The allowance may have changed since this change was scheduled,
+            // and the age scale takes precedence over a scheduled amount
+            let change = AllowanceChange {
+                previous_amount: current.amount,
+                previous_schedule: current.schedule.clone(),
+                amount: if current.age_scale.is_some() {
+                    current.amount
+                } else {
+                    change.amount
+                },
+                applied: true,
+                ..change
+            };
+            let allowance = change.apply(current);
+            allowance.update(tx.as_mut()).await?;
+            change.update(tx.as_mut()).await?;
+            if let Some(user) =
+                User::read(tx.as_mut(), &allowance.user_uid).await?
+            {
+                adjusted.push((user.family_uid, allowance));
+            }
+        }
+
+        for allowance in Allowance::list(tx.as_mut()).await? {
+            if allowance.age_scale.is_none() {
+                continue;
+            }
+            let user =
+                match User::read(tx.as_mut(), &allowance.user_uid).await? {
+                    Some(user) => user,
+                    None => continue,
+                };
+            let birth_date = match user.birth_date {
+                Some(birth_date) => birth_date,
+                None => continue,
+            };
+            let settings =
+                FamilySettings::read_or_default(tx, &user.family_uid).await?;
+
+            let amount = allowance.amount_at(
+                birth_date.years_until(settings.local_date(timestamp)),
+            );
+            if amount != allowance.amount {
+                AllowanceChange {
+                    applied: true,
+                    ..AllowanceChange::record(
+                        &allowance,
+                        amount,
+                        allowance.schedule.clone(),
+                        timestamp,
+                        false,
+                        None,
+                    )
+                }
+                .create(tx.as_mut())
+                .await?;
+                let allowance = Allowance {
+                    amount,
+                    ..allowance
+                };
+                allowance.update(tx.as_mut()).await?;
+                adjusted.retain(|(_, a)| a.uid != allowance.uid);
+                adjusted.push((user.family_uid, allowance));
+            }
+        }
+        Ok(adjusted)
+    }
+}
+
+#[async_trait]
+impl super::Task for AllowanceAdjuster {
+    fn name(&self) -> &'static str {
+        "allowance-adjuster"
+    }
+
+    async fn run<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<(), super::Error> {
+        for (family_uid, allowance) in Self::process(tx, timestamp).await? {
+            Notify::MemberAndParents {
+                event: Event::AllowanceUpdated {
+                    allowance: allowance.clone(),
+                    by: None,
+                },
+                uid: allowance.user_uid,
+                family: family_uid,
+            }
+            .send_system(tx, &self.channel)
+            .await;
+        }
+        Ok(())
+    }
+}
+
 impl AllowancePayer {
-    const PAY: &'static str = sql_from_file!("Allowance.pay");
+    /// Calculates the amount to pay for an allowance.
+    ///
+    /// If a change requesting proration took effect since the previous
+    /// payment, the amount is prorated between the previous and the new
+    /// amount.
+    ///
+    /// If the allowance requires chores to be completed, only required chores
+    /// due since the previous payment are considered.
+    ///
+    /// The returned description explains the calculation, and is empty if
+    /// the full amount is paid unconditionally.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `allowance` - The allowance to pay.
+    /// *  `timestamp` - The current timestamp.
+    pub async fn payment<'a>(
+        tx: &mut Tx<'a>,
+        allowance: &Allowance,
+        timestamp: Timestamp,
+    ) -> Result<(i64, String), DatabaseError> {
+        let previous = Timestamp(
+            Utc.from_utc_datetime(
+                &allowance
+                    .schedule
+                    .previous_before(timestamp.0.date_naive())
+                    .and_time(NaiveTime::MIN),
+            )
+            .into(),
+        );
+        let (amount, prorated) = match AllowanceChange::read_prorated(
+            tx,
+            &allowance.uid,
+            previous,
+            timestamp,
+        )
+        .await?
+        {
+            Some(change) => (change.prorated(previous, timestamp), true),
+            None => (allowance.amount as i64, false),
+        };
+        let description = if prorated {
+            String::from("Prorated for allowance change")
+        } else {
+            String::new()
+        };
+        let required = match allowance.required_share {
+            Some(required) if required > 0 => required as i64,
+            _ => return Ok((amount, description)),
+        };
+
+        let chores = Chore::read_required_for_user(
+            tx,
+            &allowance.user_uid,
+            previous,
+            timestamp,
+        )
+        .await?;
+        let total = chores.len() as i64;
+        let completed =
+            chores.iter().filter(|chore| chore.is_completed()).count() as i64;
+        if total == 0 {
+            return Ok((amount, description));
+        }
+
+        let share = completed * 100 / total;
+        let (amount, requirement) = if share >= required {
+            (
+                amount,
+                format!("{} of {} required chores done", completed, total),
+            )
+        } else if allowance.prorate {
+            (
+                amount * completed * 100 / (total * required),
+                format!(
+                    "{} of {} required chores done ({}% of {}% required), \
+                    prorated",
+                    completed, total, share, required,
+                ),
+            )
+        } else {
+            (
+                0,
+                format!(
+                    "{} of {} required chores done ({}% of {}% required)",
+                    completed, total, share, required,
+                ),
+            )
+        };
+        Ok(if prorated {
+            (
+                amount,
+                format!("{}, prorated for allowance change", requirement),
+            )
+        } else {
+            (amount, requirement)
+        })
+    }
 }
 
 #[async_trait]
@@ -22,24 +266,63 @@ impl super::Task for AllowancePayer {
         tx: &mut Tx<'a>,
         timestamp: db::values::Timestamp,
     ) -> Result<(), super::Error> {
-        Ok(sqlx::query(Self::PAY)
-            .bind(db::values::TransactionType::Allowance)
-            .bind(timestamp)
-            .bind(db::values::Schedule::from(timestamp.0.weekday()))
-            .execute(tx.as_mut())
-            .await
-            .map(|_| ())?)
+        let date = timestamp.0.date_naive();
+        let mut allowances = Allowance::read_for_schedule(
+            tx,
+            &Schedule::from(timestamp.0.weekday()),
+        )
+        .await?;
+        for schedule in (1..=31)
+            .map(Schedule::Monthly)
+            .chain([Schedule::Daily])
+            .filter(|schedule| schedule.is_due(date))
+        {
+            allowances
+                .extend(Allowance::read_for_schedule(tx, &schedule).await?);
+        }
+        for allowance in allowances {
+            let (amount, description) =
+                Self::payment(tx, &allowance, timestamp).await?;
+            if amount == 0 {
+                continue;
+            }
+
+            if allowance.ledger_uid.is_some() {
+                Transaction::create_with_auto_uid(
+                    tx,
+                    TransactionType::Allowance,
+                    allowance.user_uid,
+                    allowance.ledger_uid,
+                    None,
+                    description,
+                    amount,
+                    timestamp,
+                )
+                .await?;
+            } else {
+                Transaction::create_split_with_auto_uid(
+                    tx,
+                    TransactionType::Allowance,
+                    allowance.user_uid,
+                    description,
+                    amount,
+                    timestamp,
+                )
+                .await?;
+            }
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use chrono::DateTime;
+    use chrono::{DateTime, Weekday};
     use weru::database::Entity;
 
-    use crate::db::entities::{Allowance, Transaction};
+    use crate::db::entities::{create, ChoreTemplate};
     use crate::db::test_engine;
-    use crate::db::values::{TransactionType, UID};
+    use crate::db::values::{ChoreStatus, Role, UID};
     use crate::tasks::Task;
 
     use super::*;
@@ -52,8 +335,16 @@ mod tests {
             DateTime::parse_from_rfc3339("1970-01-01T01:00:00Z").unwrap();
         let friday =
             DateTime::parse_from_rfc3339("1970-01-02T01:00:00Z").unwrap();
-        let allowance =
-            Allowance::new(UID::new(), UID::new(), 42, friday.weekday().into());
+        let allowance = Allowance::new(
+            UID::new(),
+            UID::new(),
+            None,
+            42,
+            friday.weekday().into(),
+            None,
+            false,
+            None,
+        );
 
         // Create the allowance
         let mut conn = database.connection().await.unwrap();
@@ -91,4 +382,438 @@ mod tests {
         assert_eq!(transactions[0].user_uid, allowance.user_uid);
         assert_eq!(transactions[0].amount, allowance.amount as i64);
     }
+
+    #[actix_rt::test]
+    async fn run_monthly() {
+        let database = test_engine().await;
+        let payer = AllowancePayer;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        create::allowance(&mut conn, &user.uid, 50, Schedule::Monthly(31));
+
+        for day in ["2024-02-28T01:00:00Z", "2024-02-29T01:00:00Z"] {
+            let mut tx = conn.begin().await.unwrap();
+            payer
+                .run(&mut tx, DateTime::parse_from_rfc3339(day).unwrap().into())
+                .await
+                .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let transactions = Transaction::list(conn.as_mut()).await.unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].amount, 50);
+    }
+
+    #[actix_rt::test]
+    async fn run_jars() {
+        let database = test_engine().await;
+        let payer = AllowancePayer;
+        let friday =
+            DateTime::parse_from_rfc3339("1970-01-02T01:00:00Z").unwrap();
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let spend = create::jar(&mut conn, &user.uid, "spend", 80);
+        let give = create::jar(&mut conn, &user.uid, "give", 20);
+        create::allowance(&mut conn, &user.uid, 50, friday.weekday().into());
+
+        {
+            let mut tx = conn.begin().await.unwrap();
+            payer.run(&mut tx, friday.into()).await.unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let mut tx = conn.begin().await.unwrap();
+        let transactions = Transaction::list(tx.as_mut()).await.unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert!(transactions
+            .iter()
+            .all(|t| t.transaction_type == TransactionType::Allowance));
+        assert_eq!(
+            Transaction::jar_balances(&mut tx, &user.uid).await.unwrap(),
+            [(spend.uid.clone(), 40), (give.uid.clone(), 10)]
+                .iter()
+                .cloned()
+                .collect(),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn run_required_chores() {
+        let database = test_engine().await;
+        let payer = AllowancePayer;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let template = ChoreTemplate::new(
+            UID::new(),
+            family.uid.clone(),
+            "Empty dishwasher".into(),
+            0,
+            Some(user.uid.clone()),
+            Schedule::Daily,
+            0,
+            at("2024-03-01T00:00:00Z"),
+            None,
+            true,
+        );
+        template.create(conn.as_mut()).await.unwrap();
+        for (day, status) in [
+            ("2024-03-01", ChoreStatus::Claimed),
+            ("2024-03-02", ChoreStatus::Approved),
+            ("2024-03-03", ChoreStatus::Approved),
+            ("2024-03-05", ChoreStatus::Approved),
+            ("2024-03-08", ChoreStatus::Missed),
+        ] {
+            let chore = template.instantiate(at(&format!("{}T08:00:00Z", day)));
+            Chore { status, ..chore }
+                .create(conn.as_mut())
+                .await
+                .unwrap();
+        }
+        let allowance = create::allowance(
+            &mut conn,
+            &user.uid,
+            100,
+            Schedule::Weekly(Weekday::Fri),
+        );
+
+        // 3 of the 4 chores due since the previous friday were done
+        let friday = at("2024-03-08T08:00:00Z");
+        let mut tx = conn.begin().await.unwrap();
+        assert_eq!(
+            AllowancePayer::payment(&mut tx, &allowance, friday)
+                .await
+                .unwrap(),
+            (100, String::new()),
+        );
+        let allowance = Allowance {
+            required_share: Some(100),
+            ..allowance
+        };
+        assert_eq!(
+            AllowancePayer::payment(&mut tx, &allowance, friday)
+                .await
+                .unwrap()
+                .0,
+            0,
+        );
+        let allowance = Allowance {
+            prorate: true,
+            ..allowance
+        };
+        assert_eq!(
+            AllowancePayer::payment(&mut tx, &allowance, friday)
+                .await
+                .unwrap()
+                .0,
+            75,
+        );
+        let allowance = Allowance {
+            required_share: Some(75),
+            ..allowance
+        };
+        assert_eq!(
+            AllowancePayer::payment(&mut tx, &allowance, friday)
+                .await
+                .unwrap(),
+            (100, "3 of 4 required chores done".into()),
+        );
+        allowance.update(tx.as_mut()).await.unwrap();
+        payer.run(&mut tx, friday).await.unwrap();
+        let transactions = Transaction::list(tx.as_mut()).await.unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].description, "3 of 4 required chores done");
+    }
+
+    #[actix_rt::test]
+    async fn adjust() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = User {
+            birth_date: Some("2015-06-03".parse().unwrap()),
+            ..create::user(
+                &mut conn,
+                Role::Child,
+                "User",
+                "test@example.com",
+                &family.uid,
+            )
+        };
+        user.update(conn.as_mut()).await.unwrap();
+        let allowance = Allowance {
+            age_scale: Some("100+25".parse().unwrap()),
+            ..create::allowance(
+                &mut conn,
+                &user.uid,
+                42,
+                "mon".parse().unwrap(),
+            )
+        };
+        allowance.update(conn.as_mut()).await.unwrap();
+        let fixed =
+            create::allowance(&mut conn, &user.uid, 42, "tue".parse().unwrap());
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+
+        let mut tx = conn.begin().await.unwrap();
+        for (time, amount) in [
+            ("2024-06-02T12:00:00Z", Some(300)),
+            ("2024-06-02T13:00:00Z", None),
+            ("2024-06-03T12:00:00Z", Some(325)),
+        ] {
+            let adjusted =
+                AllowanceAdjuster::process(&mut tx, at(time)).await.unwrap();
+            assert_eq!(
+                adjusted.iter().map(|(_, a)| a.amount).next(),
+                amount,
+                "{}",
+                time,
+            );
+        }
+        assert_eq!(
+            Allowance::read(tx.as_mut(), &allowance.uid)
+                .await
+                .unwrap()
+                .map(|allowance| allowance.amount),
+            Some(325),
+        );
+        assert_eq!(
+            Allowance::read(tx.as_mut(), &fixed.uid).await.unwrap(),
+            Some(fixed),
+        );
+        assert_eq!(
+            AllowanceChange::read_for_allowance(&mut tx, &allowance.uid)
+                .await
+                .unwrap()
+                .iter()
+                .map(|change| (change.previous_amount, change.amount))
+                .collect::<Vec<_>>(),
+            vec![(42, 300), (300, 325)],
+        );
+    }
+
+    #[actix_rt::test]
+    async fn adjust_scheduled() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let allowance =
+            create::allowance(&mut conn, &user.uid, 42, "mon".parse().unwrap());
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let change = AllowanceChange {
+            applied: false,
+            ..AllowanceChange::record(
+                &allowance,
+                50,
+                "tue".parse().unwrap(),
+                at("2024-06-05T00:00:00Z"),
+                false,
+                None,
+            )
+        };
+        change.create(conn.as_mut()).await.unwrap();
+
+        let mut tx = conn.begin().await.unwrap();
+        assert_eq!(
+            AllowanceAdjuster::process(&mut tx, at("2024-06-04T12:00:00Z"))
+                .await
+                .unwrap(),
+            vec![],
+        );
+        assert_eq!(
+            AllowanceAdjuster::process(&mut tx, at("2024-06-05T12:00:00Z"))
+                .await
+                .unwrap(),
+            vec![(
+                family.uid.clone(),
+                Allowance {
+                    amount: 50,
+                    schedule: "tue".parse().unwrap(),
+                    ..allowance.clone()
+                },
+            )],
+        );
+        assert_eq!(
+            AllowanceChange::read(tx.as_mut(), &change.uid)
+                .await
+                .unwrap()
+                .map(|change| change.applied),
+            Some(true),
+        );
+        assert_eq!(
+            AllowanceAdjuster::process(&mut tx, at("2024-06-06T12:00:00Z"))
+                .await
+                .unwrap(),
+            vec![],
+        );
+    }
+
+    #[actix_rt::test]
+    async fn adjust_scheduled_age_scale() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = User {
+            birth_date: Some("2015-06-03".parse().unwrap()),
+            ..create::user(
+                &mut conn,
+                Role::Child,
+                "User",
+                "test@example.com",
+                &family.uid,
+            )
+        };
+        user.update(conn.as_mut()).await.unwrap();
+        let previous =
+            create::allowance(&mut conn, &user.uid, 42, "mon".parse().unwrap());
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let change = AllowanceChange {
+            applied: false,
+            ..AllowanceChange::record(
+                &previous,
+                50,
+                "tue".parse().unwrap(),
+                at("2024-06-03T00:00:00Z"),
+                false,
+                None,
+            )
+        };
+        change.create(conn.as_mut()).await.unwrap();
+        let allowance = Allowance {
+            amount: 300,
+            age_scale: Some("100+25".parse().unwrap()),
+            ..previous
+        };
+        allowance.update(conn.as_mut()).await.unwrap();
+
+        let mut tx = conn.begin().await.unwrap();
+        assert_eq!(
+            AllowanceAdjuster::process(&mut tx, at("2024-06-03T12:00:00Z"))
+                .await
+                .unwrap(),
+            vec![(
+                family.uid.clone(),
+                Allowance {
+                    amount: 325,
+                    schedule: "tue".parse().unwrap(),
+                    ..allowance.clone()
+                },
+            )],
+        );
+        assert_eq!(
+            AllowanceChange::read_for_allowance(&mut tx, &allowance.uid)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|change| (
+                    change.previous_amount,
+                    change.previous_schedule,
+                    change.amount,
+                    change.schedule,
+                    change.applied,
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    300,
+                    "mon".parse().unwrap(),
+                    300,
+                    "tue".parse().unwrap(),
+                    true,
+                ),
+                (
+                    300,
+                    "tue".parse().unwrap(),
+                    325,
+                    "tue".parse().unwrap(),
+                    true,
+                ),
+            ],
+        );
+    }
+
+    #[actix_rt::test]
+    async fn payment_prorated() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let previous =
+            create::allowance(&mut conn, &user.uid, 100, Weekday::Fri.into());
+        let allowance = Allowance {
+            amount: 200,
+            ..previous.clone()
+        };
+        allowance.update(conn.as_mut()).await.unwrap();
+        let change = AllowanceChange::record(
+            &previous,
+            200,
+            Weekday::Fri.into(),
+            at("2024-06-04T00:00:00Z"),
+            true,
+            None,
+        );
+        change.create(conn.as_mut()).await.unwrap();
+
+        let mut tx = conn.begin().await.unwrap();
+        assert_eq!(
+            AllowancePayer::payment(
+                &mut tx,
+                &allowance,
+                at("2024-06-07T00:00:00Z"),
+            )
+            .await
+            .unwrap(),
+            (
+                (100 * 4 + 200 * 3) / 7,
+                "Prorated for allowance change".into(),
+            ),
+        );
+        assert_eq!(
+            AllowancePayer::payment(
+                &mut tx,
+                &allowance,
+                at("2024-06-14T00:00:00Z"),
+            )
+            .await
+            .unwrap(),
+            (200, String::new()),
+        );
+    }
 }
@@ -0,0 +1,188 @@
+use crate::prelude::*;
+
+use weru::async_trait::async_trait;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::db;
+use crate::db::entities::{BirthdayGift, FamilySettings, Transaction, User};
+use crate::db::values::TransactionType;
+
+/// Books gifts on the birthdays of children.
+///
+/// Birthdays are determined in the time zone of the family.
+pub struct BirthdayGiver {
+    /// The channel used to notify family members.
+    channel: web::Data<ChannelEngine>,
+}
+
+/// A birthday gift booked in a single run.
+pub struct Outcome {
+    /// The child whose birthday it is.
+    pub user: User,
+
+    /// The age of the child on the birthday.
+    pub age: u32,
+
+    /// The transactions booking the gift.
+    pub transactions: Vec<Transaction>,
+}
+
+impl BirthdayGiver {
+    /// Creates a new task.
+    ///
+    /// # Arguments
+    /// *  `channel` - The channel used to notify family members.
+    pub fn new(channel: web::Data<ChannelEngine>) -> Self {
+        Self { channel }
+    }
+
+    /// Books the gifts of all children having their birthdays.
+    ///
+    /// A gift is booked at most once per birthday.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `timestamp` - The current timestamp.
+    pub async fn process<'a>(
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<Vec<Outcome>, DatabaseError> {
+        let mut outcomes = Vec::new();
+        for gift in BirthdayGift::list(tx.as_mut()).await? {
+            let user = match User::read(tx.as_mut(), &gift.user_uid).await? {
+                Some(user) => user,
+                None => continue,
+            };
+            let birth_date = match user.birth_date {
+                Some(birth_date) => birth_date,
+                None => continue,
+            };
+            let settings =
+                FamilySettings::read_or_default(tx, &user.family_uid).await?;
+            let birthday =
+                match gift.due(&birth_date, settings.local_date(timestamp)) {
+                    Some(birthday) => birthday,
+                    None => continue,
+                };
+
+            let age = birth_date.years_until(birthday);
+            let transactions = api::transaction::book(
+                tx,
+                TransactionType::Gift,
+                user.uid.clone(),
+                None,
+                None,
+                format!("Birthday ({} years)", age),
+                gift.amount,
+                timestamp,
+            )
+            .await?;
+            BirthdayGift {
+                booked: Some(birthday.into()),
+                ..gift
+            }
+            .update(tx.as_mut())
+            .await?;
+            outcomes.push(Outcome {
+                user,
+                age,
+                transactions,
+            });
+        }
+        Ok(outcomes)
+    }
+}
+
+#[async_trait]
+impl super::Task for BirthdayGiver {
+    fn name(&self) -> &'static str {
+        "birthday-giver"
+    }
+
+    async fn run<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<(), super::Error> {
+        for outcome in Self::process(tx, timestamp).await? {
+            Notify::Family {
+                event: Event::Birthday {
+                    user_uid: outcome.user.uid,
+                    age: outcome.age,
+                    transactions: outcome.transactions,
+                },
+                family: outcome.user.family_uid,
+            }
+            .send_system(tx, &self.channel)
+            .await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weru::database::Entity;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::{Role, Timestamp};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn process() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = User {
+            birth_date: Some("2015-06-03".parse().unwrap()),
+            ..create::user(
+                &mut conn,
+                Role::Child,
+                "User",
+                "test@example.com",
+                &family.uid,
+            )
+        };
+        user.update(conn.as_mut()).await.unwrap();
+        BirthdayGift::new(user.uid.clone(), 5000, None)
+            .create(conn.as_mut())
+            .await
+            .unwrap();
+        FamilySettings {
+            utc_offset: -300,
+            ..FamilySettings::default_for(&family.uid)
+        }
+        .create(conn.as_mut())
+        .await
+        .unwrap();
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+
+        let mut tx = conn.begin().await.unwrap();
+        for (time, booked) in [
+            // It is still the day before in the time zone of the family
+            ("2024-06-03T02:00:00Z", false),
+            ("2024-06-03T12:00:00Z", true),
+            ("2024-06-04T02:00:00Z", false),
+            ("2024-06-04T12:00:00Z", false),
+        ] {
+            let outcomes =
+                BirthdayGiver::process(&mut tx, at(time)).await.unwrap();
+            assert_eq!(!outcomes.is_empty(), booked, "{}", time);
+            if booked {
+                assert_eq!(outcomes[0].user.uid, user.uid);
+                assert_eq!(outcomes[0].age, 9);
+                assert_eq!(outcomes[0].transactions[0].amount, 5000);
+            }
+        }
+        assert_eq!(
+            BirthdayGift::read(tx.as_mut(), &user.uid)
+                .await
+                .unwrap()
+                .and_then(|gift| gift.booked),
+            Some("2024-06-03".parse().unwrap()),
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use crate::prelude::*;
+
+use weru::async_trait::async_trait;
+
+use crate::api::notify::{Event, Notify};
+use crate::db;
+use crate::db::entities::{BonusRule, Transaction, User};
+use crate::db::values::TransactionType;
+
+/// Evaluates bonus rules, and books bonuses for completed streaks.
+///
+/// Every rule is evaluated once for each completed period, and only periods
+/// ending after the rule was created are considered.
+pub struct BonusPayer {
+    /// The channel used to notify family members.
+    channel: web::Data<ChannelEngine>,
+}
+
+impl BonusPayer {
+    /// Creates a new task.
+    ///
+    /// # Arguments
+    /// *  `channel` - The channel used to notify family members.
+    pub fn new(channel: web::Data<ChannelEngine>) -> Self {
+        Self { channel }
+    }
+
+    /// Evaluates all rules, and books bonuses for completed streaks.
+    ///
+    /// The rules for which a bonus was booked are returned along with the
+    /// bonus transactions.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `timestamp` - The current timestamp.
+    pub async fn process<'a>(
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<Vec<(BonusRule, Transaction)>, DatabaseError> {
+        let mut awarded = Vec::new();
+        for rule in BonusRule::list(tx.as_mut()).await? {
+            let start = rule.period.previous(timestamp);
+            let end = rule.period.start(timestamp);
+            if end.0 <= rule.created.0 || rule.evaluated == Some(start) {
+                continue;
+            }
+
+            let current = match rule.evaluate(tx, start, end).await? {
+                Some(true) => rule.current + 1,
+                Some(false) => 0,
+                None => rule.current,
+            };
+            if current >= rule.streak.max(1) {
+                let transaction = Transaction::create_with_auto_uid(
+                    tx,
+                    TransactionType::Bonus,
+                    rule.user_uid.clone(),
+                    None,
+                    None,
+                    rule.name.clone(),
+                    rule.amount,
+                    timestamp,
+                )
+                .await?;
+                let rule = BonusRule {
+                    current: 0,
+                    evaluated: Some(start),
+                    ..rule
+                };
+                rule.update(tx.as_mut()).await?;
+                awarded.push((rule, transaction));
+            } else {
+                BonusRule {
+                    current,
+                    evaluated: Some(start),
+                    ..rule
+                }
+                .update(tx.as_mut())
+                .await?;
+            }
+        }
+        Ok(awarded)
+    }
+}
+
+#[async_trait]
+impl super::Task for BonusPayer {
+    fn name(&self) -> &'static str {
+        "bonus-payer"
+    }
+
+    async fn run<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<(), super::Error> {
+        for (rule, transaction) in Self::process(tx, timestamp).await? {
+            let family_uid =
+                match User::read(tx.as_mut(), &rule.user_uid).await? {
+                    Some(user) => user.family_uid,
+                    None => continue,
+                };
+
+            Notify::MemberAndParents {
+                event: Event::BonusAwarded {
+                    rule: rule.clone(),
+                    transaction,
+                },
+                uid: rule.user_uid.clone(),
+                family: family_uid,
+            }
+            .send_system(tx, &self.channel)
+            .await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weru::database::Entity;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::{BonusKind, Period, Role, Timestamp, UID};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn process() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let rule = BonusRule::new(
+            UID::new(),
+            user.uid.clone(),
+            "Thrifty".into(),
+            BonusKind::NoSpending,
+            Period::Weekly,
+            2,
+            20,
+            0,
+            None,
+            at("2024-03-01T00:00:00Z"),
+        );
+        rule.create(conn.as_mut()).await.unwrap();
+        create::transaction(
+            &mut conn,
+            TransactionType::Gift,
+            &user.uid,
+            "candy",
+            -10,
+            at("2024-03-13T12:00:00Z"),
+        );
+
+        let mut tx = conn.begin().await.unwrap();
+        for (day, awarded, current) in [
+            // The week of 2024-02-26 ended after the rule was created
+            ("2024-03-04T08:00:00Z", false, 1),
+            ("2024-03-04T09:00:00Z", false, 1),
+            ("2024-03-11T08:00:00Z", true, 0),
+            // Something was spent during the week of 2024-03-11
+            ("2024-03-18T08:00:00Z", false, 0),
+            ("2024-03-25T08:00:00Z", false, 1),
+        ] {
+            let result = BonusPayer::process(&mut tx, at(day)).await.unwrap();
+            assert_eq!(!result.is_empty(), awarded, "{}", day);
+            if awarded {
+                assert_eq!(
+                    result[0].1.transaction_type,
+                    TransactionType::Bonus
+                );
+                assert_eq!(result[0].1.amount, 20);
+            }
+            assert_eq!(
+                BonusRule::read(tx.as_mut(), &rule.uid)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .current,
+                current,
+                "{}",
+                day,
+            );
+        }
+    }
+}
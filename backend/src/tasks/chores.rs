@@ -0,0 +1,269 @@
+use crate::prelude::*;
+
+use weru::async_trait::async_trait;
+
+use crate::api::notify::{Event, Notify};
+use crate::db;
+use crate::db::entities::{Chore, ChoreTemplate, Transaction};
+use crate::db::values::{ChoreStatus, TransactionType, UID};
+
+/// Generates chores from templates, and marks chores that were not claimed in
+/// time as missed.
+pub struct ChoreScheduler {
+    /// The channel used to notify family members.
+    channel: web::Data<ChannelEngine>,
+}
+
+/// The chores of a family handled in a single run.
+pub struct Outcome {
+    /// The family.
+    pub family_uid: UID,
+
+    /// The chores that were generated.
+    pub generated: Vec<Chore>,
+
+    /// The chores that were missed, and the penalties booked for them.
+    pub missed: Vec<(Chore, Option<Transaction>)>,
+}
+
+impl ChoreScheduler {
+    /// Creates a new task.
+    ///
+    /// # Arguments
+    /// *  `channel` - The channel used to notify family members.
+    pub fn new(channel: web::Data<ChannelEngine>) -> Self {
+        Self { channel }
+    }
+
+    /// Marks overdue chores as missed and generates chores from templates.
+    ///
+    /// Penalties are only booked for chores generated from a template with a
+    /// penalty, and assigned to a specific child.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `timestamp` - The current timestamp.
+    pub async fn process<'a>(
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<Vec<Outcome>, DatabaseError> {
+        let mut outcomes = Vec::new();
+        for chore in Chore::read_overdue(tx, timestamp).await? {
+            let template = match &chore.template_uid {
+                Some(template_uid) => {
+                    ChoreTemplate::read(tx.as_mut(), template_uid).await?
+                }
+                None => None,
+            };
+            let penalty = match (template, &chore.assignee_uid) {
+                (Some(template), Some(assignee_uid))
+                    if template.penalty > 0 =>
+                {
+                    Some(
+                        Transaction::create_with_auto_uid(
+                            tx,
+                            TransactionType::Penalty,
+                            assignee_uid.clone(),
+                            None,
+                            None,
+                            chore.title.clone(),
+                            -template.penalty,
+                            timestamp,
+                        )
+                        .await?,
+                    )
+                }
+                _ => None,
+            };
+            let chore = Chore {
+                status: ChoreStatus::Missed,
+                ..chore
+            };
+            chore.update(tx.as_mut()).await?;
+            outcome(&mut outcomes, &chore.family_uid)
+                .missed
+                .push((chore, penalty));
+        }
+
+        for template in ChoreTemplate::list(tx.as_mut()).await? {
+            if !template.is_due(timestamp) {
+                continue;
+            }
+
+            let chore = template.instantiate(timestamp);
+            chore.create(tx.as_mut()).await?;
+            ChoreTemplate {
+                generated: Some(timestamp),
+                ..template
+            }
+            .update(tx.as_mut())
+            .await?;
+            outcome(&mut outcomes, &chore.family_uid)
+                .generated
+                .push(chore);
+        }
+
+        Ok(outcomes)
+    }
+}
+
+/// Finds the outcome for a family, adding it if it does not yet exist.
+///
+/// # Arguments
+/// *  `outcomes` - The outcomes so far.
+/// *  `family_uid` - The family.
+fn outcome<'a>(
+    outcomes: &'a mut Vec<Outcome>,
+    family_uid: &UID,
+) -> &'a mut Outcome {
+    match outcomes.iter().position(|o| &o.family_uid == family_uid) {
+        Some(index) => &mut outcomes[index],
+        None => {
+            outcomes.push(Outcome {
+                family_uid: family_uid.clone(),
+                generated: Vec::new(),
+                missed: Vec::new(),
+            });
+            outcomes.last_mut().unwrap()
+        }
+    }
+}
+
+#[async_trait]
+impl super::Task for ChoreScheduler {
+    fn name(&self) -> &'static str {
+        "chore-scheduler"
+    }
+
+    async fn run<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<(), super::Error> {
+        for outcome in Self::process(tx, timestamp).await? {
+            for (chore, transaction) in &outcome.missed {
+                let event = Event::ChoreMissed {
+                    chore: chore.clone(),
+                    transaction: transaction.clone(),
+                };
+                match &chore.assignee_uid {
+                    Some(assignee_uid) => Notify::MemberAndParents {
+                        event,
+                        uid: assignee_uid.clone(),
+                        family: outcome.family_uid.clone(),
+                    },
+                    None => Notify::Parents {
+                        event,
+                        family: outcome.family_uid.clone(),
+                    },
+                }
+                .send_system(tx, &self.channel)
+                .await;
+            }
+            if !outcome.generated.is_empty() {
+                Notify::Family {
+                    event: Event::ChoresGenerated {
+                        chores: outcome.generated.clone(),
+                    },
+                    family: outcome.family_uid.clone(),
+                }
+                .send_system(tx, &self.channel)
+                .await;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weru::database::Entity;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::{Role, Schedule, Timestamp};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn process() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let template = ChoreTemplate::new(
+            UID::new(),
+            family.uid.clone(),
+            "Empty dishwasher".into(),
+            10,
+            Some(user.uid.clone()),
+            Schedule::Daily,
+            5,
+            at("2024-03-01T00:00:00Z"),
+            None,
+            false,
+        );
+        template.create(conn.as_mut()).await.unwrap();
+        let other =
+            create::chore(&mut conn, &family.uid, "Mow lawn", 100, None);
+        Chore {
+            due: Some(at("2024-03-01T12:00:00Z")),
+            ..other.clone()
+        }
+        .update(conn.as_mut())
+        .await
+        .unwrap();
+
+        let mut tx = conn.begin().await.unwrap();
+        let outcomes =
+            ChoreScheduler::process(&mut tx, at("2024-03-01T08:00:00Z"))
+                .await
+                .unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].family_uid, family.uid);
+        assert!(outcomes[0].missed.is_empty());
+        assert_eq!(outcomes[0].generated.len(), 1);
+        let first = outcomes[0].generated[0].clone();
+        assert_eq!(first.due, Some(at("2024-03-02T00:00:00Z")));
+
+        let outcomes =
+            ChoreScheduler::process(&mut tx, at("2024-03-01T09:00:00Z"))
+                .await
+                .unwrap();
+        assert!(outcomes.is_empty());
+
+        let outcomes =
+            ChoreScheduler::process(&mut tx, at("2024-03-02T08:00:00Z"))
+                .await
+                .unwrap();
+        assert_eq!(outcomes[0].generated.len(), 1);
+        assert_eq!(outcomes[0].missed.len(), 2);
+        let (missed, penalty) = outcomes[0]
+            .missed
+            .iter()
+            .find(|(chore, _)| chore.uid == first.uid)
+            .unwrap();
+        assert_eq!(missed.status, ChoreStatus::Missed);
+        let penalty = penalty.as_ref().unwrap();
+        assert_eq!(penalty.transaction_type, TransactionType::Penalty);
+        assert_eq!(penalty.user_uid, user.uid);
+        assert_eq!(penalty.amount, -5);
+        assert!(outcomes[0].missed.iter().any(|(chore, penalty)| chore.uid
+            == other.uid
+            && penalty.is_none()));
+        assert_eq!(
+            Chore::read_for_family(&mut tx, &family.uid)
+                .await
+                .unwrap()
+                .len(),
+            1,
+        );
+    }
+}
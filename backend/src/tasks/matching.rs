@@ -0,0 +1,222 @@
+use crate::prelude::*;
+
+use weru::async_trait::async_trait;
+
+use crate::db;
+use crate::db::entities::{Goal, MatchRule, RuleMatch, Transaction};
+use crate::db::values::TransactionType;
+
+/// Books parent contributions for matching rules.
+///
+/// Every rule is evaluated once for each completed period, and only periods
+/// ending after the rule was created are considered.
+pub struct MatchPayer;
+
+#[async_trait]
+impl super::Task for MatchPayer {
+    fn name(&self) -> &'static str {
+        "match-payer"
+    }
+
+    async fn run<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<(), super::Error> {
+        for rule in MatchRule::list(tx.as_mut()).await? {
+            let start = rule.period.previous(timestamp);
+            let end = rule.period.start(timestamp);
+            if end.0 <= rule.created.0
+                || RuleMatch::read_for_rule(tx, &rule.uid, start)
+                    .await?
+                    .is_some()
+            {
+                continue;
+            }
+
+            let amount = rule.evaluate(tx, start, end).await?;
+            if amount <= 0 {
+                continue;
+            }
+
+            let transaction = Transaction::create_with_auto_uid(
+                tx,
+                TransactionType::Match,
+                rule.user_uid.clone(),
+                None,
+                rule.jar_uid.clone(),
+                rule.name.clone(),
+                amount,
+                timestamp,
+            )
+            .await?;
+            RuleMatch::new(transaction.uid, rule.uid.clone(), start)
+                .create(tx.as_mut())
+                .await?;
+
+            if let Some(goal_uid) = &rule.goal_uid {
+                if let Some(goal) = Goal::read(tx.as_mut(), goal_uid)
+                    .await?
+                    .filter(|goal| goal.completed.is_none())
+                {
+                    Goal {
+                        earmarked: goal.amount.min(goal.earmarked + amount),
+                        ..goal
+                    }
+                    .update(tx.as_mut())
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration};
+    use weru::database::Entity;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::{MatchKind, Period, Role, Timestamp, UID};
+    use crate::tasks::Task;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn run_simple() {
+        let database = test_engine().await;
+        let payer = MatchPayer;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let goal = create::goal(&mut conn, &user.uid, "Bicycle", 1000);
+        let created: Timestamp =
+            DateTime::parse_from_rfc3339("2024-02-10T12:00:00Z")
+                .unwrap()
+                .into();
+        let now: Timestamp =
+            DateTime::parse_from_rfc3339("2024-03-02T12:00:00Z")
+                .unwrap()
+                .into();
+        create::transaction(
+            &mut conn,
+            TransactionType::Gift,
+            &user.uid,
+            "gift",
+            250,
+            (created.0 - Duration::days(1)).into(),
+        );
+        create::transaction(
+            &mut conn,
+            TransactionType::Gift,
+            &user.uid,
+            "gift",
+            1000,
+            (now.0 - Duration::hours(1)).into(),
+        );
+        let rule = MatchRule::new(
+            UID::new(),
+            user.uid.clone(),
+            "Savings bonus".into(),
+            MatchKind::Savings,
+            10,
+            1,
+            None,
+            Period::Monthly,
+            None,
+            Some(goal.uid.clone()),
+            created,
+        );
+        rule.create(conn.as_mut()).await.unwrap();
+
+        for _ in 0..2 {
+            let mut tx = conn.begin().await.unwrap();
+            payer.run(&mut tx, now).await.unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let mut tx = conn.begin().await.unwrap();
+        let matches = Transaction::list(tx.as_mut())
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|t| t.transaction_type == TransactionType::Match)
+            .collect::<Vec<_>>();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].amount, 25);
+        assert_eq!(
+            RuleMatch::read(tx.as_mut(), &matches[0].uid)
+                .await
+                .unwrap()
+                .map(|m| m.period),
+            Some("2024-02-01T00:00:00Z".parse().unwrap()),
+        );
+        assert_eq!(
+            Goal::read(tx.as_mut(), &goal.uid)
+                .await
+                .unwrap()
+                .unwrap()
+                .earmarked,
+            25,
+        );
+    }
+
+    #[actix_rt::test]
+    async fn run_too_new() {
+        let database = test_engine().await;
+        let payer = MatchPayer;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let now: Timestamp =
+            DateTime::parse_from_rfc3339("2024-03-02T12:00:00Z")
+                .unwrap()
+                .into();
+        create::transaction(
+            &mut conn,
+            TransactionType::Gift,
+            &user.uid,
+            "gift",
+            250,
+            (now.0 - Duration::days(5)).into(),
+        );
+        MatchRule::new(
+            UID::new(),
+            user.uid.clone(),
+            "Savings bonus".into(),
+            MatchKind::Savings,
+            10,
+            1,
+            None,
+            Period::Monthly,
+            None,
+            None,
+            (now.0 - Duration::hours(1)).into(),
+        )
+        .create(conn.as_mut())
+        .await
+        .unwrap();
+
+        let mut tx = conn.begin().await.unwrap();
+        payer.run(&mut tx, now).await.unwrap();
+        assert!(Transaction::list(tx.as_mut())
+            .await
+            .unwrap()
+            .iter()
+            .all(|t| t.transaction_type != TransactionType::Match));
+    }
+}
@@ -13,6 +13,13 @@ use weru::log;
 use crate::db::values::Timestamp;
 
 pub mod allowance;
+pub mod birthdays;
+pub mod bonuses;
+pub mod chores;
+pub mod matching;
+pub mod pending;
+pub mod requests;
+pub mod subscriptions;
 
 /// A repeating collection of tasks.
 pub struct Scheduled {
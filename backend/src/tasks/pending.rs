@@ -0,0 +1,172 @@
+use crate::prelude::*;
+
+use weru::async_trait::async_trait;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::db;
+use crate::db::entities::{PendingTransaction, Transaction, User};
+
+/// Books pending transactions that are due.
+///
+/// The transactions are booked with their due time, and the pending
+/// transactions are removed.
+pub struct PendingTransactionBooker {
+    /// The channel used to notify family members.
+    channel: web::Data<ChannelEngine>,
+}
+
+impl PendingTransactionBooker {
+    /// Creates a new task.
+    ///
+    /// # Arguments
+    /// *  `channel` - The channel used to notify family members.
+    pub fn new(channel: web::Data<ChannelEngine>) -> Self {
+        Self { channel }
+    }
+
+    /// Books all pending transactions that are due.
+    ///
+    /// The pending transactions are returned along with the transactions
+    /// booked for them.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `timestamp` - The current timestamp.
+    pub async fn process<'a>(
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<Vec<(PendingTransaction, Vec<Transaction>)>, DatabaseError>
+    {
+        let mut booked = Vec::new();
+        for pending in PendingTransaction::read_due(tx, timestamp).await? {
+            let transactions = api::transaction::book(
+                tx,
+                pending.transaction_type,
+                pending.user_uid.clone(),
+                pending.ledger_uid.clone(),
+                None,
+                pending.description.clone(),
+                pending.amount,
+                pending.due,
+            )
+            .await?;
+            pending.delete(tx.as_mut()).await?;
+            booked.push((pending, transactions));
+        }
+        Ok(booked)
+    }
+}
+
+#[async_trait]
+impl super::Task for PendingTransactionBooker {
+    fn name(&self) -> &'static str {
+        "pending-transaction-booker"
+    }
+
+    async fn run<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<(), super::Error> {
+        for (pending, transactions) in Self::process(tx, timestamp).await? {
+            let family_uid =
+                match User::read(tx.as_mut(), &pending.user_uid).await? {
+                    Some(user) => user.family_uid,
+                    None => continue,
+                };
+
+            Notify::MemberAndParents {
+                event: Event::PendingTransactionBooked {
+                    pending: pending.clone(),
+                    transactions,
+                },
+                uid: pending.user_uid.clone(),
+                family: family_uid,
+            }
+            .send_system(tx, &self.channel)
+            .await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::{Role, Timestamp, TransactionType};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn process() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let at = |s: &str| -> Timestamp { s.parse().unwrap() };
+        let gift = create::pending_transaction(
+            &mut conn,
+            &user.uid,
+            "Birthday",
+            5000,
+            at("2024-06-03T00:00:00Z"),
+        );
+        let payback = create::pending_transaction(
+            &mut conn,
+            &user.uid,
+            "Pay back",
+            -1000,
+            at("2024-06-07T00:00:00Z"),
+        );
+
+        let mut tx = conn.begin().await.unwrap();
+        let booked = PendingTransactionBooker::process(
+            &mut tx,
+            at("2024-06-01T08:00:00Z"),
+        )
+        .await
+        .unwrap();
+        assert!(booked.is_empty());
+
+        let booked = PendingTransactionBooker::process(
+            &mut tx,
+            at("2024-06-03T08:00:00Z"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(booked.len(), 1);
+        assert_eq!(booked[0].0, gift);
+        assert_eq!(booked[0].1.len(), 1);
+        assert_eq!(booked[0].1[0].transaction_type, TransactionType::Gift);
+        assert_eq!(booked[0].1[0].amount, 5000);
+        assert_eq!(booked[0].1[0].time, gift.due);
+
+        let booked = PendingTransactionBooker::process(
+            &mut tx,
+            at("2024-06-04T08:00:00Z"),
+        )
+        .await
+        .unwrap();
+        assert!(booked.is_empty());
+        assert_eq!(
+            PendingTransaction::read_for_user(&mut tx, &user.uid)
+                .await
+                .unwrap(),
+            vec![payback],
+        );
+        assert_eq!(
+            Transaction::balance(&mut tx, &user.uid, None)
+                .await
+                .unwrap(),
+            Some(5000),
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use crate::prelude::*;
+
+use chrono::Duration;
+use weru::async_trait::async_trait;
+use weru::email::Sender;
+use weru::log;
+
+use crate::api;
+use crate::api::notify::{Event, Notify};
+use crate::configuration::FamilyConfiguration;
+use crate::db;
+use crate::db::entities::{FamilySettings, Request, RequestReminder, User};
+use crate::db::values::{RequestStatus, Role, UID};
+
+/// The name of the template used for reminder emails.
+const TEMPLATE: &'static str = &"request-reminder";
+
+/// Reminds parents of pending requests, and expires requests that have been
+/// pending for too long.
+///
+/// The delays are configured per family in the family settings.
+pub struct RequestExpirer {
+    /// The channel used to notify family members.
+    channel: web::Data<ChannelEngine>,
+
+    /// The sender used for reminder emails.
+    sender: web::Data<Box<dyn Sender>>,
+
+    /// The URL of the server, used in emails.
+    server_url: String,
+
+    /// The configuration of families without one of their own, used to format
+    /// amounts in emails.
+    defaults: FamilyConfiguration,
+}
+
+/// The requests of a family handled in a single run.
+pub struct Outcome {
+    /// The family.
+    pub family_uid: UID,
+
+    /// The requests that expired.
+    pub expired: Vec<Request>,
+
+    /// The requests of which parents should be reminded.
+    pub reminded: Vec<Request>,
+}
+
+impl RequestExpirer {
+    /// Creates a new task.
+    ///
+    /// # Arguments
+    /// *  `channel` - The channel used to notify family members.
+    /// *  `sender` - The sender used for reminder emails.
+    /// *  `server_url` - The URL of the server.
+    /// *  `defaults` - The default family configuration.
+    pub fn new(
+        channel: web::Data<ChannelEngine>,
+        sender: web::Data<Box<dyn Sender>>,
+        server_url: String,
+        defaults: FamilyConfiguration,
+    ) -> Self {
+        Self {
+            channel,
+            sender,
+            server_url,
+            defaults,
+        }
+    }
+
+    /// Expires old requests and records reminders in the database.
+    ///
+    /// A reminder is recorded at most once per request, and expired requests
+    /// are not reminded of.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `timestamp` - The current timestamp.
+    pub async fn process<'a>(
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<Vec<Outcome>, DatabaseError> {
+        let mut outcomes = Vec::new();
+        for settings in FamilySettings::list(tx.as_mut()).await? {
+            if settings.request_reminder.is_none()
+                && settings.request_expiry.is_none()
+            {
+                continue;
+            }
+
+            let mut outcome = Outcome {
+                family_uid: settings.family_uid.clone(),
+                expired: Vec::new(),
+                reminded: Vec::new(),
+            };
+            let older_than = |days: Option<i64>, request: &Request| {
+                days.map(|days| {
+                    timestamp.0 - request.time.0 >= Duration::days(days)
+                })
+                .unwrap_or(false)
+            };
+            for request in
+                Request::read_for_family(tx, &settings.family_uid).await?
+            {
+                if older_than(settings.request_expiry, &request) {
+                    let request =
+                        request.decide(RequestStatus::Expired, None, None);
+                    request.update(tx.as_mut()).await?;
+                    outcome.expired.push(request);
+                } else if older_than(settings.request_reminder, &request)
+                    && RequestReminder::read(tx.as_mut(), &request.uid)
+                        .await?
+                        .is_none()
+                {
+                    RequestReminder::new(request.uid, timestamp)
+                        .create(tx.as_mut())
+                        .await?;
+                    outcome.reminded.push(request);
+                }
+            }
+            outcomes.push(outcome);
+        }
+        Ok(outcomes)
+    }
+
+    /// Sends reminder emails to all parents of a family.
+    ///
+    /// Errors are logged and then ignored.
+    ///
+    /// # Arguments
+    /// *  `tx` - The database transaction.
+    /// *  `outcome` - The outcome for the family.
+    async fn email<'a>(&self, tx: &mut Tx<'a>, outcome: &Outcome) {
+        let members = match User::read_by_family(tx, &outcome.family_uid).await
+        {
+            Ok(members) => members,
+            Err(e) => {
+                log::warn!("failed to load family for reminder: {}", e);
+                return;
+            }
+        };
+        let configuration =
+            match FamilyConfiguration::read(tx, &outcome.family_uid).await {
+                Ok(configuration) => {
+                    configuration.unwrap_or_else(|| self.defaults.clone())
+                }
+                Err(e) => {
+                    log::warn!(
+                        "failed to load configuration for reminder: {}",
+                        e
+                    );
+                    return;
+                }
+            };
+        let requests = outcome
+            .reminded
+            .iter()
+            .map(|request| {
+                format!(
+                    "*  {}, {} ({})",
+                    request.name,
+                    configuration.currency().format_amount(request.amount),
+                    members
+                        .iter()
+                        .find(|u| u.uid == request.user_uid)
+                        .map(|u| u.name.as_str())
+                        .unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        for parent in members.iter().filter(|u| u.role == Role::Parent) {
+            let mailbox = match parent
+                .email
+                .as_ref()
+                .and_then(|email| api::mailbox(&parent.name, email))
+            {
+                Some(mailbox) => mailbox,
+                None => continue,
+            };
+            if let Err(e) = self
+                .sender
+                .send(
+                    mailbox.into(),
+                    &[],
+                    &TEMPLATE.into(),
+                    &[
+                        (String::from("user.name"), parent.name.clone()),
+                        (String::from("requests"), requests.clone()),
+                        (String::from("server.url"), self.server_url.clone()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                )
+                .await
+            {
+                log::warn!("failed to send reminder to {}: {}", parent.uid, e);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl super::Task for RequestExpirer {
+    fn name(&self) -> &'static str {
+        "request-expirer"
+    }
+
+    async fn run<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<(), super::Error> {
+        for outcome in Self::process(tx, timestamp).await? {
+            for request in &outcome.expired {
+                Notify::MemberAndParents {
+                    event: Event::RequestExpired {
+                        request: request.clone(),
+                    },
+                    uid: request.user_uid.clone(),
+                    family: outcome.family_uid.clone(),
+                }
+                .send_system(tx, &self.channel)
+                .await;
+            }
+            if !outcome.reminded.is_empty() {
+                Notify::Parents {
+                    event: Event::RequestReminder {
+                        requests: outcome.reminded.clone(),
+                    },
+                    family: outcome.family_uid.clone(),
+                }
+                .send_system(tx, &self.channel)
+                .await;
+                self.email(tx, &outcome).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weru::database::Entity;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::Timestamp;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn process() {
+        let database = test_engine().await;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let other_family = create::family(&mut conn, "Other Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let other_user = create::user(
+            &mut conn,
+            Role::Child,
+            "Other User",
+            "other@example.com",
+            &other_family.uid,
+        );
+        let request = create::request(
+            &mut conn,
+            &user.uid,
+            "Request",
+            "A description",
+            42,
+            "https://example.com",
+        );
+        create::request(
+            &mut conn,
+            &other_user.uid,
+            "Other Request",
+            "A description",
+            42,
+            "https://example.com",
+        );
+        FamilySettings {
+            request_reminder: Some(3),
+            request_expiry: Some(14),
+            ..FamilySettings::default_for(&family.uid)
+        }
+        .create(conn.as_mut())
+        .await
+        .unwrap();
+        let after = |days| -> Timestamp {
+            (request.time.0 + Duration::days(days)).into()
+        };
+
+        let mut tx = conn.begin().await.unwrap();
+        let outcomes =
+            RequestExpirer::process(&mut tx, after(1)).await.unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert!(outcomes[0].expired.is_empty());
+        assert!(outcomes[0].reminded.is_empty());
+
+        let outcomes =
+            RequestExpirer::process(&mut tx, after(4)).await.unwrap();
+        assert_eq!(outcomes[0].family_uid, family.uid);
+        assert!(outcomes[0].expired.is_empty());
+        assert_eq!(outcomes[0].reminded, vec![request.clone()]);
+
+        let outcomes =
+            RequestExpirer::process(&mut tx, after(5)).await.unwrap();
+        assert!(outcomes[0].reminded.is_empty());
+
+        let outcomes =
+            RequestExpirer::process(&mut tx, after(14)).await.unwrap();
+        assert_eq!(outcomes[0].expired.len(), 1);
+        assert_eq!(outcomes[0].expired[0].status, RequestStatus::Expired);
+        assert_eq!(outcomes[0].expired[0].decided_by, None);
+        assert_eq!(
+            Request::read(tx.as_mut(), &request.uid)
+                .await
+                .unwrap()
+                .map(|r| r.status),
+            Some(RequestStatus::Expired),
+        );
+        assert_eq!(
+            Request::read_for_family(&mut tx, &other_family.uid)
+                .await
+                .unwrap()
+                .len(),
+            1,
+        );
+    }
+}
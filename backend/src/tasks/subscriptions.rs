@@ -0,0 +1,237 @@
+use crate::prelude::*;
+
+use weru::async_trait::async_trait;
+
+use crate::db;
+use crate::db::entities::{Subscription, Transaction};
+use crate::db::values::TransactionType;
+
+/// Books the deductions of active subscriptions that are due.
+pub struct SubscriptionPayer;
+
+#[async_trait]
+impl super::Task for SubscriptionPayer {
+    fn name(&self) -> &'static str {
+        "subscription-payer"
+    }
+
+    async fn run<'a>(
+        &self,
+        tx: &mut Tx<'a>,
+        timestamp: db::values::Timestamp,
+    ) -> Result<(), super::Error> {
+        for subscription in Subscription::read_active(tx, timestamp).await? {
+            if !subscription.is_due(timestamp) {
+                continue;
+            }
+
+            Transaction::create_with_auto_uid(
+                tx,
+                TransactionType::Subscription,
+                subscription.user_uid.clone(),
+                subscription.ledger_uid.clone(),
+                subscription.jar_uid.clone(),
+                subscription.name.clone(),
+                -subscription.amount,
+                timestamp,
+            )
+            .await?;
+            Subscription {
+                booked: Some(timestamp),
+                ..subscription
+            }
+            .update(tx.as_mut())
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use weru::database::Entity;
+
+    use crate::db::entities::create;
+    use crate::db::test_engine;
+    use crate::db::values::{Role, Schedule, Timestamp, UID};
+    use crate::tasks::Task;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn run_simple() {
+        let database = test_engine().await;
+        let payer = SubscriptionPayer;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let request = create::request(
+            &mut conn,
+            &user.uid,
+            "Game",
+            "A game subscription",
+            100,
+            "https://example.com",
+        );
+        let at = |s| -> Timestamp {
+            DateTime::parse_from_rfc3339(s).unwrap().into()
+        };
+        let subscription = Subscription::new(
+            UID::new(),
+            user.uid.clone(),
+            request.uid,
+            None,
+            None,
+            "Game".into(),
+            100,
+            Schedule::Monthly(1),
+            Some(at("2024-04-15T00:00:00Z")),
+            false,
+            None,
+            None,
+        );
+        subscription.create(conn.as_mut()).await.unwrap();
+
+        for day in [
+            "2024-02-29T12:00:00Z",
+            "2024-03-01T01:00:00Z",
+            "2024-03-01T12:00:00Z",
+            "2024-04-01T12:00:00Z",
+            "2024-05-01T12:00:00Z",
+        ] {
+            let mut tx = conn.begin().await.unwrap();
+            payer.run(&mut tx, at(day)).await.unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let mut tx = conn.begin().await.unwrap();
+        let transactions = Transaction::list(tx.as_mut()).await.unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert!(transactions.iter().all(|t| {
+            t.transaction_type == TransactionType::Subscription
+                && t.amount == -100
+        }));
+        assert_eq!(
+            Subscription::read(tx.as_mut(), &subscription.uid)
+                .await
+                .unwrap()
+                .unwrap()
+                .booked,
+            Some(at("2024-04-01T12:00:00Z")),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn run_paused() {
+        let database = test_engine().await;
+        let payer = SubscriptionPayer;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let request = create::request(
+            &mut conn,
+            &user.uid,
+            "Game",
+            "A game subscription",
+            100,
+            "https://example.com",
+        );
+        Subscription::new(
+            UID::new(),
+            user.uid.clone(),
+            request.uid,
+            None,
+            None,
+            "Game".into(),
+            100,
+            Schedule::Monthly(1),
+            None,
+            true,
+            None,
+            None,
+        )
+        .create(conn.as_mut())
+        .await
+        .unwrap();
+
+        let mut tx = conn.begin().await.unwrap();
+        payer
+            .run(
+                &mut tx,
+                DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z")
+                    .unwrap()
+                    .into(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(Transaction::list(tx.as_mut()).await.unwrap(), Vec::new());
+    }
+
+    #[actix_rt::test]
+    async fn run_jar() {
+        let database = test_engine().await;
+        let payer = SubscriptionPayer;
+        let mut conn = database.connection().await.unwrap();
+        let family = create::family(&mut conn, "Family");
+        let user = create::user(
+            &mut conn,
+            Role::Child,
+            "User",
+            "test@example.com",
+            &family.uid,
+        );
+        let jar = create::jar(&mut conn, &user.uid, "Games", 100);
+        let request = create::request(
+            &mut conn,
+            &user.uid,
+            "Game",
+            "A game subscription",
+            100,
+            "https://example.com",
+        );
+        Subscription::new(
+            UID::new(),
+            user.uid.clone(),
+            request.uid,
+            None,
+            Some(jar.uid.clone()),
+            "Game".into(),
+            100,
+            Schedule::Monthly(1),
+            None,
+            false,
+            None,
+            None,
+        )
+        .create(conn.as_mut())
+        .await
+        .unwrap();
+
+        let mut tx = conn.begin().await.unwrap();
+        payer
+            .run(
+                &mut tx,
+                DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z")
+                    .unwrap()
+                    .into(),
+            )
+            .await
+            .unwrap();
+        let transactions = Transaction::list(tx.as_mut()).await.unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].jar_uid, Some(jar.uid));
+    }
+}
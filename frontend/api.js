@@ -1,3 +1,4 @@
+import * as currencies from "./currency.js";
 import { translate as _ } from "./translation.js";
 
 /**
@@ -51,6 +52,32 @@ const module = {
                 amount,
                 description}),
 
+        /**
+         * Creates transactions for several children at once.
+         *
+         * @param state
+         *     The application state.
+         * @param type
+         *     The transaction type.
+         * @param description
+         *     A description of the transactions.
+         * @param recipients
+         *     The children on the format `[{user_uid, amount}]`, where
+         *     `amount` is optional and overrides the common amount.
+         * @param amounts
+         *     An object on the format `{amount, total}`, where `amount` is the
+         *     common amount, and `total` an amount split evenly between all
+         *     children instead.
+         */
+        bulk: (state, type, description, recipients, amounts) => module.post(
+            "transactions/{}".format(state.family.uid), {
+                transaction_type: type,
+                description,
+                recipients,
+                amount: amounts?.amount,
+                total: amounts?.total,
+            }),
+
         /**
          * Lists transactions for a user.
          *
@@ -66,9 +93,496 @@ const module = {
          */
         list: (state, user, offset, limit) => module.get(
             "transaction/{}?offset={}&limit={}".format(user, offset, limit)),
+
+        /**
+         * Schedules a transaction to be booked at a later time.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the user involved in the transaction.
+         * @param type
+         *     The transaction type.
+         * @param amount
+         *     The amount.
+         * @param description
+         *     A description of the transaction.
+         * @param due
+         *     The time at which the transaction is booked.
+         */
+        schedule: (state, user, type, amount, description, due) =>
+            module.post("transaction/{}/pending".format(user), {
+                transaction_type: type,
+                amount,
+                description,
+                due}),
+
+        /**
+         * Lists the pending transactions for a user.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the user involved in the transactions.
+         */
+        pending: (state, user) => module.get(
+            "transaction/{}/pending".format(user)),
+
+        /**
+         * Changes a pending transaction.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the user involved in the transaction.
+         * @param uid
+         *     The unique ID of the pending transaction.
+         * @param changes
+         *     The fields to change, such as `amount`, `description` and
+         *     `due`.
+         */
+        reschedule: (state, user, uid, changes) => module.put(
+            "transaction/{}/pending/{}".format(user, uid), changes),
+
+        /**
+         * Cancels a pending transaction.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the user involved in the transaction.
+         * @param uid
+         *     The unique ID of the pending transaction.
+         */
+        cancel: (state, user, uid) => module.remove(
+            "transaction/{}/pending/{}".format(user, uid)),
+    },
+
+    goal: {
+        /**
+         * Creates a savings goal.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child saving towards the goal.
+         * @param goal
+         *     The goal. This must contain at least `name` and `amount`.
+         */
+        create: (state, user, goal) => module.post(
+            "goal/{}".format(user), goal),
+
+        /**
+         * Updates a savings goal.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child saving towards the goal.
+         * @param uid
+         *     The unique ID of the goal.
+         * @param goal
+         *     The changed fields of the goal.
+         */
+        update: (state, user, uid, goal) => module.put(
+            "goal/{}/{}".format(user, uid), goal),
+
+        /**
+         * Marks a savings goal as completed.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child saving towards the goal.
+         * @param uid
+         *     The unique ID of the goal.
+         */
+        complete: (state, user, uid) => module.post(
+            "goal/{}/{}/complete".format(user, uid), {}),
+    },
+
+    ledger: {
+        /**
+         * Creates a non-monetary ledger for the family.
+         *
+         * @param state
+         *     The application state.
+         * @param name
+         *     The ledger name.
+         * @param unit
+         *     The unit of values in the ledger.
+         */
+        create: (state, name, unit) => module.post(
+            "ledger/{}".format(state.family.uid), {
+                name,
+                unit}),
+
+        /**
+         * Removes a non-monetary ledger, and all its transactions.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the ledger.
+         */
+        remove: (state, uid) => module.remove(
+            "ledger/{}/{}".format(state.family.uid, uid)),
+    },
+
+    matching: {
+        /**
+         * Creates a savings matching rule for a child.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child.
+         * @param rule
+         *     The rule, with the fields `name`, `kind`, `per`, `matched`,
+         *     `period` and optionally `cap`, `jar_uid` and `goal_uid`.
+         */
+        create: (state, user, rule) => module.post(
+            "match/{}".format(user), rule),
+
+        /**
+         * Previews the contributions of the matching rules of a child for
+         * the current period.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child.
+         */
+        preview: (state, user) => module.get(
+            "match/{}/preview".format(user)),
+
+        /**
+         * Removes a savings matching rule.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child.
+         * @param uid
+         *     The unique ID of the rule.
+         */
+        remove: (state, user, uid) => module.remove(
+            "match/{}/{}".format(user, uid)),
+    },
+
+    bonus: {
+        /**
+         * Creates a bonus rule for a child.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child.
+         * @param rule
+         *     The rule, with the fields `name`, `kind` (`chores` or
+         *     `no-spending`), `period`, `amount` and optionally `streak`.
+         */
+        create: (state, user, rule) => module.post(
+            "bonus/{}".format(user), rule),
+
+        /**
+         * Removes a bonus rule.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child.
+         * @param uid
+         *     The unique ID of the rule.
+         */
+        remove: (state, user, uid) => module.remove(
+            "bonus/{}/{}".format(user, uid)),
+    },
+
+    fine: {
+        /**
+         * Fines a child for one of the reasons of the family.
+         *
+         * @param state
+         *     The application state.
+         * @param reason
+         *     The unique ID of the reason.
+         * @param user
+         *     The unique ID of the child.
+         * @param amount
+         *     The amount of the fine, or `null` for the default amount of
+         *     the reason.
+         */
+        apply: (state, reason, user, amount) => module.post(
+            "fine/{}/{}/apply".format(state.family.uid, reason), {
+                user_uid: user,
+                amount,
+            }),
+
+        /**
+         * Adds a reason for fines.
+         *
+         * @param state
+         *     The application state.
+         * @param name
+         *     The name of the reason.
+         * @param amount
+         *     The default amount of fines for this reason.
+         */
+        create: (state, name, amount) => module.post(
+            "fine/{}".format(state.family.uid), {name, amount}),
+
+        /**
+         * Removes a reason for fines.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the reason.
+         */
+        remove: (state, uid) => module.remove(
+            "fine/{}/{}".format(state.family.uid, uid)),
+    },
+
+    giftLink: {
+        /**
+         * Confirms receipt of a gift recorded through a gift link, which
+         * books the gift.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the link.
+         * @param amount
+         *     The amount actually received, or `null` for the recorded
+         *     amount.
+         */
+        confirm: (state, uid, amount) => module.post(
+            "gift-link/{}/{}/confirm".format(state.family.uid, uid), {
+                amount,
+            }),
+
+        /**
+         * Creates a gift link for a child.
+         *
+         * The unique ID of the returned link is the token to share.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child.
+         * @param expires
+         *     The time after which the link can no longer be used.
+         */
+        create: (state, user, expires) => module.post(
+            "gift-link/{}".format(state.family.uid), {
+                user_uid: user,
+                expires,
+            }),
+
+        /**
+         * Gets the public information about a gift link.
+         *
+         * This does not require a session.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the link.
+         */
+        get: (state, uid) => module.get("gift/{}".format(uid)),
+
+        /**
+         * Records a gift through a gift link.
+         *
+         * This does not require a session.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the link.
+         * @param giver
+         *     The name of the giver.
+         * @param message
+         *     A message for the child.
+         * @param amount
+         *     The amount of the gift.
+         */
+        record: (state, uid, giver, message, amount) => module.post(
+            "gift/{}".format(uid), {giver, message, amount}),
+
+        /**
+         * Removes a gift link.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the link.
+         */
+        remove: (state, uid) => module.remove(
+            "gift-link/{}/{}".format(state.family.uid, uid)),
+    },
+
+    subscription: {
+        /**
+         * Cancels a subscription.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child paying for the subscription.
+         * @param uid
+         *     The unique ID of the subscription.
+         * @return a future
+         */
+        cancel: (state, user, uid) => module.post(
+            "subscription/{}/{}/cancel".format(user, uid), {}),
+
+        /**
+         * Pauses or resumes a subscription.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the child paying for the subscription.
+         * @param uid
+         *     The unique ID of the subscription.
+         * @param paused
+         *     Whether the subscription should be paused.
+         * @return a future
+         */
+        pause: (state, user, uid, paused) => module.post(
+            "subscription/{}/{}/pause".format(user, uid), {
+                paused,
+            }),
+    },
+
+    chore: {
+        /**
+         * Approves a chore marked as done, and pays the reward.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the chore.
+         * @return a future
+         */
+        approve: (state, uid) => module.post(
+            "chore/{}/{}/approve".format(state.family.uid, uid), {}),
+
+        /**
+         * Claims an open chore.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the chore.
+         * @return a future
+         */
+        claim: (state, uid) => module.post(
+            "chore/{}/{}/claim".format(state.family.uid, uid), {}),
+
+        /**
+         * Creates a chore.
+         *
+         * @param state
+         *     The application state.
+         * @param title
+         *     A short title.
+         * @param reward
+         *     The reward paid when the chore is approved.
+         * @param due
+         *     An optional due date.
+         * @param assignee
+         *     The unique ID of the child to assign the chore to, or
+         *     `undefined` to make it open to all children.
+         * @return a future
+         */
+        create: (state, title, reward, due, assignee) => module.post(
+            "chore/{}".format(state.family.uid), {
+                title,
+                reward,
+                due: due ? due : undefined,
+                assignee_uid: assignee ? assignee : undefined,
+            }),
+
+        /**
+         * Marks a chore as done.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the chore.
+         * @return a future
+         */
+        done: (state, uid) => module.post(
+            "chore/{}/{}/done".format(state.family.uid, uid), {}),
+
+        /**
+         * Creates a template from which chores are generated on a schedule.
+         *
+         * @param state
+         *     The application state.
+         * @param title
+         *     The title of the generated chores.
+         * @param reward
+         *     The reward of the generated chores.
+         * @param schedule
+         *     The schedule; `daily`, a weekday such as `Sat` or `monthly-`
+         *     followed by the day of the month.
+         * @param assignee
+         *     The unique ID of the child to assign the chores to, or
+         *     `undefined` to make them open to all children.
+         * @param penalty
+         *     The amount deducted from the assignee when a chore is missed.
+         * @param required
+         *     Whether the chores count towards allowance requirements.
+         * @return a future
+         */
+        schedule: (state, title, reward, schedule, assignee, penalty,
+                required) =>
+            module.post(
+                "chore/{}/template".format(state.family.uid), {
+                    title,
+                    reward,
+                    schedule,
+                    assignee_uid: assignee ? assignee : undefined,
+                    penalty: penalty ? penalty : undefined,
+                    required: !!required,
+                }),
+
+        /**
+         * Removes a chore template.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the template.
+         * @return a future
+         */
+        unschedule: (state, uid) => module.remove(
+            "chore/{}/template/{}".format(state.family.uid, uid)),
     },
 
     request: {
+        /**
+         * Comments on a pending request.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the user making the request.
+         * @param uid
+         *     The unique ID of the request.
+         * @param text
+         *     The comment text.
+         * @return a future
+         */
+        comment: (state, user, uid, text) => module.post(
+            "request/{}/{}/comment".format(user, uid), {
+                text,
+            }),
+
         /**
          * Declines a request.
          *
@@ -78,10 +592,14 @@ const module = {
          *     The unique ID of the user making the request.
          * @param uid
          *     The unique ID of the request.
+         * @param reason
+         *     An optional explanation of the decision.
          * @return a future
          */
-        decline: (state, user, uid) => module.remove(
-            "request/{}/{}".format(user, uid)),
+        decline: (state, user, uid, reason) => module.remove(
+            "request/{}/{}".format(user, uid), {
+                reason,
+            }),
 
         /**
          * Gets a specific request.
@@ -107,13 +625,66 @@ const module = {
          *     The unique ID of the request.
          * @param cost
          *     An optional cost to override the value in the request.
+         * @param contributions
+         *     An optional list of contributions towards the cost. Each
+         *     contribution is an object with `amount` and either `user_uid`
+         *     for family members or `name` for others.
          * @return a future
          */
-        grant: (state, user, uid, cost) => module.post(
+        grant: (state, user, uid, cost, contributions) => module.post(
             "request/{}/{}".format(user, uid), {
                 cost,
+                contributions: contributions || [],
             }),
 
+        /**
+         * Lists the request history of the family, including decided
+         * requests.
+         *
+         * @param state
+         *     The application state.
+         * @param filter
+         *     An object with the optional filters `user_uid`, `status`,
+         *     `since` and `until`.
+         * @param offset
+         *     The offset from the most recent request from which to read.
+         * @param limit
+         *     The maximum number of requests to return.
+         */
+        history: (state, filter, offset, limit) => module.get(
+            "requests/{}?{}".format(
+                state.family.uid,
+                new URLSearchParams(Object.fromEntries(
+                    Object.entries({...filter, offset, limit})
+                        .filter(([_, v]) => v !== undefined))))),
+
+        /**
+         * Edits a pending request made by the current child.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the request.
+         * @param values
+         *     An object with the new `name`, `description`, `amount` and
+         *     `url`. Absent values are left unchanged.
+         * @return a future
+         */
+        update: (state, uid, values) => module.put(
+            "request/{}/{}".format(state.me.uid, uid), values),
+
+        /**
+         * Withdraws a pending request made by the current child.
+         *
+         * @param state
+         *     The application state.
+         * @param uid
+         *     The unique ID of the request.
+         * @return a future
+         */
+        withdraw: (state, uid) => module.post(
+            "request/{}/{}/withdraw".format(state.me.uid, uid), {}),
+
         /**
          * Creates a request.
          *
@@ -127,15 +698,24 @@ const module = {
          *     The cost of the item.
          * @param url
          *     An optional URL with more information.
+         * @param category
+         *     An optional category, used for automatic approval.
+         * @param recurrence
+         *     An optional object on the format `{schedule, ends}` for
+         *     recurring requests, such as subscriptions. `ends` is optional.
          * @return a future
          */
-        make: (state, name, description, amount, url) => module.post(
-            "request/{}".format(state.me.uid), {
-                name,
-                description,
-                amount,
-                url: url ? url : undefined,
-            }),
+        make: (state, name, description, amount, url, category, recurrence) =>
+            module.post(
+                "request/{}".format(state.me.uid), {
+                    name,
+                    description,
+                    amount,
+                    url: url ? url : undefined,
+                    category: category ? category : undefined,
+                    schedule: recurrence?.schedule,
+                    ends: recurrence?.ends,
+                }),
     },
 
     /**
@@ -153,6 +733,8 @@ const module = {
             family.invitations = r.invitations;
             const me = family.members[state.me.uid];
             const balance = r.balances[state.me.uid];
+            const ledgers = listToMap(r.ledgers, "uid");
+            const ledgerBalances = r.ledger_balances[state.me.uid] || {};
             await state.update({
                 me: {
                     uid: me.uid,
@@ -164,13 +746,18 @@ const module = {
                     balance: balance !== undefined
                         ? {
                             num: balance,
-                            str: (
-                                r.currency.format[0]
-                                + balance
-                                + r.currency.format[1]),
+                            str: currencies.format(r.currency, balance),
                         }
                         : {},
+                    ledgers: Object.entries(ledgerBalances)
+                        .filter(([uid, _]) => uid in ledgers)
+                        .map(([uid, num]) => ({
+                            name: ledgers[uid].name,
+                            num,
+                            str: "{} {}".format(num, ledgers[uid].unit),
+                        })),
                 },
+                ledgers,
                 currency: r.currency,
                 family,
             }).store();
@@ -372,6 +959,22 @@ const module = {
                 await state.store();
                 return r;
             }),
+
+        /**
+         * Changes the settings of the family.
+         *
+         * @param state
+         *     The application state.
+         * @param settings
+         *     The settings to change, such as `approval_threshold`,
+         *     `approvals_required`, `request_reminder`, `request_expiry`,
+         *     `overdraft_limit` and `utc_offset`. The delays are given in
+         *     days, and the offset from UTC in minutes. Absent values are left
+         *     unchanged.
+         * @return a future
+         */
+        settings: (state, settings) => module.put(
+            "family/{}/settings".format(state.family.uid), settings),
     },
 
     invitation: {
@@ -472,12 +1075,108 @@ const module = {
          *     The new amount.
          * @param schedule
          *     The new schedule.
+         * @param requirement
+         *     An optional object on the format `{share, prorate}`, where
+         *     `share` is the percentage of required chores that must be done
+         *     for the allowance to be paid in full, and `prorate` whether a
+         *     prorated amount is paid otherwise. Pass `null` to make the
+         *     allowance unconditional.
+         * @param ageScale
+         *     An optional scale from which the amount is derived using the
+         *     age of the child, either on the format `base+step`, where
+         *     `step` is added per year of age, or a table on the format
+         *     `age:amount,age:amount`. Pass `null` for a fixed amount.
+         * @param change
+         *     An optional object on the format `{effective, prorate}`, where
+         *     `effective` is the time from which a new amount or schedule
+         *     applies, and `prorate` whether the next payment is prorated
+         *     between the previous and the new amount. Only the amount and
+         *     schedule may be changed in advance.
          */
-        allowance: (state, user, allowance, amount, schedule) => module.put(
-            "user/{}/allowance/{}".format(user, allowance), {
-                amount,
-                schedule,
+        allowance: (
+            state, user, allowance, amount, schedule, requirement, ageScale,
+            change,
+        ) => module.put(
+                "user/{}/allowance/{}".format(user, allowance), {
+                    amount,
+                    schedule,
+                    required_share: requirement === undefined
+                        ? undefined
+                        : requirement?.share ?? null,
+                    prorate: requirement?.prorate,
+                    age_scale: ageScale,
+                    effective: change?.effective,
+                    prorate_payment: change?.prorate,
+                }),
+
+        /**
+         * Lists the changes to the allowance for a child, including changes
+         * that have not taken effect yet.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the user.
+         * @param allowance
+         *     The unique ID of the allowance.
+         */
+        allowanceHistory: (state, user, allowance) => module.get(
+            "user/{}/allowance/{}/history".format(user, allowance)),
+
+        /**
+         * Updates the automatic approval rule for a child.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the user.
+         * @param rule
+         *     The rule on the format `{max_amount, categories, budget,
+         *     period}`, where `categories` is a list of strings, or `null` to
+         *     remove the rule.
+         */
+        autoApproval: (state, user, rule) => module.put(
+            "user/{}/auto-approval".format(user), {
+                rule,
+            }),
+
+        /**
+         * Updates the birth date of a family member, and the gift booked on
+         * the birthdays of a child.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the user.
+         * @param birthDate
+         *     The birth date on the format `YYYY-MM-DD`, or `null`.
+         * @param gift
+         *     The amount of the birthday gift, or `null` for no gift.
+         */
+        birthday: (state, user, birthDate, gift) => module.put(
+            "user/{}/birthday".format(user), {
+                birth_date: birthDate,
+                gift,
+            })
+            .then(async r => {
+                state.family.members[user] = r.user;
+                await state.store();
+                return r;
             }),
+
+        /**
+         * Replaces the jars of a child.
+         *
+         * @param state
+         *     The application state.
+         * @param user
+         *     The unique ID of the user.
+         * @param jars
+         *     A list of objects with the keys `name` and `percentage`. The
+         *     percentages must add up to 100.
+         */
+        jars: (state, user, jars) => module.put(
+            "user/{}/jars".format(user), {jars}),
     },
 
     /**
@@ -502,9 +1201,17 @@ const module = {
      *
      * This function automatically parses the response as JSON.
      */
-    remove: (resource) => req(resource, {
-        method: "DELETE",
-    }),
+    remove: (resource, data) => req(resource, data === undefined
+        ? {
+            method: "DELETE",
+        }
+        : {
+            method: "DELETE",
+            body: JSON.stringify(data),
+            headers: {
+                "Content-Type": "application/json",
+            },
+        }),
 
     /**
      * A wrapper for `fetch` with method `POST` taking `BASE_URL` into account.
@@ -0,0 +1,65 @@
+/**
+ * Formats an amount in a currency.
+ *
+ * @param currency
+ *     The currency, as returned by the server.
+ * @param amount
+ *     The amount, in the minor unit of the currency.
+ * @return a string
+ */
+export const format = (currency, amount) => {
+    const exponent = currency.exponent || 0;
+    const magnitude = Math.abs(amount);
+    const divisor = 10 ** exponent;
+    const major = Math.floor(magnitude / divisor).toString();
+    const minor = (magnitude % divisor).toString().padStart(exponent, "0");
+
+    let value = "";
+    for (let i = 0; i < major.length; i++) {
+        if (i > 0 && (major.length - i) % 3 === 0) {
+            value += currency.grouping_separator || "";
+        }
+        value += major[i];
+    }
+    if (exponent > 0) {
+        value += currency.decimal_separator + minor;
+    }
+
+    const [prefix, suffix] = currency.format;
+    if (amount >= 0) {
+        return prefix + value + suffix;
+    }
+    switch (currency.negative_style) {
+    case "inner":
+        return prefix + "-" + value + suffix;
+    case "parentheses":
+        return "(" + prefix + value + suffix + ")";
+    default:
+        return "-" + prefix + value + suffix;
+    }
+};
+
+/**
+ * Converts an amount to a value suitable for an input field.
+ *
+ * @param currency
+ *     The currency, as returned by the server.
+ * @param amount
+ *     The amount, in the minor unit of the currency.
+ * @return a string
+ */
+export const input = (currency, amount) => (
+    (amount / 10 ** (currency.exponent || 0))
+        .toFixed(currency.exponent || 0));
+
+/**
+ * Parses an amount entered by the user.
+ *
+ * @param currency
+ *     The currency, as returned by the server.
+ * @param value
+ *     The value entered, in the major unit of the currency.
+ * @return the amount in the minor unit of the currency
+ */
+export const parse = (currency, value) => Math.round(
+    parseFloat(value) * 10 ** (currency.exponent || 0));
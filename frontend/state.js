@@ -157,12 +157,18 @@ const TEMPLATE = {
         balance: {
             num: 0,
             str: "",
-        }
+        },
+        ledgers: [],
     },
     currency: {
         format: ["", ""],
         name: "",
+        exponent: 0,
+        decimal_separator: ".",
+        grouping_separator: "",
+        negative_style: "leading",
     },
+    ledgers: {},
     family: {
         members: {},
         invitations: [],
@@ -1,4 +1,5 @@
 import VIEWS from "./views.js";
+import * as currencies from "./currency.js";
 import { translate as _ } from "./translation.js";
 
 
@@ -401,10 +402,45 @@ export const applyState = (state) => {
  * @param amount
  *     The currency amount.
  */
-export const currency = (state, amount) => (
-    state.currency.format[0]
-    + new Intl.NumberFormat().format(amount)
-    + state.currency.format[1]);
+export const currency = (state, amount) => currencies.format(
+    state.currency, amount);
+
+/**
+ * Converts a numerical value in a ledger to a string.
+ *
+ * @param state
+ *     The application state.
+ * @param amount
+ *     The amount.
+ * @param ledger
+ *     The unique ID of the ledger, or null for the monetary ledger.
+ */
+export const ledgerValue = (state, amount, ledger) => {
+    const l = ledger ? state.ledgers[ledger] : undefined;
+    return l ? `${amount} ${l.unit}` : currency(state, amount);
+};
+
+/**
+ * Converts a currency amount to a value suitable for an input field.
+ *
+ * @param state
+ *     The application state.
+ * @param amount
+ *     The currency amount.
+ */
+export const currencyInput = (state, amount) => currencies.input(
+    state.currency, amount);
+
+/**
+ * Parses a currency amount entered by the user.
+ *
+ * @param state
+ *     The application state.
+ * @param value
+ *     The value entered.
+ */
+export const amount = (state, value) => currencies.parse(
+    state.currency, value);
 
 
 /**
@@ -448,6 +484,9 @@ export const transaction = async (state, transaction, buttons) => {
     case "allowance":
         description.innerText = _("Allowance on {}")
             .format(date(state, new Date(Date.parse(transaction.time))));
+        if (transaction.description) {
+            description.innerText += "\n" + transaction.description;
+        }
         kind.innerText = _("Allowance");
         break;
     case "gift":
@@ -458,6 +497,34 @@ export const transaction = async (state, transaction, buttons) => {
         description.innerText = transaction.description;
         kind.innerText = _("Fulfilled wish");
         break;
+    case "match":
+        description.innerText = transaction.description;
+        kind.innerText = _("Savings match");
+        break;
+    case "auto-approved":
+        description.innerText = transaction.description;
+        kind.innerText = _("Automatically granted wish");
+        break;
+    case "subscription":
+        description.innerText = transaction.description;
+        kind.innerText = _("Subscription");
+        break;
+    case "chore":
+        description.innerText = transaction.description;
+        kind.innerText = _("Chore");
+        break;
+    case "bonus":
+        description.innerText = transaction.description;
+        kind.innerText = _("Bonus");
+        break;
+    case "fine":
+        description.innerText = transaction.description;
+        kind.innerText = _("Fine");
+        break;
+    case "penalty":
+        description.innerText = transaction.description;
+        kind.innerText = _("Missed chore");
+        break;
     }
     time.innerText = timestamp(state, new Date(Date.parse(transaction.time)));
     amount.innerText = currency(state, transaction.amount);
@@ -489,7 +556,7 @@ export const transactionRow = (state, tr, t) => {
             ? _("Allowance")
             : t.description;
         description.onclick = () => transaction(state, t);
-        amount.innerText = currency(state, t.amount);
+        amount.innerText = ledgerValue(state, t.amount, t.ledger_uid);
     } else {
         description.innerHTML = "&nbsp;";
         description.onclick = () => {};
@@ -518,7 +585,7 @@ export const requestRow = (state, tr, r) => {
         description.innerText = r.name;
         description.onclick = () => location.hash =
             `#request/${r.user_uid}/${r.uid}`;
-        amount.innerText = currency(state, r.amount);
+        amount.innerText = ledgerValue(state, r.amount, r.ledger_uid);
     } else {
         description.innerHTML = "&nbsp;";
         description.onclick = () => {};
@@ -547,6 +614,20 @@ export const notification = {
     parse: (state, e) => {
         switch (e.type) {
         case "AllowanceUpdated":
+            if (e.by === null) {
+                return {
+                    action: () => {},
+                    title: _("Allowance changed"),
+                    body: (e.allowance.user_uid === state.me.uid
+                            ? _("Your allowance is now {amount}!")
+                            : _("{child}s allowance is now {amount}."))
+                        .format({
+                            amount: currency(state, e.allowance.amount),
+                            child: state.family.members[
+                                e.allowance.user_uid].name,
+                        }),
+                };
+            }
             return {
                 action: () => {},
                 title: _("Allowance changed"),
@@ -563,6 +644,17 @@ export const notification = {
                                 e.allowance.user_uid].name,
                         }),
             };
+        case "AllowanceChangeScheduled":
+            return {
+                action: () => {},
+                title: _("Allowance change scheduled"),
+                body: _("{parent} scheduled a new allowance of {amount} from {date}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        amount: currency(state, e.change.amount),
+                        date: new Date(e.change.effective).toLocaleDateString(),
+                    }),
+            };
         case "FamilyMemberInvited":
             return {
                 action: () => {},
@@ -603,6 +695,347 @@ export const notification = {
                         wish: e.request.name,
                     }),
             };
+        case "RequestUpdated":
+            return {
+                action: () => location.hash = "#request"
+                    + "." + e.request.user_uid
+                    + "." + e.request.uid,
+                title: _("Wish changed"),
+                body: _("{user} has changed a wish: {wish}.")
+                    .format({
+                        user: state.family.members[e.request.user_uid].name,
+                        wish: e.request.name,
+                    }),
+            };
+        case "SubscriptionCancelled":
+            return {
+                action: () => {},
+                title: _("Subscription cancelled"),
+                body: _("{user} cancelled the subscription: {name}.")
+                    .format({
+                        user: state.family.members[e.by].name,
+                        name: e.subscription.name,
+                    }),
+            };
+        case "SubscriptionPaused":
+            return {
+                action: () => {},
+                title: e.subscription.paused
+                    ? _("Subscription paused")
+                    : _("Subscription resumed"),
+                body: (e.subscription.paused
+                        ? _("{parent} paused the subscription: {name}.")
+                        : _("{parent} resumed the subscription: {name}."))
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        name: e.subscription.name,
+                    }),
+            };
+        case "ChoreCreated":
+            return {
+                action: () => {},
+                title: _("New chore"),
+                body: _("{parent} added a chore: {title}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        title: e.chore.title,
+                    }),
+            };
+        case "ChoreClaimed":
+            return {
+                action: () => {},
+                title: _("Chore claimed"),
+                body: _("{user} will do the chore: {title}.")
+                    .format({
+                        user: state.family.members[e.by].name,
+                        title: e.chore.title,
+                    }),
+            };
+        case "ChoreDone":
+            return {
+                action: () => {},
+                title: _("Chore done"),
+                body: _("{user} has done the chore: {title}.")
+                    .format({
+                        user: state.family.members[e.by].name,
+                        title: e.chore.title,
+                    }),
+            };
+        case "ChoreApproved":
+            return {
+                action: () => {},
+                title: _("Chore approved"),
+                body: _("{parent} approved the chore: {title}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        title: e.chore.title,
+                    }),
+            };
+        case "BonusAwarded":
+            return {
+                action: () => {},
+                title: _("Streak completed!"),
+                body: (e.rule.user_uid === state.me.uid
+                        ? _("Well done! You earned a bonus of {amount}: {name}.")
+                        : _("{child} earned a bonus of {amount}: {name}."))
+                    .format({
+                        child: state.family.members[e.rule.user_uid].name,
+                        amount: currency(state, e.transaction.amount),
+                        name: e.rule.name,
+                    }),
+            };
+        case "BonusRuleCreated":
+            return {
+                action: () => {},
+                title: _("New bonus"),
+                body: _("{parent} added a bonus: {name}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        name: e.rule.name,
+                    }),
+            };
+        case "BonusRuleRemoved":
+            return {
+                action: () => {},
+                title: _("Bonus removed"),
+                body: _("{parent} removed a bonus: {name}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        name: e.rule.name,
+                    }),
+            };
+        case "FineApplied":
+            return {
+                action: () => {},
+                title: _("Fine"),
+                body: (e.transaction.user_uid === state.me.uid
+                        ? _("You were fined {amount}: {name}.")
+                        : _("{child} was fined {amount}: {name}."))
+                    .format({
+                        child: state.family.members[e.transaction.user_uid].name,
+                        amount: currency(state, -e.transaction.amount),
+                        name: e.reason.name,
+                    }),
+            };
+        case "FineReasonCreated":
+            return {
+                action: () => {},
+                title: _("New reason for fines"),
+                body: _("{parent} added a reason for fines: {name}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        name: e.reason.name,
+                    }),
+            };
+        case "FineReasonRemoved":
+            return {
+                action: () => {},
+                title: _("Reason for fines removed"),
+                body: _("{parent} removed a reason for fines: {name}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        name: e.reason.name,
+                    }),
+            };
+        case "GiftLinkCreated":
+            return {
+                action: () => {},
+                title: _("Gift link created"),
+                body: _("{parent} created a gift link for {child}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        child: state.family.members[e.link.user_uid].name,
+                    }),
+            };
+        case "GiftLinkRecorded":
+            return {
+                action: () => {},
+                title: _("Gift announced"),
+                body: _("{giver} announced a gift of {amount} for {child}.")
+                    .format({
+                        giver: e.link.giver,
+                        amount: currency(state, e.link.amount),
+                        child: state.family.members[e.link.user_uid].name,
+                    }),
+            };
+        case "GiftLinkRemoved":
+            return {
+                action: () => {},
+                title: _("Gift link removed"),
+                body: _("{parent} removed a gift link for {child}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        child: state.family.members[e.link.user_uid].name,
+                    }),
+            };
+        case "GiftReceived":
+            return {
+                action: () => {},
+                title: _("Gift received"),
+                body: (e.link.user_uid === state.me.uid
+                        ? _("You received {amount} from {giver}!")
+                        : _("{child} received {amount} from {giver}."))
+                    .format({
+                        amount: currency(state, e.transactions.reduce(
+                            (sum, t) => sum + t.amount, 0)),
+                        giver: e.link.giver,
+                        child: state.family.members[e.link.user_uid].name,
+                    }),
+            };
+        case "PendingTransactionCreated":
+            return {
+                action: () => {},
+                title: _("Transaction scheduled"),
+                body: _("{parent} scheduled {amount} for {child}: {description}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        amount: currency(state, e.pending.amount),
+                        child: state.family.members[e.pending.user_uid].name,
+                        description: e.pending.description,
+                    }),
+            };
+        case "PendingTransactionUpdated":
+            return {
+                action: () => {},
+                title: _("Scheduled transaction changed"),
+                body: _("{parent} changed a scheduled transaction: {description}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        description: e.pending.description,
+                    }),
+            };
+        case "PendingTransactionCancelled":
+            return {
+                action: () => {},
+                title: _("Scheduled transaction cancelled"),
+                body: _("{parent} cancelled a scheduled transaction: {description}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        description: e.pending.description,
+                    }),
+            };
+        case "PendingTransactionBooked":
+            return {
+                action: () => {},
+                title: _("Scheduled transaction booked"),
+                body: _("{amount} was booked for {child}: {description}.")
+                    .format({
+                        amount: currency(state, e.pending.amount),
+                        child: state.family.members[e.pending.user_uid].name,
+                        description: e.pending.description,
+                    }),
+            };
+        case "TransactionsBooked":
+            return {
+                action: () => {},
+                title: _("Transaction booked"),
+                body: _("{parent} booked {amount} for you: {description}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        amount: currency(state, e.transactions.reduce(
+                            (sum, t) => sum + t.amount, 0)),
+                        description: e.transactions[0]?.description,
+                    }),
+            };
+        case "Birthday":
+            return {
+                action: () => {},
+                title: _("Happy birthday!"),
+                body: (e.user_uid === state.me.uid
+                        ? _("Happy birthday! You turn {age} today.")
+                        : _("{child} turns {age} today!"))
+                    .format({
+                        child: state.family.members[e.user_uid].name,
+                        age: e.age,
+                    }),
+            };
+        case "BirthdayUpdated":
+            return {
+                action: () => {},
+                title: _("Birthday changed"),
+                body: _("{parent} changed the birthday of {user}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        user: e.user.name,
+                    }),
+            };
+        case "ChoreMissed":
+            return {
+                action: () => {},
+                title: _("Chore missed"),
+                body: _("Nobody did the chore in time: {title}.")
+                    .format({
+                        title: e.chore.title,
+                    }),
+            };
+        case "ChoresGenerated":
+            return {
+                action: () => {},
+                title: _("New chores"),
+                body: e.chores.map((chore) => chore.title).join(", "),
+            };
+        case "ChoreTemplateCreated":
+            return {
+                action: () => {},
+                title: _("Recurring chore added"),
+                body: _("{parent} added a recurring chore: {title}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        title: e.template.title,
+                    }),
+            };
+        case "ChoreTemplateRemoved":
+            return {
+                action: () => {},
+                title: _("Recurring chore removed"),
+                body: _("{parent} removed a recurring chore: {title}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        title: e.template.title,
+                    }),
+            };
+        case "RequestExpired":
+            return {
+                action: () => {},
+                title: _("Wish expired"),
+                body: _("{user}s wish was not answered in time: {wish}.")
+                    .format({
+                        user: state.family.members[e.request.user_uid].name,
+                        wish: e.request.name,
+                    }),
+            };
+        case "RequestReminder":
+            return {
+                action: () => location.hash = "#",
+                title: _("Wishes are waiting"),
+                body: _("{count} wishes are waiting for your decision.")
+                    .format({
+                        count: e.requests.length,
+                    }),
+            };
+        case "RequestWithdrawn":
+            return {
+                action: () => {},
+                title: _("Wish withdrawn"),
+                body: _("{user} has withdrawn a wish: {wish}.")
+                    .format({
+                        user: state.family.members[e.request.user_uid].name,
+                        wish: e.request.name,
+                    }),
+            };
+        case "RequestApproved":
+            return {
+                action: () => location.hash = "#request"
+                    + "." + e.request.user_uid
+                    + "." + e.request.uid,
+                title: _("Wish needs your approval"),
+                body: _("{parent} approved {user}s wish: {wish}.")
+                    .format({
+                        parent: state.family.members[e.by].name,
+                        user: state.family.members[e.request.user_uid].name,
+                        wish: e.request.name,
+                    }),
+            };
         case "RequestGranted":
             return {
                 action: () => {},
@@ -613,14 +1046,50 @@ export const notification = {
                         wish: e.request.name,
                     }),
             };
+        case "RequestAutoApproved":
+            return {
+                action: () => {},
+                title: _("Wish granted automatically"),
+                body: _("{user}s wish was granted automatically: {wish}.")
+                    .format({
+                        user: state.family.members[e.request.user_uid].name,
+                        wish: e.request.name,
+                    }),
+            };
+        case "AutoApprovalUpdated":
+            return {
+                action: () => {},
+                title: _("Automatic approval changed"),
+                body: (e.rule
+                        ? _("{parent} changed which wishes are granted automatically.")
+                        : _("{parent} turned off automatic approval of wishes."))
+                    .format({
+                        parent: state.family.members[e.by].name,
+                    }),
+            };
         case "RequestDeclined":
             return {
                 action: () => {},
                 title: _("Your wish has been declined"),
-                body: _("{parent} declined your wish: {wish}.")
+                body: (e.request.reason
+                        ? _("{parent} declined your wish: {wish}. {reason}")
+                        : _("{parent} declined your wish: {wish}."))
                     .format({
                         parent: state.family.members[e.by].name,
                         wish: e.request.name,
+                        reason: e.request.reason,
+                    }),
+            };
+        case "RequestCommented":
+            return {
+                action: () => location.hash = "#request"
+                    + "." + e.comment.user_uid
+                    + "." + e.comment.request_uid,
+                title: _("New comment on a wish"),
+                body: _("{user}: {text}")
+                    .format({
+                        user: state.family.members[e.by].name,
+                        text: e.comment.text,
                     }),
             };
         }
@@ -53,7 +53,7 @@ export default {
                 data.get("password"),
                 data.get("user-role") === "child"
                     ? {
-                        amount: parseInt(data.get("allowance-amount")),
+                        amount: ui.amount(state, data.get("allowance-amount")),
                         schedule: data.get("allowance-schedule"),
                     }
                     : undefined,
@@ -43,7 +43,7 @@ export default {
                 data.get("user-email"),
                 data.get("user-role") === "child"
                     ? {
-                        amount: parseInt(data.get("allowance-amount")),
+                        amount: ui.amount(state, data.get("allowance-amount")),
                         schedule: data.get("allowance-schedule"),
                     }
                     : undefined,
@@ -18,7 +18,7 @@ export default {
                     state,
                     data.get("name"),
                     data.get("description"),
-                    parseInt(data.get("amount")),
+                    ui.amount(state, data.get("amount")),
                     data.get("url"));
                 ui.applyState(state);
                 location.hash = "#overview";
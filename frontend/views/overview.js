@@ -96,7 +96,7 @@ export default {
             switch (r) {
             case "yes":
                 return {
-                    amount: parseInt(amount.value),
+                    amount: ui.amount(state, amount.value),
                     description: description.value,
                 }
             case "no":
@@ -105,6 +105,45 @@ export default {
         };
 
         if (state.me.role === "child") {
+            const [ledgerTemplate, ledgerTarget] = ui.extractElement(
+                view.doc, "#my-ledgers template");
+            state.account.ledgers.forEach((l) => {
+                const item = ledgerTemplate.content.cloneNode(true);
+                const [text] = ui.managed(item);
+                text.innerText = _("{name}: {amount}").format({
+                    name: l.name,
+                    amount: l.str,
+                });
+                ledgerTarget.appendChild(item);
+            });
+            const myJars = view.context.jars[state.me.uid];
+            (myJars ? myJars.jars : []).forEach((j) => {
+                const item = ledgerTemplate.content.cloneNode(true);
+                const [text] = ui.managed(item);
+                text.innerText = _("{name}: {amount}").format({
+                    name: j.name,
+                    amount: ui.currency(state, myJars.balances[j.uid] || 0),
+                });
+                ledgerTarget.appendChild(item);
+            });
+            const [goalTemplate, goalTarget] = ui.extractElement(
+                view.doc, "#my-goals template");
+            (view.context.goals[state.me.uid] || []).forEach((p) => {
+                const item = goalTemplate.content.cloneNode(true);
+                const [text] = ui.managed(item);
+                const progress = _("{name}: {saved} of {amount}").format({
+                    name: p.goal.name,
+                    saved: ui.currency(state, p.saved),
+                    amount: ui.currency(state, p.goal.amount),
+                });
+                text.innerText = p.projected
+                    ? _("{progress}, expected by {date}").format({
+                        progress,
+                        date: new Date(p.projected).toLocaleDateString(),
+                    })
+                    : progress;
+                goalTarget.appendChild(item);
+            });
             childTable(
                 "#my-transactions template",
                 view.context.transactions,
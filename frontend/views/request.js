@@ -7,7 +7,8 @@ export default {
     initialize: async (state, user_uid, request_uid) => {
         const {request} = await api.request.get(state, user_uid, request_uid);
         const user = state.family.members[request.user_uid];
-        return {request, user};
+        const cost = ui.currencyInput(state, request.amount);
+        return {request, user, cost};
     },
 
     show: async (view, state) => {
@@ -29,7 +30,7 @@ export default {
                 ]);
             switch (r) {
             case "yes":
-                return parseInt(amount.value);
+                return ui.amount(state, amount.value);
             case "no":
                 return undefined;
             }
@@ -47,10 +48,16 @@ export default {
         remove.addEventListener("click", async () => {
             if (await queryRemove()) {
                 try {
-                    await api.request.decline(
-                        state,
-                        view.context.request.user_uid,
-                        view.context.request.uid);
+                    if (state.me.role === "child") {
+                        await api.request.withdraw(
+                            state,
+                            view.context.request.uid);
+                    } else {
+                        await api.request.decline(
+                            state,
+                            view.context.request.user_uid,
+                            view.context.request.uid);
+                    }
                     location.hash = "#overview";
                 } catch(e) {
                     ui.applyState(state);
@@ -11,7 +11,17 @@ const ALLOWANCE_UPDATE_TIMEOUT = 2000;
 
 export default {
     initialize: async (state, user_uid) => {
-        return await api.user.get(state, user_uid);
+        const r = await api.user.get(state, user_uid);
+        return {
+            ...r,
+            amounts: r.allowance
+                ? {
+                    allowance: ui.currency(state, r.allowance.amount),
+                    allowanceInput: ui.currencyInput(
+                        state, r.allowance.amount),
+                }
+                : {},
+        };
     },
 
     show: async (view, state) => {
@@ -70,7 +80,8 @@ export default {
                                 state,
                                 view.context.user.uid,
                                 view.context.allowance.uid,
-                                parseInt(data.get("allowance-amount")),
+                                ui.amount(
+                                    state, data.get("allowance-amount")),
                                 data.get("allowance-schedule"));
                         } catch (e) {
                             // Ignore